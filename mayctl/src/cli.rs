@@ -1,5 +1,7 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum, error::ErrorKind};
 use mayland_comm::{Action, Request};
+use mayland_config::outputs::Mode;
+use std::{ffi::OsString, path::PathBuf};
 
 #[derive(Debug, Parser)]
 #[clap(version, about)]
@@ -21,7 +23,84 @@ pub struct Cli {
 	version: Option<bool>,
 }
 
-#[derive(Debug, Subcommand)]
+impl Cli {
+	/// parse the command line arguments into the request to send
+	///
+	/// dispatches can be chained with `--`, which sends them as a single batch,
+	/// e.g. `mayctl dispatch workspace 3 -- spawn kitty`
+	pub fn parse_request() -> (Cli, Request) {
+		let args = std::env::args_os().collect::<Vec<_>>();
+		let mut chunks = split_chain(args).into_iter();
+
+		let cli = Cli::parse_from(chunks.next().unwrap());
+		let chained = chunks
+			.map(|chunk| {
+				let args = std::iter::once(OsString::from("mayctl dispatch")).chain(chunk.iter().cloned());
+				Action::from(Chained::parse_from(args).dispatch)
+			})
+			.collect::<Vec<_>>();
+
+		if chained.is_empty() {
+			let request = Request::from(cli.cmd.clone());
+			return (cli, request);
+		}
+
+		let Cmd::Dispatch { dispatch } = cli.cmd.clone() else {
+			let mut cmd = Cli::command();
+			cmd.error(
+				ErrorKind::ArgumentConflict,
+				"only dispatches can be chained with `--`",
+			)
+			.exit();
+		};
+
+		let actions = std::iter::once(Action::from(dispatch)).chain(chained).collect();
+		(cli, Request::Batch(actions))
+	}
+}
+
+/// split the arguments at every `--` that chains another dispatch
+///
+/// the arguments of a spawn can contain `--` themselves,
+/// so a spawn always ends the chain
+fn split_chain(args: Vec<OsString>) -> Vec<Vec<OsString>> {
+	let mut chunks = vec![Vec::new()];
+	for arg in args {
+		let first = chunks.len() == 1;
+		let chunk = chunks.last_mut().unwrap();
+
+		if arg == "--" && !is_spawn(chunk, first) {
+			chunks.push(Vec::new());
+		} else {
+			chunk.push(arg);
+		}
+	}
+
+	chunks
+}
+
+/// is the dispatch of the chunk a spawn?
+fn is_spawn(chunk: &[OsString], first: bool) -> bool {
+	let mut positional = (chunk.iter()).filter(|arg| !arg.as_encoded_bytes().starts_with(b"-"));
+
+	// the first chunk starts with `mayctl dispatch`
+	let dispatch = if first {
+		positional.nth(2)
+	} else {
+		positional.next()
+	};
+	dispatch.is_some_and(|dispatch| dispatch == "spawn")
+}
+
+/// a dispatch chained after `--`
+#[derive(Debug, Parser)]
+#[clap(disable_help_subcommand = true)]
+struct Chained {
+	#[command(subcommand)]
+	dispatch: Dispatch,
+}
+
+#[derive(Debug, Clone, Subcommand)]
 pub enum Cmd {
 	/// issue a dispatch to the compositor
 	Dispatch {
@@ -38,26 +117,86 @@ pub enum Cmd {
 	Windows,
 	/// request workspace info from the compositor
 	Workspaces,
+	/// take a screenshot of an output
+	Screenshot {
+		/// the output to take a screenshot of, defaults to the active output
+		#[arg(long)]
+		output: Option<String>,
+		/// the file to save the screenshot to, defaults to the screenshot directory
+		#[arg(short = 'o', long)]
+		file: Option<PathBuf>,
+	},
+	/// configure an output until the config is reloaded
+	Output {
+		/// the connector of the output
+		name: String,
+		/// the mode, as "<width>x<height>" or "<width>x<height>@<refresh-rate>"
+		#[arg(long)]
+		mode: Option<Mode>,
+		/// the logical position
+		#[arg(long, num_args = 2, value_names = ["X", "Y"], allow_negative_numbers = true)]
+		position: Option<Vec<i32>>,
+		/// turn the output on and make it the active output, or turn it off
+		#[arg(long, value_name = "BOOL")]
+		active: Option<bool>,
+		/// the output scale
+		#[arg(long)]
+		scale: Option<f64>,
+		/// the output transform
+		#[arg(long, value_enum)]
+		transform: Option<Transform>,
+	},
+	/// subscribe to the compositor event stream
+	Subscribe,
 }
 
-#[derive(Debug, Subcommand)]
+#[derive(Debug, Clone, Subcommand)]
 pub enum Dispatch {
 	/// issue a dispatch to quit the compositor
 	Quit,
 
+	/// turn off all outputs
+	PowerOff,
+	/// turn all outputs back on
+	PowerOn,
+
+	/// take a screenshot
+	Screenshot {
+		#[arg(value_enum)]
+		target: ScreenshotTarget,
+	},
+
 	/// close active window
 	#[clap(name = "close", visible_alias = "close-window")]
 	CloseWindow,
 	/// toggle floating status of active window
 	ToggleFloating,
+	/// toggle fullscreen status of active window
+	ToggleFullscreen,
+	/// toggle maximized status of active window
+	ToggleMaximize,
 	/// cycle through windows
 	Cycle {
 		#[arg(value_enum)]
 		direction: CycleDirection,
 	},
+	/// switch to the next or previous workspace
+	CycleWorkspace {
+		#[arg(value_enum)]
+		direction: CycleDirection,
+	},
+
+	/// grow the tiling split
+	GrowSplit,
+	/// shrink the tiling split
+	ShrinkSplit,
 
 	/// switch to another workspace
 	Workspace { workspace: usize },
+	/// move active window to another workspace
+	MoveToWorkspace { workspace: usize },
+	/// move active window to another workspace and switch to it
+	MoveToWorkspaceAndFollow { workspace: usize },
 
 	/// spawn command
 	Spawn {
@@ -66,6 +205,31 @@ pub enum Dispatch {
 	},
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ScreenshotTarget {
+	Output,
+	Window,
+	Region,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Transform {
+	Normal,
+	#[value(name = "90")]
+	_90,
+	#[value(name = "180")]
+	_180,
+	#[value(name = "270")]
+	_270,
+	Flipped,
+	#[value(name = "flipped-90")]
+	Flipped90,
+	#[value(name = "flipped-180")]
+	Flipped180,
+	#[value(name = "flipped-270")]
+	Flipped270,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum CycleDirection {
 	Next,
@@ -81,6 +245,27 @@ impl From<Cmd> for Request {
 			Cmd::Outputs => Request::Outputs,
 			Cmd::Windows => Request::Windows,
 			Cmd::Workspaces => Request::Workspaces,
+			Cmd::Screenshot { output, file } => Request::Screenshot {
+				output,
+				// mayland doesn't share the working directory of mayctl
+				path: file.map(|file| std::path::absolute(&file).unwrap_or(file)),
+			},
+			Cmd::Output {
+				name,
+				mode,
+				position,
+				active,
+				scale,
+				transform,
+			} => Request::ConfigureOutput {
+				name,
+				mode: mode.map(mayland_comm::output::TargetMode::from),
+				position: position.map(|position| [position[0], position[1]]),
+				active,
+				scale,
+				transform: transform.map(mayland_comm::output::Transform::from),
+			},
+			Cmd::Subscribe => Request::Subscribe,
 		}
 	}
 }
@@ -90,13 +275,30 @@ impl From<Dispatch> for Action {
 		match value {
 			Dispatch::Quit => Action::Quit,
 
+			Dispatch::PowerOff => Action::PowerOff,
+			Dispatch::PowerOn => Action::PowerOn,
+
+			Dispatch::Screenshot { target } => {
+				Action::Screenshot(mayland_comm::action::ScreenshotTarget::from(target))
+			}
+
 			Dispatch::CloseWindow => Action::CloseWindow,
 			Dispatch::ToggleFloating => Action::ToggleFloating,
+			Dispatch::ToggleFullscreen => Action::ToggleFullscreen,
+			Dispatch::ToggleMaximize => Action::ToggleMaximize,
 			Dispatch::Cycle { direction } => {
 				Action::Cycle(mayland_comm::action::CycleDirection::from(direction))
 			}
+			Dispatch::CycleWorkspace { direction } => {
+				Action::CycleWorkspace(mayland_comm::action::CycleDirection::from(direction))
+			}
+
+			Dispatch::GrowSplit => Action::GrowSplit,
+			Dispatch::ShrinkSplit => Action::ShrinkSplit,
 
 			Dispatch::Workspace { workspace } => Action::Workspace(workspace),
+			Dispatch::MoveToWorkspace { workspace } => Action::MoveToWorkspace(workspace),
+			Dispatch::MoveToWorkspaceAndFollow { workspace } => Action::MoveToWorkspaceAndFollow(workspace),
 
 			Dispatch::Spawn { spawn } => Action::Spawn(spawn),
 		}
@@ -111,19 +313,71 @@ impl From<Action> for Dispatch {
 		match value {
 			Action::Quit => Dispatch::Quit,
 
+			Action::PowerOff => Dispatch::PowerOff,
+			Action::PowerOn => Dispatch::PowerOn,
+
+			Action::Screenshot(target) => Dispatch::Screenshot {
+				target: ScreenshotTarget::from(target),
+			},
+
 			Action::CloseWindow => Dispatch::CloseWindow,
 			Action::ToggleFloating => Dispatch::ToggleFloating,
+			Action::ToggleFullscreen => Dispatch::ToggleFullscreen,
+			Action::ToggleMaximize => Dispatch::ToggleMaximize,
 			Action::Cycle(direction) => Dispatch::Cycle {
 				direction: CycleDirection::from(direction),
 			},
+			Action::CycleWorkspace(direction) => Dispatch::CycleWorkspace {
+				direction: CycleDirection::from(direction),
+			},
+
+			Action::GrowSplit => Dispatch::GrowSplit,
+			Action::ShrinkSplit => Dispatch::ShrinkSplit,
 
 			Action::Workspace(workspace) => Dispatch::Workspace { workspace },
+			Action::MoveToWorkspace(workspace) => Dispatch::MoveToWorkspace { workspace },
+			Action::MoveToWorkspaceAndFollow(workspace) => Dispatch::MoveToWorkspaceAndFollow { workspace },
 
 			Action::Spawn(spawn) => Dispatch::Spawn { spawn },
 		}
 	}
 }
 
+impl From<ScreenshotTarget> for mayland_comm::action::ScreenshotTarget {
+	fn from(value: ScreenshotTarget) -> Self {
+		match value {
+			ScreenshotTarget::Output => mayland_comm::action::ScreenshotTarget::Output,
+			ScreenshotTarget::Window => mayland_comm::action::ScreenshotTarget::Window,
+			ScreenshotTarget::Region => mayland_comm::action::ScreenshotTarget::Region,
+		}
+	}
+}
+
+impl From<mayland_comm::action::ScreenshotTarget> for ScreenshotTarget {
+	fn from(value: mayland_comm::action::ScreenshotTarget) -> Self {
+		match value {
+			mayland_comm::action::ScreenshotTarget::Output => ScreenshotTarget::Output,
+			mayland_comm::action::ScreenshotTarget::Window => ScreenshotTarget::Window,
+			mayland_comm::action::ScreenshotTarget::Region => ScreenshotTarget::Region,
+		}
+	}
+}
+
+impl From<Transform> for mayland_comm::output::Transform {
+	fn from(value: Transform) -> Self {
+		match value {
+			Transform::Normal => mayland_comm::output::Transform::Normal,
+			Transform::_90 => mayland_comm::output::Transform::_90,
+			Transform::_180 => mayland_comm::output::Transform::_180,
+			Transform::_270 => mayland_comm::output::Transform::_270,
+			Transform::Flipped => mayland_comm::output::Transform::Flipped,
+			Transform::Flipped90 => mayland_comm::output::Transform::Flipped90,
+			Transform::Flipped180 => mayland_comm::output::Transform::Flipped180,
+			Transform::Flipped270 => mayland_comm::output::Transform::Flipped270,
+		}
+	}
+}
+
 impl From<CycleDirection> for mayland_comm::action::CycleDirection {
 	fn from(value: CycleDirection) -> Self {
 		match value {
@@ -144,8 +398,9 @@ impl From<mayland_comm::action::CycleDirection> for CycleDirection {
 
 #[test]
 fn clap() {
-	use clap::CommandFactory;
-
 	let cmd = Cli::command();
 	cmd.debug_assert();
+
+	let cmd = Chained::command();
+	cmd.debug_assert();
 }
@@ -2,8 +2,8 @@ use self::{
 	cli::Cli,
 	term::{Term, ensure_matches, unexpected},
 };
-use clap::{CommandFactory, Parser};
-use mayland_comm::{MAYLAND_SOCKET_VAR, Request, Response};
+use clap::CommandFactory;
+use mayland_comm::{Event, MAYLAND_SOCKET_VAR, Request, Response};
 use serde::Serialize;
 use std::{
 	fmt::Display,
@@ -18,12 +18,11 @@ mod term;
 
 fn main() -> Term {
 	clap_complete::CompleteEnv::with_factory(Cli::command).complete();
-	let cli = Cli::parse();
+	let (cli, request) = Cli::parse_request();
 	let Some(socket_path) = std::env::var_os(MAYLAND_SOCKET_VAR).map(PathBuf::from) else {
 		return Term::MaylandNotRunning;
 	};
 
-	let request = Request::from(cli.cmd);
 	let message = serde_json::to_vec(&request).unwrap();
 
 	let mut stream = match UnixStream::connect(&socket_path) {
@@ -43,14 +42,20 @@ fn main() -> Term {
 		Ok(response) => response,
 		Err(err) => return Term::InvalidResponse(err),
 	};
-	stream.shutdown(Shutdown::Read).unwrap();
 
 	if let Response::Err(err) = response {
 		return Term::from(err);
 	}
 
+	if let Request::Subscribe = request {
+		ensure_matches!(response, Response::Subscribe, "subscribe");
+		return subscribe(read, socket_path, cli.json);
+	}
+
+	stream.shutdown(Shutdown::Read).unwrap();
+
 	match request {
-		Request::Dispatch(_) => {
+		Request::Dispatch(_) | Request::Batch(_) => {
 			ensure_matches!(response, Response::Dispatch, "dispatch");
 			println!("ok dispatch");
 		}
@@ -102,11 +107,52 @@ fn main() -> Term {
 				prettify(&workspaces);
 			}
 		}
+		Request::Screenshot { .. } => {
+			let Response::Screenshot(path) = response else {
+				unexpected!(response, "screenshot")
+			};
+
+			if cli.json {
+				stringify(&path);
+			} else {
+				println!("ok screenshot {}", path.display());
+			}
+		}
+		Request::ConfigureOutput { .. } => {
+			ensure_matches!(response, Response::ConfigureOutput, "configure output");
+			println!("ok output");
+		}
+		Request::Subscribe => unreachable!("subscribe is handled above"),
 	}
 
 	Term::Ok
 }
 
+/// print every event that mayland sends until the connection is closed
+fn subscribe(mut read: BufReader<&mut UnixStream>, socket_path: PathBuf, json: bool) -> Term {
+	let mut buf = String::new();
+	loop {
+		buf.clear();
+		match read.read_line(&mut buf) {
+			Ok(0) => return Term::Ok,
+			Ok(_) => {}
+			Err(err) => return Term::IoError(socket_path, err),
+		}
+
+		let event = match serde_json::from_str::<Event>(&buf) {
+			Ok(event) => event,
+			Err(err) => return Term::InvalidResponse(err),
+		};
+
+		if json {
+			// keep one event per line, so that the output can be piped
+			println!("{}", serde_json::to_string(&event).unwrap());
+		} else {
+			print!("{event}");
+		}
+	}
+}
+
 fn prettify<T: Display>(t: &[T]) {
 	for (i, t) in t.iter().enumerate() {
 		if i != 0 {
@@ -206,5 +206,8 @@ pub(crate) fn get_response_name(response: &Response) -> &'static str {
 		Response::Outputs(_) => "outputs",
 		Response::Windows(_) => "windows",
 		Response::Workspaces(_) => "workspaces",
+		Response::Screenshot(_) => "screenshot",
+		Response::ConfigureOutput => "configure output",
+		Response::Subscribe => "subscribe",
 	}
 }
@@ -16,6 +16,26 @@ pub enum Action {
 	/// ```
 	Quit,
 
+	/// turn off all outputs
+	///
+	/// ```json
+	/// { "tag": "power_off" }
+	/// ```
+	PowerOff,
+	/// turn all outputs back on
+	///
+	/// ```json
+	/// { "tag": "power_on" }
+	/// ```
+	PowerOn,
+
+	/// take a screenshot and save it to the screenshot directory
+	///
+	/// ```json
+	/// { "tag": "screenshot", "val": "output" }
+	/// ```
+	Screenshot(ScreenshotTarget),
+
 	/// close the currently focussed window
 	///
 	/// ```json
@@ -28,12 +48,43 @@ pub enum Action {
 	/// { "tag": "toggle_floating" }
 	/// ```
 	ToggleFloating,
+	/// toggle the fullscreen state of the currently focussed window
+	///
+	/// ```json
+	/// { "tag": "toggle_fullscreen" }
+	/// ```
+	ToggleFullscreen,
+	/// toggle the maximized state of the currently focussed window
+	///
+	/// ```json
+	/// { "tag": "toggle_maximize" }
+	/// ```
+	ToggleMaximize,
 	/// cycle the active window
 	///
 	/// ```json
 	/// { "tag": "cycle", "val": "next" }
 	/// ```
 	Cycle(CycleDirection),
+	/// switch to the next or previous workspace
+	///
+	/// ```json
+	/// { "tag": "cycle_workspace", "val": "next" }
+	/// ```
+	CycleWorkspace(CycleDirection),
+
+	/// grow the master window of the tiling layout
+	///
+	/// ```json
+	/// { "tag": "grow_split" }
+	/// ```
+	GrowSplit,
+	/// shrink the master window of the tiling layout
+	///
+	/// ```json
+	/// { "tag": "shrink_split" }
+	/// ```
+	ShrinkSplit,
 
 	/// switch to a workspace
 	///
@@ -41,6 +92,18 @@ pub enum Action {
 	/// { "tag": "workspace", "val": 2 }
 	/// ```
 	Workspace(usize),
+	/// move the currently focussed window to a workspace
+	///
+	/// ```json
+	/// { "tag": "move_to_workspace", "val": 2 }
+	/// ```
+	MoveToWorkspace(usize),
+	/// move the currently focussed window to a workspace and switch to it
+	///
+	/// ```json
+	/// { "tag": "move_to_workspace_and_follow", "val": 2 }
+	/// ```
+	MoveToWorkspaceAndFollow(usize),
 
 	/// spawn a command
 	///
@@ -50,12 +113,24 @@ pub enum Action {
 	Spawn(Vec<String>),
 }
 
-/// the direction to cycle windows in
+/// the direction to cycle windows or workspaces in
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CycleDirection {
-	/// focus the next window
+	/// focus the next window or workspace
 	Next,
-	/// focus the previous window
+	/// focus the previous window or workspace
 	Prev,
 }
+
+/// what to take a screenshot of
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenshotTarget {
+	/// the active output
+	Output,
+	/// the currently focussed window
+	Window,
+	/// a region that is selected with the pointer
+	Region,
+}
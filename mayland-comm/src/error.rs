@@ -10,6 +10,12 @@ pub enum Error {
 	InvalidRequest,
 	/// the config couldn't be read
 	FailedToReadConfig(PathBuf),
+	/// no output with the name exists
+	OutputNotFound(String),
+	/// the screenshot couldn't be taken or saved
+	ScreenshotFailed(String),
+	/// the output config couldn't be applied
+	InvalidOutputConfig(String),
 }
 
 impl std::error::Error for Error {}
@@ -19,6 +25,9 @@ impl Display for Error {
 		match self {
 			Error::InvalidRequest => write!(f, "invalid request"),
 			Error::FailedToReadConfig(path) => write!(f, "failed to read config {}", path.display()),
+			Error::OutputNotFound(output) => write!(f, "output {output:?} not found"),
+			Error::ScreenshotFailed(reason) => write!(f, "failed to take screenshot: {reason}"),
+			Error::InvalidOutputConfig(reason) => write!(f, "invalid output config: {reason}"),
 		}
 	}
 }
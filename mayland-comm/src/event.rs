@@ -0,0 +1,98 @@
+//! events for subscribers
+
+use crate::workspace::Window;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// an event that mayland sends to subscribers
+///
+/// after a [`Request::Subscribe`](super::Request::Subscribe), mayland answers with
+/// [`Response::Subscribe`](super::Response::Subscribe) and then keeps the connection
+/// open, sending every event as json on a single line.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "tag", content = "val")]
+pub enum Event {
+	/// the active workspace changed
+	///
+	/// ```json
+	/// { "tag": "workspace_switched", "val": 2 }
+	/// ```
+	WorkspaceSwitched(usize),
+
+	/// a window was mapped
+	///
+	/// ```json
+	/// { "tag": "window_opened", "val": { "app_id": "kitty", "title": "kitty" }}
+	/// ```
+	WindowOpened(Window),
+	/// a window was unmapped
+	///
+	/// ```json
+	/// { "tag": "window_closed", "val": { "app_id": "kitty", "title": "kitty" }}
+	/// ```
+	WindowClosed(Window),
+	/// the keyboard focus changed, `null` if no window is focussed
+	///
+	/// ```json
+	/// { "tag": "window_focused", "val": { "app_id": "kitty", "title": "kitty" }}
+	/// ```
+	WindowFocused(Option<Window>),
+	/// the title of a window changed
+	///
+	/// ```json
+	/// { "tag": "window_title_changed", "val": { "app_id": "kitty", "title": "~" }}
+	/// ```
+	WindowTitleChanged(Window),
+
+	/// an output was connected
+	///
+	/// ```json
+	/// { "tag": "output_added", "val": "DP-1" }
+	/// ```
+	OutputAdded(String),
+	/// an output was disconnected
+	///
+	/// ```json
+	/// { "tag": "output_removed", "val": "DP-1" }
+	/// ```
+	OutputRemoved(String),
+
+	/// the config was reloaded
+	///
+	/// ```json
+	/// { "tag": "config_reloaded" }
+	/// ```
+	ConfigReloaded,
+}
+
+impl Display for Event {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Event::WorkspaceSwitched(idx) => writeln!(f, "workspace switched to {idx}"),
+			Event::WindowOpened(window) => writeln!(f, "window opened{}", DisplayWindow(window)),
+			Event::WindowClosed(window) => writeln!(f, "window closed{}", DisplayWindow(window)),
+			Event::WindowFocused(Some(window)) => writeln!(f, "window focused{}", DisplayWindow(window)),
+			Event::WindowFocused(None) => writeln!(f, "window focus cleared"),
+			Event::WindowTitleChanged(window) => {
+				writeln!(f, "window title changed{}", DisplayWindow(window))
+			}
+			Event::OutputAdded(output) => writeln!(f, "output added {output:?}"),
+			Event::OutputRemoved(output) => writeln!(f, "output removed {output:?}"),
+			Event::ConfigReloaded => writeln!(f, "config reloaded"),
+		}
+	}
+}
+
+struct DisplayWindow<'a>(&'a Window);
+
+impl Display for DisplayWindow<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match (&self.0.app_id, &self.0.title) {
+			(Some(app_id), Some(title)) => write!(f, " {app_id:?} @ {title:?}"),
+			(Some(app_id), None) => write!(f, " {app_id:?}"),
+			(None, Some(title)) => write!(f, " @ {title:?}"),
+			(None, None) => Ok(()),
+		}
+	}
+}
@@ -7,11 +7,16 @@
 //! to communicate with mayland, you have to send a [`Request`] and then mayland will send
 //! a [`Response`] back.
 //!
-//! all requests are sent as json, where the request is all on a single line to allow for future
-//! request batching.
+//! with [`Request::Subscribe`] the connection stays open, and mayland keeps sending
+//! [`Event`]s, one per line.
+//!
+//! all requests are sent as json, where the request is all on a single line.
 //! this is easily done with the default [`serde_json`](https://crates.io/crates/serde_json)
 //! Serializer, which already serializes the json content into a single line.
 //!
+//! you can send multiple newline-separated requests over the same connection, and mayland
+//! will answer each of them in order, with one response per line.
+//!
 //! all enums that have values are in the serde
 //! [adjacently tagged](https://serde.rs/enum-representations.html#adjacently-tagged)
 //! enum representation, with the tag being `"tag"` and the content being `"val"`, and
@@ -20,12 +25,15 @@
 #![warn(missing_docs)]
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 pub mod action;
 mod error;
+pub mod event;
 
 pub use self::action::Action;
 pub use self::error::Error;
+pub use self::event::Event;
 
 /// the environment variable, where the path to the mayland socket is found.
 ///
@@ -43,6 +51,14 @@ pub enum Request {
 	/// { "tag": "dispatch", "val": { "tag": "quit" }}
 	/// ```
 	Dispatch(Action),
+	/// dispatch multiple actions in order, without anything else happening in between
+	///
+	/// stops at the first action that returns an error
+	///
+	/// ```json
+	/// { "tag": "batch", "val": [{ "tag": "workspace", "val": 3 }, { "tag": "spawn", "val": [ "kitty" ]}]}
+	/// ```
+	Batch(Vec<Action>),
 	/// request mayland to reload the config
 	///
 	/// ```json
@@ -73,6 +89,49 @@ pub enum Request {
 	/// { "tag": "workspaces" }
 	/// ```
 	Workspaces,
+	/// take a screenshot of an output
+	///
+	/// takes a screenshot of the active output if `output` is `None`, and saves it
+	/// into the configured screenshot directory if `path` is `None`.
+	///
+	/// ```json
+	/// { "tag": "screenshot", "val": { "output": "eDP-1", "path": "/tmp/screenshot.png" }}
+	/// ```
+	Screenshot {
+		/// the connector of the output
+		output: Option<String>,
+		/// the absolute path to save the png file to
+		path: Option<PathBuf>,
+	},
+	/// change the config of an output until the config is reloaded
+	///
+	/// every field that is `None` is left unchanged
+	///
+	/// ```json
+	/// { "tag": "configure_output", "val": { "name": "eDP-1", "mode": { "w": 1920, "h": 1080, "refresh": null }, "scale": 1.5 }}
+	/// ```
+	ConfigureOutput {
+		/// the connector of the output
+		name: String,
+		/// the display mode
+		mode: Option<output::TargetMode>,
+		/// the logical position
+		position: Option<[i32; 2]>,
+		/// turn the output on and make it the active output, or turn it off
+		active: Option<bool>,
+		/// the output scale
+		scale: Option<f64>,
+		/// the output transform
+		transform: Option<output::Transform>,
+	},
+	/// subscribe to the mayland event stream
+	///
+	/// the connection is kept open and mayland sends an [`Event`] per line
+	///
+	/// ```json
+	/// { "tag": "subscribe" }
+	/// ```
+	Subscribe,
 }
 
 /// the response that mayland sends back
@@ -82,7 +141,7 @@ pub enum Request {
 pub enum Response {
 	/// mayland returned an error
 	Err(Error),
-	/// mayland successfully handled the dispatch request
+	/// mayland successfully handled the dispatch or batch request
 	Dispatch,
 	/// mayland successfully handled the reload request
 	Reload,
@@ -94,6 +153,12 @@ pub enum Response {
 	Windows(Vec<Window>),
 	/// mayland workspace info
 	Workspaces(Vec<Workspace>),
+	/// mayland saved the screenshot to the path
+	Screenshot(PathBuf),
+	/// mayland successfully configured the output
+	ConfigureOutput,
+	/// mayland successfully subscribed the client to the event stream
+	Subscribe,
 }
 
 /// an input device registered in mayland
@@ -204,6 +269,7 @@ pub mod output {
 				writeln!(f, "    mapped at: {},{}", logical.x, logical.y)?;
 				writeln!(f, "    mapped size: {}x{}", logical.w, logical.h)?;
 				writeln!(f, "    mapped transform: {}", logical.transform)?;
+				writeln!(f, "    mapped scale: {}", logical.scale)?;
 			}
 
 			writeln!(f, "    make: {}", self.make)?;
@@ -251,6 +317,19 @@ pub mod output {
 		}
 	}
 
+	/// a display mode to switch to
+	#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+	pub struct TargetMode {
+		/// mode width
+		pub w: u16,
+		/// mode height
+		pub h: u16,
+		/// refresh rate in mhz
+		///
+		/// if `None`, the mode with the highest refresh rate is picked
+		pub refresh: Option<u32>,
+	}
+
 	/// logical output information
 	#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 	pub struct Logical {
@@ -264,7 +343,8 @@ pub mod output {
 		pub h: i32,
 		/// output transform
 		pub transform: Transform,
-		// scale
+		/// output scale
+		pub scale: f64,
 	}
 
 	/// output transform
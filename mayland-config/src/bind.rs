@@ -1,6 +1,9 @@
 use bitflags::bitflags;
 use indexmap::IndexMap;
-use serde::{Deserialize, Deserializer, de::Error as _, de::Visitor};
+use serde::{
+	Deserialize, Deserializer,
+	de::{Error as _, MapAccess, Visitor},
+};
 use smithay::input::keyboard::{
 	Keysym, ModifiersState,
 	keysyms::KEY_NoSymbol,
@@ -8,20 +11,38 @@ use smithay::input::keyboard::{
 };
 use std::fmt::Debug;
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
-pub struct Binds(IndexMap<Mapping, Action>);
+#[derive(Debug, PartialEq, Eq)]
+pub struct Binds {
+	/// keyboard, scroll and gesture binds
+	actions: IndexMap<Mapping, Action>,
+	/// mouse button binds
+	mouse: IndexMap<Mapping, MouseAction>,
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum Action {
 	Quit,
 
+	PowerOff,
+	PowerOn,
+
+	Screenshot(ScreenshotTarget),
+
 	#[serde(alias = "close")]
 	CloseWindow,
 	ToggleFloating,
+	ToggleFullscreen,
+	ToggleMaximize,
 	Cycle(CycleDirection),
+	CycleWorkspace(CycleDirection),
+
+	GrowSplit,
+	ShrinkSplit,
 
 	Workspace(usize),
+	MoveToWorkspace(usize),
+	MoveToWorkspaceAndFollow(usize),
 
 	Spawn(#[serde(deserialize_with = "deserialize_spawn")] Vec<String>),
 }
@@ -42,6 +63,34 @@ pub enum CycleDirection {
 	Prev,
 }
 
+/// an action that can only be bound to a mouse button,
+/// as it lasts until the button is released
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MouseAction {
+	/// move the window under the pointer
+	Move,
+	/// resize the window under the pointer
+	Resize,
+}
+
+/// the direction of a scroll or a swipe gesture
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Direction {
+	Up,
+	Down,
+	Left,
+	Right,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScreenshotTarget {
+	Output,
+	Window,
+	Region,
+}
+
 impl From<Action> for mayland_comm::Action {
 	/// this implementation is not strictly necessary and should
 	/// probably not be used, but it exists so that the compiler warns
@@ -50,11 +99,26 @@ impl From<Action> for mayland_comm::Action {
 		match action {
 			Action::Quit => mayland_comm::Action::Quit,
 
+			Action::PowerOff => mayland_comm::Action::PowerOff,
+			Action::PowerOn => mayland_comm::Action::PowerOn,
+
+			Action::Screenshot(target) => mayland_comm::Action::Screenshot(target.into()),
+
 			Action::CloseWindow => mayland_comm::Action::CloseWindow,
 			Action::ToggleFloating => mayland_comm::Action::ToggleFloating,
+			Action::ToggleFullscreen => mayland_comm::Action::ToggleFullscreen,
+			Action::ToggleMaximize => mayland_comm::Action::ToggleMaximize,
 			Action::Cycle(direction) => mayland_comm::Action::Cycle(direction.into()),
+			Action::CycleWorkspace(direction) => mayland_comm::Action::CycleWorkspace(direction.into()),
+
+			Action::GrowSplit => mayland_comm::Action::GrowSplit,
+			Action::ShrinkSplit => mayland_comm::Action::ShrinkSplit,
 
 			Action::Workspace(workspace) => mayland_comm::Action::Workspace(workspace),
+			Action::MoveToWorkspace(workspace) => mayland_comm::Action::MoveToWorkspace(workspace),
+			Action::MoveToWorkspaceAndFollow(workspace) => {
+				mayland_comm::Action::MoveToWorkspaceAndFollow(workspace)
+			}
 
 			Action::Spawn(spawn) => mayland_comm::Action::Spawn(spawn),
 		}
@@ -66,11 +130,26 @@ impl From<mayland_comm::Action> for Action {
 		match action {
 			mayland_comm::Action::Quit => Action::Quit,
 
+			mayland_comm::Action::PowerOff => Action::PowerOff,
+			mayland_comm::Action::PowerOn => Action::PowerOn,
+
+			mayland_comm::Action::Screenshot(target) => Action::Screenshot(target.into()),
+
 			mayland_comm::Action::CloseWindow => Action::CloseWindow,
 			mayland_comm::Action::ToggleFloating => Action::ToggleFloating,
+			mayland_comm::Action::ToggleFullscreen => Action::ToggleFullscreen,
+			mayland_comm::Action::ToggleMaximize => Action::ToggleMaximize,
 			mayland_comm::Action::Cycle(direction) => Action::Cycle(direction.into()),
+			mayland_comm::Action::CycleWorkspace(direction) => Action::CycleWorkspace(direction.into()),
+
+			mayland_comm::Action::GrowSplit => Action::GrowSplit,
+			mayland_comm::Action::ShrinkSplit => Action::ShrinkSplit,
 
 			mayland_comm::Action::Workspace(workspace) => Action::Workspace(workspace),
+			mayland_comm::Action::MoveToWorkspace(workspace) => Action::MoveToWorkspace(workspace),
+			mayland_comm::Action::MoveToWorkspaceAndFollow(workspace) => {
+				Action::MoveToWorkspaceAndFollow(workspace)
+			}
 
 			mayland_comm::Action::Spawn(spawn) => Action::Spawn(spawn),
 		}
@@ -95,6 +174,26 @@ impl From<mayland_comm::action::CycleDirection> for CycleDirection {
 	}
 }
 
+impl From<ScreenshotTarget> for mayland_comm::action::ScreenshotTarget {
+	fn from(value: ScreenshotTarget) -> Self {
+		match value {
+			ScreenshotTarget::Output => mayland_comm::action::ScreenshotTarget::Output,
+			ScreenshotTarget::Window => mayland_comm::action::ScreenshotTarget::Window,
+			ScreenshotTarget::Region => mayland_comm::action::ScreenshotTarget::Region,
+		}
+	}
+}
+
+impl From<mayland_comm::action::ScreenshotTarget> for ScreenshotTarget {
+	fn from(value: mayland_comm::action::ScreenshotTarget) -> Self {
+		match value {
+			mayland_comm::action::ScreenshotTarget::Output => ScreenshotTarget::Output,
+			mayland_comm::action::ScreenshotTarget::Window => ScreenshotTarget::Window,
+			mayland_comm::action::ScreenshotTarget::Region => ScreenshotTarget::Region,
+		}
+	}
+}
+
 impl Default for Binds {
 	fn default() -> Self {
 		let mut binds = IndexMap::new();
@@ -103,7 +202,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::Escape,
+				trigger: Trigger::Key(Keysym::Escape),
 			},
 			Action::Quit,
 		);
@@ -112,7 +211,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::q,
+				trigger: Trigger::Key(Keysym::q),
 			},
 			Action::CloseWindow,
 		);
@@ -121,7 +220,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::v,
+				trigger: Trigger::Key(Keysym::v),
 			},
 			Action::ToggleFloating,
 		);
@@ -130,23 +229,62 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::Tab,
+				trigger: Trigger::Key(Keysym::Tab),
 			},
 			Action::Cycle(CycleDirection::Next),
 		);
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD | Modifiers::SHIFT,
-				key: Keysym::Tab,
+				trigger: Trigger::Key(Keysym::Tab),
 			},
 			Action::Cycle(CycleDirection::Prev),
 		);
 
+		// resize the tiling split
+		binds.insert(
+			Mapping {
+				mods: Modifiers::MOD,
+				trigger: Trigger::Key(Keysym::equal),
+			},
+			Action::GrowSplit,
+		);
+		binds.insert(
+			Mapping {
+				mods: Modifiers::MOD,
+				trigger: Trigger::Key(Keysym::minus),
+			},
+			Action::ShrinkSplit,
+		);
+
+		// screenshots
+		binds.insert(
+			Mapping {
+				mods: Modifiers::empty(),
+				trigger: Trigger::Key(Keysym::Print),
+			},
+			Action::Screenshot(ScreenshotTarget::Output),
+		);
+		binds.insert(
+			Mapping {
+				mods: Modifiers::MOD,
+				trigger: Trigger::Key(Keysym::Print),
+			},
+			Action::Screenshot(ScreenshotTarget::Window),
+		);
+		binds.insert(
+			Mapping {
+				mods: Modifiers::SHIFT,
+				trigger: Trigger::Key(Keysym::Print),
+			},
+			Action::Screenshot(ScreenshotTarget::Region),
+		);
+
 		// spawn kitty
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::t,
+				trigger: Trigger::Key(Keysym::t),
 			},
 			Action::Spawn(vec!["kitty".to_owned()]),
 		);
@@ -155,7 +293,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::e,
+				trigger: Trigger::Key(Keysym::e),
 			},
 			Action::Spawn(vec!["nautilus".to_owned()]),
 		);
@@ -164,7 +302,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::n,
+				trigger: Trigger::Key(Keysym::n),
 			},
 			Action::Spawn(vec!["firefox".to_owned()]),
 		);
@@ -173,7 +311,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::space,
+				trigger: Trigger::Key(Keysym::space),
 			},
 			Action::Spawn(vec!["fuzzel".to_owned()]),
 		);
@@ -182,51 +320,81 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::_1,
+				trigger: Trigger::Key(Keysym::_1),
 			},
 			Action::Workspace(0),
 		);
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::_2,
+				trigger: Trigger::Key(Keysym::_2),
 			},
 			Action::Workspace(1),
 		);
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::_3,
+				trigger: Trigger::Key(Keysym::_3),
 			},
 			Action::Workspace(2),
 		);
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::_4,
+				trigger: Trigger::Key(Keysym::_4),
 			},
 			Action::Workspace(3),
 		);
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::_5,
+				trigger: Trigger::Key(Keysym::_5),
 			},
 			Action::Workspace(4),
 		);
 		binds.insert(
 			Mapping {
 				mods: Modifiers::MOD,
-				key: Keysym::_6,
+				trigger: Trigger::Key(Keysym::_6),
 			},
 			Action::Workspace(5),
 		);
 
+		// touchpad gestures
+		binds.insert(
+			Mapping {
+				mods: Modifiers::empty(),
+				trigger: Trigger::Swipe(3, Direction::Left),
+			},
+			Action::CycleWorkspace(CycleDirection::Next),
+		);
+		binds.insert(
+			Mapping {
+				mods: Modifiers::empty(),
+				trigger: Trigger::Swipe(3, Direction::Right),
+			},
+			Action::CycleWorkspace(CycleDirection::Prev),
+		);
+		binds.insert(
+			Mapping {
+				mods: Modifiers::empty(),
+				trigger: Trigger::Swipe(4, Direction::Left),
+			},
+			Action::Cycle(CycleDirection::Next),
+		);
+		binds.insert(
+			Mapping {
+				mods: Modifiers::empty(),
+				trigger: Trigger::Swipe(4, Direction::Right),
+			},
+			Action::Cycle(CycleDirection::Prev),
+		);
+
 		// audio media keys
 		binds.insert(
 			Mapping {
 				mods: Modifiers::empty(),
-				key: Keysym::XF86_AudioMute,
+				trigger: Trigger::Key(Keysym::XF86_AudioMute),
 			},
 			Action::Spawn(vec![
 				"wpctl".to_owned(),
@@ -238,7 +406,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::empty(),
-				key: Keysym::XF86_AudioRaiseVolume,
+				trigger: Trigger::Key(Keysym::XF86_AudioRaiseVolume),
 			},
 			Action::Spawn(vec![
 				"wpctl".to_owned(),
@@ -252,7 +420,7 @@ impl Default for Binds {
 		binds.insert(
 			Mapping {
 				mods: Modifiers::empty(),
-				key: Keysym::XF86_AudioLowerVolume,
+				trigger: Trigger::Key(Keysym::XF86_AudioLowerVolume),
 			},
 			Action::Spawn(vec![
 				"wpctl".to_owned(),
@@ -264,20 +432,106 @@ impl Default for Binds {
 			]),
 		);
 
-		Binds(binds)
+		let mut mouse = IndexMap::new();
+
+		// move and resize windows with the mouse
+		mouse.insert(
+			Mapping {
+				mods: Modifiers::MOD,
+				trigger: Trigger::Button(BTN_LEFT),
+			},
+			MouseAction::Move,
+		);
+		mouse.insert(
+			Mapping {
+				mods: Modifiers::MOD,
+				trigger: Trigger::Button(BTN_RIGHT),
+			},
+			MouseAction::Resize,
+		);
+
+		Binds {
+			actions: binds,
+			mouse,
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Binds {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_map(BindsVisitor)
+	}
+}
+
+struct BindsVisitor;
+
+impl<'de> Visitor<'de> for BindsVisitor {
+	type Value = Binds;
+
+	fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("a map of binds")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut actions = IndexMap::new();
+		let mut mouse = IndexMap::new();
+
+		// mouse buttons bind to a different set of actions
+		while let Some(mapping) = map.next_key::<Mapping>()? {
+			if let Trigger::Button(_) = mapping.trigger {
+				let action = map.next_value::<MouseAction>()?;
+				mouse.insert(mapping, action);
+			} else {
+				let action = map.next_value::<Action>()?;
+				actions.insert(mapping, action);
+			}
+		}
+
+		Ok(Binds { actions, mouse })
 	}
 }
 
 impl Binds {
 	pub fn find_action(&self, modifiers: &ModifiersState, keysym: Keysym) -> Option<Action> {
-		let mapping = Mapping::from_xkb(modifiers, keysym);
-		self.0.get(&mapping).cloned()
+		let mapping = Mapping::from_xkb(modifiers, Trigger::Key(keysym));
+		self.actions.get(&mapping).cloned()
+	}
+
+	/// find the action bound to the mouse button with the evdev code `button`
+	pub fn find_mouse_action(&self, modifiers: &ModifiersState, button: u32) -> Option<MouseAction> {
+		let mapping = Mapping::from_xkb(modifiers, Trigger::Button(button));
+		self.mouse.get(&mapping).copied()
+	}
+
+	/// find the action bound to a swipe gesture with `fingers` fingers
+	pub fn find_swipe_action(
+		&self,
+		modifiers: &ModifiersState,
+		fingers: u32,
+		direction: Direction,
+	) -> Option<Action> {
+		let mapping = Mapping::from_xkb(modifiers, Trigger::Swipe(fingers, direction));
+		self.actions.get(&mapping).cloned()
+	}
+
+	/// is any swipe gesture with `fingers` fingers bound?
+	pub fn has_swipe(&self, modifiers: &ModifiersState, fingers: u32) -> bool {
+		let mods = Modifiers::from_xkb(modifiers);
+		(self.actions.keys()).any(|mapping| {
+			mapping.mods == mods && matches!(mapping.trigger, Trigger::Swipe(f, _) if f == fingers)
+		})
+	}
+
+	pub fn find_scroll_action(&self, modifiers: &ModifiersState, direction: Direction) -> Option<Action> {
+		let mapping = Mapping::from_xkb(modifiers, Trigger::Scroll(direction));
+		self.actions.get(&mapping).cloned()
 	}
 
 	pub(crate) fn flatten_mod(mut self, comp: CompMod) -> Self {
-		self.0 = self
-			.0
-			.into_iter()
+		self.actions = (self.actions.into_iter())
+			.map(|(key, val)| (key.flatten_mod(comp), val))
+			.collect();
+		self.mouse = (self.mouse.into_iter())
 			.map(|(key, val)| (key.flatten_mod(comp), val))
 			.collect();
 
@@ -345,17 +599,96 @@ impl Modifiers {
 	}
 }
 
+/// the evdev code of the left mouse button
+pub const BTN_LEFT: u32 = 0x110;
+/// the evdev code of the right mouse button
+pub const BTN_RIGHT: u32 = 0x111;
+/// the evdev code of the middle mouse button
+pub const BTN_MIDDLE: u32 = 0x112;
+/// the evdev code of the back mouse button
+pub const BTN_SIDE: u32 = 0x113;
+/// the evdev code of the forward mouse button
+pub const BTN_EXTRA: u32 = 0x114;
+
+/// the names of the supported mouse buttons and their evdev codes
+const BUTTONS: [(&str, u32); 5] = [
+	("mouse-left", BTN_LEFT),
+	("mouse-right", BTN_RIGHT),
+	("mouse-middle", BTN_MIDDLE),
+	("mouse-back", BTN_SIDE),
+	("mouse-forward", BTN_EXTRA),
+];
+
+/// the names of the scroll directions
+const SCROLL: [(&str, Direction); 4] = [
+	("scroll-up", Direction::Up),
+	("scroll-down", Direction::Down),
+	("scroll-left", Direction::Left),
+	("scroll-right", Direction::Right),
+];
+
+/// the names of the swipe directions
+const SWIPE: [(&str, Direction); 4] = [
+	("up", Direction::Up),
+	("down", Direction::Down),
+	("left", Direction::Left),
+	("right", Direction::Right),
+];
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Trigger {
+	Key(Keysym),
+	Button(u32),
+	Scroll(Direction),
+	/// a swipe gesture with the amount of fingers
+	Swipe(u32, Direction),
+}
+
+impl Trigger {
+	fn from_name(name: &str) -> Option<Trigger> {
+		if let Some(&(_, button)) = BUTTONS.iter().find(|(btn, _)| btn.eq_ignore_ascii_case(name)) {
+			return Some(Trigger::Button(button));
+		} else if let Some(&(_, direction)) = SCROLL.iter().find(|(dir, _)| dir.eq_ignore_ascii_case(name)) {
+			return Some(Trigger::Scroll(direction));
+		} else if let Some(swipe) = Trigger::swipe_from_name(name) {
+			return Some(swipe);
+		}
+
+		let keysym = keysym_from_name(name, KEYSYM_CASE_INSENSITIVE);
+		if keysym.raw() == KEY_NoSymbol {
+			None
+		} else {
+			Some(Trigger::Key(keysym))
+		}
+	}
+
+	/// parse a swipe gesture in the form of `swipe-<fingers>-<direction>`
+	fn swipe_from_name(name: &str) -> Option<Trigger> {
+		let (swipe, rest) = name.split_once('-')?;
+		if !swipe.eq_ignore_ascii_case("swipe") {
+			return None;
+		}
+
+		let (fingers, direction) = rest.split_once('-')?;
+		let fingers = fingers.parse::<u32>().ok()?;
+		let &(_, direction) = (SWIPE.iter()).find(|(dir, _)| dir.eq_ignore_ascii_case(direction))?;
+
+		// two finger swipes are scrolling
+		(fingers >= 3).then_some(Trigger::Swipe(fingers, direction))
+	}
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Mapping {
 	mods: Modifiers,
-	key: Keysym,
+	trigger: Trigger,
 }
 
 impl Mapping {
-	/// construct a [`Mapping`] from a [`ModifiersState`] and a [`Keysym`]
-	fn from_xkb(modifiers: &ModifiersState, key: Keysym) -> Mapping {
+	/// construct a [`Mapping`] from a [`ModifiersState`] and a [`Trigger`]
+	fn from_xkb(modifiers: &ModifiersState, trigger: Trigger) -> Mapping {
 		let mods = Modifiers::from_xkb(modifiers);
-		Mapping { mods, key }
+		Mapping { mods, trigger }
 	}
 
 	/// remove [`Modifiers::MOD`] from `self`
@@ -385,32 +718,32 @@ impl Visitor<'_> for MappingVisitor {
 
 	fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
 		let mut mods = Modifiers::empty();
-		let mut key = None;
+		let mut trigger = None;
 
 		for split in v
 			.split(|c: char| c.is_whitespace() || c == '+')
 			.filter(|word| !word.is_empty())
 		{
 			if !mods.add(split) {
-				let keysym = keysym_from_name(split, KEYSYM_CASE_INSENSITIVE);
-
-				if keysym.raw() == KEY_NoSymbol {
+				let Some(key) = Trigger::from_name(split) else {
 					return Err(serde::de::Error::custom(format_args!("invalid key {split:?}")));
-				} else if key.is_some() {
+				};
+
+				if trigger.is_some() {
 					return Err(serde::de::Error::custom(format_args!(
 						"duplicate key definition at {split:?}"
 					)));
 				}
 
-				key = Some(keysym);
+				trigger = Some(key);
 			}
 		}
 
-		let Some(key) = key else {
+		let Some(trigger) = trigger else {
 			return Err(serde::de::Error::custom("missing key"));
 		};
 
-		Ok(Mapping { mods, key })
+		Ok(Mapping { mods, trigger })
 	}
 }
 
@@ -13,6 +13,10 @@ pub struct Input {
 	touchpad: Touchpad,
 	mouse: Mouse,
 	pub tablet: Tablet,
+	pub touch: Touch,
+
+	/// turn powered off outputs back on with any input
+	pub wake_outputs: bool,
 
 	devices: Vec<Device>,
 }
@@ -22,6 +26,7 @@ enum Device {
 	Touchpad(String, Touchpad),
 	Mouse(String, Mouse),
 	Tablet(String, Tablet),
+	Touch(String, Touch),
 }
 
 impl Input {
@@ -60,6 +65,18 @@ impl Input {
 			.map(|(_, tablet)| tablet)
 			.unwrap_or(&self.tablet)
 	}
+
+	pub fn touch(&self, name: &str) -> &Touch {
+		self.devices
+			.iter()
+			.filter_map(|device| match device {
+				Device::Touch(name, touch) => Some((name, touch)),
+				_ => None,
+			})
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, touch)| touch)
+			.unwrap_or(&self.touch)
+	}
 }
 
 impl<'de> Deserialize<'de> for Input {
@@ -68,6 +85,7 @@ impl<'de> Deserialize<'de> for Input {
 			Touchpad(String),
 			Mouse(String),
 			Tablet(String),
+			Touch(String),
 		}
 
 		enum Field {
@@ -75,6 +93,8 @@ impl<'de> Deserialize<'de> for Input {
 			Touchpad,
 			Mouse,
 			Tablet,
+			Touch,
+			WakeOutputs,
 
 			Device(DeviceField),
 
@@ -96,6 +116,8 @@ impl<'de> Deserialize<'de> for Input {
 					"touchpad" => Ok(Field::Touchpad),
 					"mouse" => Ok(Field::Mouse),
 					"tablet" => Ok(Field::Tablet),
+					"touch" => Ok(Field::Touch),
+					"wake-outputs" => Ok(Field::WakeOutputs),
 					_ => Ok(Field::Ignore),
 				}
 			}
@@ -125,6 +147,11 @@ impl<'de> Deserialize<'de> for Input {
 						let name = DeviceField::Tablet(name);
 						Ok(Field::Device(name))
 					}
+					"touch" => {
+						let name = val.newtype_variant::<String>()?;
+						let name = DeviceField::Touch(name);
+						Ok(Field::Device(name))
+					}
 					_ => {
 						let _ = val.newtype_variant::<serde::de::IgnoredAny>();
 						Ok(Field::Ignore)
@@ -153,11 +180,14 @@ impl<'de> Deserialize<'de> for Input {
 				let mut touchpad = None;
 				let mut mouse = None;
 				let mut tablet = None;
+				let mut touch = None;
+				let mut wake_outputs = None;
 
 				enum TmpDevice {
 					Touchpad(String, per_device::Touchpad),
 					Mouse(String, per_device::Mouse),
 					Tablet(String, per_device::Tablet),
+					Touch(String, per_device::Touch),
 				}
 
 				let mut devices = Vec::new();
@@ -192,6 +222,20 @@ impl<'de> Deserialize<'de> for Input {
 
 							tablet = Some(map.next_value::<Tablet>()?);
 						}
+						Field::Touch => {
+							if touch.is_some() {
+								return Err(serde::de::Error::duplicate_field("touch"));
+							}
+
+							touch = Some(map.next_value::<Touch>()?);
+						}
+						Field::WakeOutputs => {
+							if wake_outputs.is_some() {
+								return Err(serde::de::Error::duplicate_field("wake-outputs"));
+							}
+
+							wake_outputs = Some(map.next_value::<bool>()?);
+						}
 
 						Field::Device(device) => match device {
 							DeviceField::Touchpad(dev) => {
@@ -209,6 +253,11 @@ impl<'de> Deserialize<'de> for Input {
 								let device = TmpDevice::Tablet(dev, tablet);
 								devices.push(device);
 							}
+							DeviceField::Touch(dev) => {
+								let touch = map.next_value::<per_device::Touch>()?;
+								let device = TmpDevice::Touch(dev, touch);
+								devices.push(device);
+							}
 						},
 
 						Field::Ignore => {
@@ -221,6 +270,8 @@ impl<'de> Deserialize<'de> for Input {
 				let touchpad = touchpad.unwrap_or_default();
 				let mouse = mouse.unwrap_or_default();
 				let tablet = tablet.unwrap_or_default();
+				let touch = touch.unwrap_or_default();
+				let wake_outputs = wake_outputs.unwrap_or_default();
 
 				let devices = devices
 					.into_iter()
@@ -228,6 +279,7 @@ impl<'de> Deserialize<'de> for Input {
 						TmpDevice::Touchpad(dev, value) => Device::Touchpad(dev, value.merge(&touchpad)),
 						TmpDevice::Mouse(dev, value) => Device::Mouse(dev, value.merge(&mouse)),
 						TmpDevice::Tablet(dev, value) => Device::Tablet(dev, value.merge(&tablet)),
+						TmpDevice::Touch(dev, value) => Device::Touch(dev, value.merge(&touch)),
 					})
 					.collect();
 
@@ -236,6 +288,9 @@ impl<'de> Deserialize<'de> for Input {
 					touchpad,
 					mouse,
 					tablet,
+					touch,
+
+					wake_outputs,
 
 					devices,
 				};
@@ -387,6 +442,13 @@ pub struct Tablet {
 	pub relative: bool,
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Touch {
+	pub map_to: TabletMapping,
+}
+
+/// the area that an absolute input device maps to
 #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum TabletMapping {
@@ -396,7 +458,9 @@ pub enum TabletMapping {
 	Output(String),
 }
 
-fn deserialize_path<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+pub(crate) fn deserialize_path<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Option<String>, D::Error> {
 	let option = Option::<String>::deserialize(deserializer)?;
 	let Some(mut path) = option else { return Ok(None) };
 
@@ -516,6 +580,20 @@ mod per_device {
 			}
 		}
 	}
+
+	#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+	#[serde(default, rename_all = "kebab-case")]
+	pub struct Touch {
+		pub map_to: Option<TabletMapping>,
+	}
+
+	impl Touch {
+		pub fn merge(self, other: &super::Touch) -> super::Touch {
+			super::Touch {
+				map_to: self.map_to.unwrap_or_else(|| other.map_to.clone()),
+			}
+		}
+	}
 }
 
 mod tap_drag_lock {
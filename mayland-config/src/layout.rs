@@ -6,15 +6,24 @@ pub struct Layout {
 	pub tiling: Tiling,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
 #[serde(default)]
 pub struct Tiling {
 	pub gaps: u8,
 	pub border: u8,
+	pub ratio: f64,
 }
 
+/// all values are parsed with [`mayfig`],
+/// which does not support nan floats
+impl Eq for Tiling {}
+
 impl Default for Tiling {
 	fn default() -> Self {
-		Tiling { gaps: 10, border: 20 }
+		Tiling {
+			gaps: 10,
+			border: 20,
+			ratio: 0.5,
+		}
 	}
 }
@@ -36,6 +36,7 @@ pub struct Config {
 	pub cursor: Cursor,
 	pub decoration: Decoration,
 	pub layout: Layout,
+	pub screenshot: Screenshot,
 	#[serde(rename = "env")]
 	pub environment: Environment,
 	pub bind: Binds,
@@ -49,6 +50,31 @@ pub struct Cursor {
 	pub xcursor_size: Option<u32>,
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Screenshot {
+	#[serde(deserialize_with = "input::deserialize_path")]
+	pub path: Option<String>,
+}
+
+impl Screenshot {
+	/// the directory screenshots are saved to
+	///
+	/// falls back to `~/Pictures/Screenshots`
+	pub fn directory(&self) -> PathBuf {
+		if let Some(path) = &self.path {
+			return PathBuf::from(path);
+		}
+
+		let mut pictures = dirs::picture_dir()
+			.or_else(|| dirs::home_dir().map(|home| home.join("Pictures")))
+			.unwrap_or_else(std::env::temp_dir);
+		pictures.push("Screenshots");
+
+		pictures
+	}
+}
+
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct Environment {
 	pub envs: HashMap<String, String>,
@@ -1,7 +1,7 @@
 use serde::{Deserialize, de::Visitor};
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
-#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
 pub struct Outputs(HashMap<String, Output>);
 
 #[derive(Debug, Clone)]
@@ -58,14 +58,98 @@ impl Outputs {
 	pub fn get_output(&self, info: &OutputInfo) -> Option<&Output> {
 		self.0.get(&info.connector)
 	}
+
+	pub fn get_output_by_name(&self, connector: &str) -> Option<&Output> {
+		self.0.get(connector)
+	}
+
+	/// set the config of the output with the connector name
+	pub fn set_output(&mut self, connector: String, output: Output) {
+		self.0.insert(connector, output);
+	}
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
 #[serde(default)]
 pub struct Output {
 	pub mode: Option<Mode>,
 	pub active: bool,
 	pub position: Option<[i32; 2]>,
+	pub scale: Option<f64>,
+	pub transform: Transform,
+	/// the connector name of the output to mirror
+	pub mirror: Option<String>,
+	/// turn the output off and don't map it
+	pub disable: bool,
+}
+
+/// all values are parsed with [`mayfig`],
+/// which does not support nan floats
+impl Eq for Output {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Transform {
+	#[default]
+	Normal,
+	#[serde(rename = "90")]
+	_90,
+	#[serde(rename = "180")]
+	_180,
+	#[serde(rename = "270")]
+	_270,
+	Flipped,
+	#[serde(rename = "flipped-90")]
+	Flipped90,
+	#[serde(rename = "flipped-180")]
+	Flipped180,
+	#[serde(rename = "flipped-270")]
+	Flipped270,
+}
+
+impl From<Transform> for smithay::utils::Transform {
+	fn from(value: Transform) -> Self {
+		match value {
+			Transform::Normal => smithay::utils::Transform::Normal,
+			Transform::_90 => smithay::utils::Transform::_90,
+			Transform::_180 => smithay::utils::Transform::_180,
+			Transform::_270 => smithay::utils::Transform::_270,
+			Transform::Flipped => smithay::utils::Transform::Flipped,
+			Transform::Flipped90 => smithay::utils::Transform::Flipped90,
+			Transform::Flipped180 => smithay::utils::Transform::Flipped180,
+			Transform::Flipped270 => smithay::utils::Transform::Flipped270,
+		}
+	}
+}
+
+impl From<smithay::utils::Transform> for Transform {
+	fn from(value: smithay::utils::Transform) -> Self {
+		match value {
+			smithay::utils::Transform::Normal => Transform::Normal,
+			smithay::utils::Transform::_90 => Transform::_90,
+			smithay::utils::Transform::_180 => Transform::_180,
+			smithay::utils::Transform::_270 => Transform::_270,
+			smithay::utils::Transform::Flipped => Transform::Flipped,
+			smithay::utils::Transform::Flipped90 => Transform::Flipped90,
+			smithay::utils::Transform::Flipped180 => Transform::Flipped180,
+			smithay::utils::Transform::Flipped270 => Transform::Flipped270,
+		}
+	}
+}
+
+impl From<mayland_comm::output::Transform> for Transform {
+	fn from(value: mayland_comm::output::Transform) -> Self {
+		match value {
+			mayland_comm::output::Transform::Normal => Transform::Normal,
+			mayland_comm::output::Transform::_90 => Transform::_90,
+			mayland_comm::output::Transform::_180 => Transform::_180,
+			mayland_comm::output::Transform::_270 => Transform::_270,
+			mayland_comm::output::Transform::Flipped => Transform::Flipped,
+			mayland_comm::output::Transform::Flipped90 => Transform::Flipped90,
+			mayland_comm::output::Transform::Flipped180 => Transform::Flipped180,
+			mayland_comm::output::Transform::Flipped270 => Transform::Flipped270,
+		}
+	}
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -97,23 +181,32 @@ impl Visitor<'_> for ModeVis {
 	where
 		E: serde::de::Error,
 	{
+		v.parse().map_err(serde::de::Error::custom)
+	}
+}
+
+impl FromStr for Mode {
+	type Err = String;
+
+	/// parse a mode in the format `"<width>x<height>"` or `"<width>x<height>@<refresh-rate in hz>"`
+	fn from_str(v: &str) -> Result<Self, Self::Err> {
 		let (size, refresh) = v.split_once('@').map(|(s, r)| (s, Some(r))).unwrap_or((v, None));
 
 		let Some((width, height)) = size.split_once('x') else {
-			return Err(serde::de::Error::custom(format_args!("invalid size {size:?}")));
+			return Err(format!("invalid size {size:?}"));
 		};
 
 		let width = width
 			.parse::<u16>()
-			.map_err(|err| serde::de::Error::custom(format_args!("invalid width {width:?} ({err})")))?;
+			.map_err(|err| format!("invalid width {width:?} ({err})"))?;
 		let height = height
 			.parse::<u16>()
-			.map_err(|err| serde::de::Error::custom(format_args!("invalid height {height:?} ({err})")))?;
+			.map_err(|err| format!("invalid height {height:?} ({err})"))?;
 
 		let refresh = if let Some(refresh) = refresh {
-			let refresh = refresh.parse::<f32>().map_err(|err| {
-				serde::de::Error::custom(format_args!("invalid refresh {refresh:?} ({err})"))
-			})?;
+			let refresh = refresh
+				.parse::<f32>()
+				.map_err(|err| format!("invalid refresh {refresh:?} ({err})"))?;
 			let refresh = (refresh * 1000.).round() as i32;
 			Some(refresh)
 		} else {
@@ -127,3 +220,23 @@ impl Visitor<'_> for ModeVis {
 		})
 	}
 }
+
+impl From<mayland_comm::output::TargetMode> for Mode {
+	fn from(value: mayland_comm::output::TargetMode) -> Self {
+		Mode {
+			width: value.w,
+			height: value.h,
+			refresh: value.refresh.map(|refresh| refresh as i32),
+		}
+	}
+}
+
+impl From<Mode> for mayland_comm::output::TargetMode {
+	fn from(value: Mode) -> Self {
+		mayland_comm::output::TargetMode {
+			w: value.width,
+			h: value.height,
+			refresh: value.refresh.map(|refresh| refresh as u32),
+		}
+	}
+}
@@ -1,10 +1,11 @@
-use self::{udev::Udev, winit::Winit};
+use self::{headless::Headless, udev::Udev, winit::Winit};
 use crate::{render::MaylandRenderElements, state::Mayland};
 use smithay::{
 	backend::{allocator::dmabuf::Dmabuf, renderer::glow::GlowRenderer},
 	output::Output,
 };
 
+pub mod headless;
 pub mod udev;
 pub mod winit;
 
@@ -13,27 +14,46 @@ pub mod winit;
 pub enum Backend {
 	Udev(Udev),
 	Winit(Winit),
+	Headless(Headless),
 }
 
 impl Backend {
-	pub fn render(&mut self, mayland: &mut Mayland, output: &Output, elements: &[MaylandRenderElements]) {
+	/// render the elements onto the output
+	///
+	/// returns false if the frame couldn't be rendered or queued
+	pub fn render(
+		&mut self,
+		mayland: &mut Mayland,
+		output: &Output,
+		elements: &[MaylandRenderElements],
+	) -> bool {
 		match self {
 			Backend::Udev(udev) => udev.render(mayland, output, elements),
 			Backend::Winit(winit) => winit.render(mayland, output, elements),
+			Backend::Headless(headless) => headless.render(mayland, output, elements),
 		}
 	}
 
-	pub fn renderer(&mut self) -> &mut GlowRenderer {
+	/// the headless backend doesn't have a renderer
+	pub fn renderer(&mut self) -> Option<&mut GlowRenderer> {
 		match self {
-			Backend::Udev(udev) => udev.renderer(),
-			Backend::Winit(winit) => winit.renderer(),
+			Backend::Udev(udev) => Some(udev.renderer()),
+			Backend::Winit(winit) => Some(winit.renderer()),
+			Backend::Headless(_) => None,
 		}
 	}
 
 	pub fn switch_vt(&mut self, vt: i32) {
 		match self {
 			Backend::Udev(udev) => udev.switch_vt(vt),
-			Backend::Winit(_) => (),
+			Backend::Winit(_) | Backend::Headless(_) => (),
+		}
+	}
+
+	pub fn set_output_power(&mut self, mayland: &mut Mayland, output: &Output, powered: bool) {
+		match self {
+			Backend::Udev(udev) => udev.set_output_power(mayland, output, powered),
+			Backend::Winit(_) | Backend::Headless(_) => (),
 		}
 	}
 
@@ -41,6 +61,7 @@ impl Backend {
 		match self {
 			Backend::Udev(udev) => udev.import_dmabuf(dmabuf),
 			Backend::Winit(winit) => winit.import_dmabuf(dmabuf),
+			Backend::Headless(_) => false,
 		}
 	}
 
@@ -48,27 +69,37 @@ impl Backend {
 		match self {
 			Backend::Udev(udev) => udev.comm_outputs(mayland),
 			Backend::Winit(winit) => winit.comm_outputs(),
+			Backend::Headless(headless) => headless.comm_outputs(),
 		}
 	}
 
-	pub fn reload_output_config(&mut self, mayland: &mut Mayland) {
+	/// apply the output config, returns false if the mode of an output couldn't be set
+	pub fn reload_output_config(&mut self, mayland: &mut Mayland) -> bool {
 		match self {
 			Backend::Udev(udev) => udev.reload_output_config(mayland),
 			Backend::Winit(winit) => winit.reload_output_config(mayland),
+			Backend::Headless(headless) => headless.reload_output_config(mayland),
 		}
 	}
 
 	pub fn winit(&mut self) -> &mut Winit {
 		match self {
-			Backend::Udev(_udev) => unreachable!("should only be called in winit context"),
 			Backend::Winit(winit) => winit,
+			_ => unreachable!("should only be called in winit context"),
 		}
 	}
 
 	pub fn udev(&mut self) -> &mut Udev {
 		match self {
 			Backend::Udev(udev) => udev,
-			Backend::Winit(_winit) => unreachable!("should only be called in udev context"),
+			_ => unreachable!("should only be called in udev context"),
+		}
+	}
+
+	pub fn headless(&mut self) -> &mut Headless {
+		match self {
+			Backend::Headless(headless) => headless,
+			_ => unreachable!("should only be called in headless context"),
 		}
 	}
 }
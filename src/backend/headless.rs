@@ -0,0 +1,162 @@
+use crate::{
+	render::MaylandRenderElements,
+	state::Mayland,
+	utils::{logical_output, output_scale, output_transform},
+};
+use mayland_config::outputs::OutputInfo;
+use smithay::{
+	output::{Mode, Output, PhysicalProperties, Subpixel},
+	reexports::calloop::timer::{TimeoutAction, Timer},
+	utils::{Physical, Size},
+};
+use std::time::Duration;
+
+const REFRESH: i32 = 60_000;
+
+/// a backend without any display or gpu, meant for automated testing
+///
+/// nothing actually gets rendered, but the outputs still behave like real
+/// outputs to clients, including frame callbacks on a simulated vblank
+#[derive(Debug)]
+pub struct Headless {
+	outputs: Vec<Output>,
+}
+
+impl Headless {
+	pub fn init(mayland: &mut Mayland) -> Self {
+		let mut headless = Headless { outputs: Vec::new() };
+		headless.add_output(mayland, Size::new(1920, 1080));
+
+		headless
+	}
+
+	/// add a new virtual output with the specified size in physical pixels
+	pub fn add_output(&mut self, mayland: &mut Mayland, size: Size<i32, Physical>) -> Output {
+		let name = format!("headless-{}", self.outputs.len() + 1);
+
+		let output = Output::new(
+			name.clone(),
+			PhysicalProperties {
+				size: Size::new(0, 0),
+				subpixel: Subpixel::Unknown,
+				make: "may".to_owned(),
+				model: "headless".to_owned(),
+				serial_number: String::new(),
+			},
+		);
+
+		let output_info = OutputInfo {
+			connector: name,
+			make: "may".to_owned(),
+			model: "headless".to_owned(),
+			serial: None,
+		};
+
+		let config = mayland.config.output.get_output(&output_info);
+		let scale = output_scale(config);
+		let transform = output_transform(config);
+
+		let mode = Mode {
+			size,
+			refresh: REFRESH,
+		};
+		output.change_current_state(Some(mode), Some(transform), Some(scale), None);
+		output.set_preferred(mode);
+
+		output.user_data().insert_if_missing(|| output_info);
+
+		mayland.add_output(output.clone());
+		self.outputs.push(output.clone());
+
+		output
+	}
+}
+
+impl Headless {
+	pub fn render(
+		&mut self,
+		mayland: &mut Mayland,
+		output: &Output,
+		_elements: &[MaylandRenderElements],
+	) -> bool {
+		let output_state = mayland.output_state.get_mut(output).unwrap();
+		output_state.queued.waiting_for_vblank();
+
+		// simulate a vblank after one refresh cycle
+		let frame_time = Duration::from_micros(1_000_000_000 / REFRESH as u64);
+		let output = output.clone();
+		mayland
+			.loop_handle
+			.insert_source(Timer::from_duration(frame_time), move |_, (), state| {
+				// the output might have been removed in the meantime
+				if let Some(output_state) = state.mayland.output_state.get_mut(&output) {
+					output_state.queued.on_vblank();
+					state.mayland.send_frame_callbacks(&output);
+				}
+
+				TimeoutAction::Drop
+			})
+			.unwrap();
+
+		true
+	}
+
+	pub fn comm_outputs(&self) -> Vec<mayland_comm::Output> {
+		self.outputs
+			.iter()
+			.map(|output| {
+				let mode = output.current_mode().map(|mode| mayland_comm::output::Mode {
+					w: mode.size.w.clamp(0, u16::MAX as i32) as u16,
+					h: mode.size.h.clamp(0, u16::MAX as i32) as u16,
+					refresh: mode.refresh as u32,
+
+					preferred: true,
+				});
+
+				let physical = output.physical_properties();
+				let logical = logical_output(output);
+
+				mayland_comm::Output {
+					name: output.name(),
+					mode,
+					make: physical.make,
+					model: physical.model,
+					serial: None,
+					size: None,
+					logical: Some(logical),
+					modes: mode.into_iter().collect(),
+				}
+			})
+			.collect()
+	}
+
+	pub fn reload_output_config(&mut self, mayland: &mut Mayland) -> bool {
+		for output in &self.outputs {
+			let output_info = output.user_data().get::<OutputInfo>().unwrap();
+			let config = mayland.config.output.get_output(output_info);
+
+			let mut changed = false;
+
+			let scale = output_scale(config);
+			if output.current_scale() != scale {
+				output.change_current_state(None, None, Some(scale), None);
+				changed = true;
+			}
+
+			let transform = output_transform(config);
+			if output.current_transform() != transform {
+				output.change_current_state(None, Some(transform), None, None);
+				changed = true;
+			}
+
+			if changed {
+				mayland.output_size_changed(output);
+			}
+		}
+
+		mayland.reconfigure_outputs();
+		mayland.queue_redraw_all();
+
+		true
+	}
+}
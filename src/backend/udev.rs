@@ -1,8 +1,8 @@
 use crate::{
 	input::{apply_libinput_settings, device::InputDevice},
 	render::{MaylandRenderElements, shaders},
-	state::{Mayland, State},
-	utils::logical_output,
+	state::{Mayland, QueueState, State},
+	utils::{logical_output, output_scale, output_transform},
 };
 use mayland_config::outputs::OutputInfo;
 use smithay::{
@@ -21,7 +21,11 @@ use smithay::{
 		egl::{EGLContext, EGLDevice, EGLDisplay},
 		input::InputEvent,
 		libinput::{LibinputInputBackend, LibinputSessionInterface},
-		renderer::{ImportDma, ImportEgl, glow::GlowRenderer},
+		renderer::{
+			ImportDma, ImportEgl,
+			element::utils::{Relocate, RelocateRenderElement, RescaleRenderElement},
+			glow::GlowRenderer,
+		},
 		session::{Event as SessionEvent, Session, libseat::LibSeatSession},
 		udev::{self, UdevBackend, UdevEvent},
 	},
@@ -35,7 +39,7 @@ use smithay::{
 		rustix::fs::{Dev as dev_t, OFlags},
 		wayland_protocols::wp::presentation_time::server::wp_presentation_feedback,
 	},
-	utils::{DeviceFd, Monotonic, Size},
+	utils::{DeviceFd, Monotonic, Physical, Point, Size},
 	wayland::{
 		dmabuf::{DmabufFeedbackBuilder, DmabufGlobal},
 		presentation::Refresh,
@@ -52,12 +56,10 @@ use std::{
 	time::Duration,
 };
 
-type GbmDrmCompositor = DrmCompositor<
-	GbmAllocator<DrmDeviceFd>,
-	GbmFramebufferExporter<DrmDeviceFd>,
-	OutputPresentationFeedback,
-	DrmDeviceFd,
->;
+type GbmDrmCompositor<U = OutputPresentationFeedback> =
+	DrmCompositor<GbmAllocator<DrmDeviceFd>, GbmFramebufferExporter<DrmDeviceFd>, U, DrmDeviceFd>;
+
+type MirrorRenderElements = RelocateRenderElement<RescaleRenderElement<MaylandRenderElements>>;
 
 const SUPPORTED_COLOR_FORMATS: &[Fourcc] = &[Fourcc::Argb8888, Fourcc::Abgr8888];
 
@@ -72,6 +74,7 @@ pub struct OutputDevice {
 	glow: GlowRenderer,
 	formats: FormatSet,
 	surfaces: HashMap<crtc::Handle, Surface>,
+	mirrors: HashMap<crtc::Handle, Mirror>,
 }
 
 #[derive(Debug)]
@@ -80,6 +83,71 @@ struct Surface {
 	compositor: GbmDrmCompositor,
 }
 
+/// an output that shows the contents of another output
+#[derive(Debug)]
+struct Mirror {
+	info: OutputInfo,
+	/// the connector name of the mirrored output
+	source: String,
+	/// only passes the mode, scale and transform to the compositor,
+	/// this output isn't mapped and isn't advertised to clients
+	output: Output,
+	compositor: GbmDrmCompositor<()>,
+	/// a frame is queued and waiting for vblank
+	queued: bool,
+	/// the source output got redrawn while a frame was queued
+	damaged: bool,
+}
+
+impl OutputDevice {
+	/// turn off all outputs that mirror the output with the connector name,
+	/// they are turned on again by the next frame of the output
+	fn clear_mirrors(&mut self, name: &str) {
+		for mirror in (self.mirrors.values_mut()).filter(|mirror| mirror.source == name) {
+			if let Err(err) = mirror.compositor.clear() {
+				tracing::warn!("failed to turn off mirror: {:?}", err);
+			}
+		}
+	}
+
+	fn create_compositor<U>(
+		&self,
+		connector: &connector::Info,
+		crtc: crtc::Handle,
+		mode: control::Mode,
+		output: &Output,
+	) -> GbmDrmCompositor<U> {
+		let surface = self
+			.drm
+			.create_surface(crtc, mode, &[connector.handle()])
+			.unwrap();
+
+		let mut planes = surface.planes().clone();
+
+		// overlay planes need to be cleared when switching vt to
+		// avoid the windows getting stuck on the monitor when switching
+		// to a compositor that doesn't clean overlay planes on activate
+		// todo find a better way to do this
+		planes.overlay.clear();
+
+		let gbm_flags = GbmBufferFlags::RENDERING | GbmBufferFlags::SCANOUT;
+		let allocator = GbmAllocator::new(self.gbm.clone(), gbm_flags);
+
+		DrmCompositor::new(
+			OutputModeSource::Auto(output.downgrade()),
+			surface,
+			Some(planes),
+			allocator,
+			GbmFramebufferExporter::new(self.gbm.clone(), NodeFilter::Node(self.render_node)),
+			SUPPORTED_COLOR_FORMATS.iter().copied(),
+			self.formats.clone(),
+			self.drm.cursor_size(),
+			Some(self.gbm.clone()),
+		)
+		.unwrap()
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct UdevOutputState {
 	pub device_id: dev_t,
@@ -153,7 +221,14 @@ impl Udev {
 }
 
 impl Udev {
-	pub fn render(&mut self, mayland: &mut Mayland, output: &Output, elements: &[MaylandRenderElements]) {
+	pub fn render(
+		&mut self,
+		mayland: &mut Mayland,
+		output: &Output,
+		elements: &[MaylandRenderElements],
+	) -> bool {
+		self.render_mirrors(mayland, output);
+
 		let device = self.output_device.as_mut().unwrap();
 		let udev_state = output.user_data().get::<UdevOutputState>().unwrap();
 		let surface = device.surfaces.get_mut(&udev_state.crtc).unwrap();
@@ -161,8 +236,9 @@ impl Udev {
 		let drm_compositor = &mut surface.compositor;
 		match drm_compositor.render_frame(&mut device.glow, elements, [0.; 4], FrameFlags::DEFAULT) {
 			Ok(render_output_res) => {
+				// without damage the output already shows these elements
 				if render_output_res.is_empty {
-					return;
+					return true;
 				}
 
 				let output_presentation_feedback =
@@ -172,13 +248,80 @@ impl Udev {
 					Ok(()) => {
 						let output_state = mayland.output_state.get_mut(output).unwrap();
 						output_state.queued.waiting_for_vblank();
+						true
+					}
+					Err(err) => {
+						tracing::error!("error queueing frame {:?}", err);
+						false
 					}
-					Err(err) => tracing::error!("error queueing frame {:?}", err),
 				}
 			}
 			Err(err) => {
 				drm_compositor.reset_buffers();
 				tracing::error!("error rendering frame {:?}", err);
+				false
+			}
+		}
+	}
+
+	/// render the elements of the output onto all outputs that mirror it,
+	/// scaled to fit and centered
+	fn render_mirrors(&mut self, mayland: &mut Mayland, output: &Output) {
+		let device = self.output_device.as_mut().unwrap();
+
+		let name = output.name();
+		if !(device.mirrors.values()).any(|mirror| mirror.source == name) {
+			return;
+		}
+
+		let Some(source_mode) = output.current_mode() else { return };
+		let source_scale = output.current_scale();
+		let source_size = output.current_transform().transform_size(source_mode.size);
+
+		for mirror in (device.mirrors.values_mut()).filter(|mirror| mirror.source == name) {
+			if mirror.queued {
+				mirror.damaged = true;
+				continue;
+			}
+
+			// the elements are created with the scale of the source
+			// output, so the mirror has to use the same scale
+			let mirror_output = &mirror.output;
+			mirror_output.change_current_state(None, None, Some(source_scale), None);
+
+			let mode = mirror.compositor.pending_mode();
+			let size = Size::<i32, Physical>::from((i32::from(mode.size().0), i32::from(mode.size().1)));
+			let size = mirror_output.current_transform().transform_size(size);
+
+			let factor = f64::min(
+				f64::from(size.w) / f64::from(source_size.w),
+				f64::from(size.h) / f64::from(source_size.h),
+			);
+			let scaled = source_size.to_f64().upscale(factor).to_i32_round();
+			let offset = Point::from(((size.w - scaled.w) / 2, (size.h - scaled.h) / 2));
+
+			let elements = mayland.elements(&mut device.glow, output, true);
+			let elements = (elements.into_iter())
+				.map(|element| RescaleRenderElement::from_element(element, Point::from((0, 0)), factor))
+				.map(|element| RelocateRenderElement::from_element(element, offset, Relocate::Relative))
+				.collect::<Vec<MirrorRenderElements>>();
+
+			let drm_compositor = &mut mirror.compositor;
+			match drm_compositor.render_frame(&mut device.glow, &elements, [0.; 4], FrameFlags::DEFAULT) {
+				Ok(render_output_res) => {
+					if render_output_res.is_empty {
+						continue;
+					}
+
+					match drm_compositor.queue_frame(()) {
+						Ok(()) => mirror.queued = true,
+						Err(err) => tracing::error!("error queueing mirror frame {:?}", err),
+					}
+				}
+				Err(err) => {
+					drm_compositor.reset_buffers();
+					tracing::error!("error rendering mirror frame {:?}", err);
+				}
 			}
 		}
 	}
@@ -191,6 +334,34 @@ impl Udev {
 		self.session.change_vt(vt).unwrap();
 	}
 
+	pub fn set_output_power(&mut self, mayland: &mut Mayland, output: &Output, powered: bool) {
+		let Some(device) = &mut self.output_device else { return };
+
+		// the crtc is enabled again by the next frame after clearing
+		if powered {
+			// the vblanks of frames that were queued before clearing never arrive
+			let output_state = mayland.output_state.get_mut(output).unwrap();
+			output_state.queued = QueueState::Idle;
+
+			let name = output.name();
+			for mirror in (device.mirrors.values_mut()).filter(|mirror| mirror.source == name) {
+				mirror.queued = false;
+			}
+
+			return;
+		}
+
+		let udev_state = output.user_data().get::<UdevOutputState>().unwrap();
+
+		if let Some(surface) = device.surfaces.get_mut(&udev_state.crtc)
+			&& let Err(err) = surface.compositor.clear()
+		{
+			tracing::warn!("failed to turn off output: {:?}", err);
+		}
+
+		device.clear_mirrors(&output.name());
+	}
+
 	pub fn import_dmabuf(&mut self, dmabuf: &Dmabuf) -> bool {
 		let Some(output_device) = self.output_device.as_mut() else {
 			return false;
@@ -209,7 +380,9 @@ impl Udev {
 		if let Some(device) = &self.output_device {
 			for (connector, crtc) in device.drm_scanner.crtcs() {
 				let surface = device.surfaces.get(&crtc);
-				let mode = surface.map(|surface| surface.compositor.pending_mode());
+				let mirror = device.mirrors.get(&crtc);
+				let mode = (surface.map(|surface| surface.compositor.pending_mode()))
+					.or_else(|| mirror.map(|mirror| mirror.compositor.pending_mode()));
 				let mode = mode.map(|mode| mayland_comm::output::Mode {
 					w: mode.size().0,
 					h: mode.size().1,
@@ -258,29 +431,105 @@ impl Udev {
 		outputs
 	}
 
-	pub fn reload_output_config(&mut self, mayland: &mut Mayland) {
-		let Some(device) = &mut self.output_device else { return };
+	pub fn reload_output_config(&mut self, mayland: &mut Mayland) -> bool {
+		let Some(device) = &mut self.output_device else { return true };
 
+		// outputs that got disabled or enabled or that started or stopped
+		// mirroring another output have to be set up from scratch
+		let reconnect = (device.drm_scanner.crtcs())
+			.filter(|(connector, crtc)| {
+				let info = output_info(&device.drm, connector);
+				let config = mayland.config.output.get_output(&info);
+
+				let disabled = config.is_some_and(|conf| conf.disable);
+				let mirror = config.and_then(|conf| mirror_source(conf, &info));
+
+				let enabled = device.surfaces.contains_key(crtc) || device.mirrors.contains_key(crtc);
+				let mirroring = device.mirrors.get(crtc).map(|mirror| &*mirror.source);
+
+				disabled == enabled || (!disabled && mirror != mirroring)
+			})
+			.map(|(connector, crtc)| (connector.clone(), crtc))
+			.collect::<Vec<_>>();
+
+		for (connector, crtc) in reconnect {
+			self.disable_crtc(crtc, mayland);
+			self.connector_connected(connector, crtc, mayland);
+		}
+
+		let device = self.output_device.as_mut().unwrap();
+
+		let mut success = true;
 		for (connector, crtc) in device.drm_scanner.crtcs() {
-			let surface = device.surfaces.get_mut(&crtc).unwrap();
+			if let Some(mirror) = device.mirrors.get_mut(&crtc) {
+				let config = mayland.config.output.get_output(&mirror.info);
+
+				let mirror_output = &mirror.output;
+
+				let mode = pick_mode(connector, config.and_then(|conf| conf.mode));
+				if mirror.compositor.pending_mode() != mode {
+					if let Err(err) = mirror.compositor.use_mode(mode) {
+						tracing::warn!(
+							"failed to set mode of output {:?}: {:?}",
+							mirror_output.name(),
+							err
+						);
+						success = false;
+						continue;
+					}
+
+					mirror_output.change_current_state(Some(Mode::from(mode)), None, None, None);
+				}
+
+				let transform = output_transform(config);
+				mirror_output.change_current_state(None, Some(transform), None, None);
+
+				continue;
+			}
+
+			let Some(surface) = device.surfaces.get_mut(&crtc) else { continue };
 
 			let config = mayland.config.output.get_output(&surface.info);
 			let output = mayland.workspaces.udev_output(device.id, crtc).cloned().unwrap();
 
+			let mut changed = false;
+
 			let mode = pick_mode(connector, config.and_then(|conf| conf.mode));
 			if surface.compositor.pending_mode() != mode {
-				surface.compositor.use_mode(mode).unwrap();
+				if let Err(err) = surface.compositor.use_mode(mode) {
+					tracing::warn!("failed to set mode of output {:?}: {:?}", output.name(), err);
+					success = false;
+					continue;
+				}
 
 				let wl_mode = Mode::from(mode);
 				output.change_current_state(Some(wl_mode), None, None, None);
 				output.set_preferred(wl_mode);
 
+				changed = true;
+			}
+
+			let scale = output_scale(config);
+			if output.current_scale() != scale {
+				output.change_current_state(None, None, Some(scale), None);
+				changed = true;
+			}
+
+			let transform = output_transform(config);
+			if output.current_transform() != transform {
+				output.change_current_state(None, Some(transform), None, None);
+				changed = true;
+			}
+
+			if changed {
 				mayland.output_size_changed(&output);
 			}
 		}
 
 		mayland.reconfigure_outputs();
 		mayland.queue_redraw_all();
+
+		success
 	}
 }
 
@@ -435,6 +684,7 @@ impl Udev {
 			glow,
 			formats,
 			surfaces: HashMap::new(),
+			mirrors: HashMap::new(),
 		};
 		self.output_device = Some(output_device);
 
@@ -508,23 +758,12 @@ impl Udev {
 		tracing::info!("connecting connector: {:?}", output_info);
 
 		let config = mayland.config.output.get_output(&output_info);
-		let mode = pick_mode(&connector, config.and_then(|conf| conf.mode));
-
-		let surface = device
-			.drm
-			.create_surface(crtc, mode, &[connector.handle()])
-			.unwrap();
-
-		let mut planes = surface.planes().clone();
-
-		// overlay planes need to be cleared when switching vt to
-		// avoid the windows getting stuck on the monitor when switching
-		// to a compositor that doesn't clean overlay planes on activate
-		// todo find a better way to do this
-		planes.overlay.clear();
+		if config.is_some_and(|conf| conf.disable) {
+			tracing::info!("output {:?} is disabled", output_info.connector);
+			return;
+		}
 
-		let gbm_flags = GbmBufferFlags::RENDERING | GbmBufferFlags::SCANOUT;
-		let allocator = GbmAllocator::new(device.gbm.clone(), gbm_flags);
+		let mode = pick_mode(&connector, config.and_then(|conf| conf.mode));
 
 		let (physical_width, physical_height) = connector.size().unwrap_or((0, 0));
 
@@ -540,28 +779,44 @@ impl Udev {
 		);
 
 		let wl_mode = Mode::from(mode);
-		output.change_current_state(Some(wl_mode), None, None, None);
+		let scale = output_scale(config);
+		let transform = output_transform(config);
+		output.change_current_state(Some(wl_mode), Some(transform), Some(scale), None);
 		output.set_preferred(wl_mode);
 
+		if let Some(source) = config.and_then(|conf| mirror_source(conf, &output_info)) {
+			tracing::info!("output {:?} mirrors {:?}", output_info.connector, source);
+
+			let source = source.to_owned();
+			let compositor = device.create_compositor(&connector, crtc, mode, &output);
+
+			let mirror = Mirror {
+				info: output_info,
+				source: source.clone(),
+				output,
+				compositor,
+				queued: false,
+				damaged: false,
+			};
+			let prev = device.mirrors.insert(crtc, mirror);
+			assert!(prev.is_none(), "crtc must not have already existed");
+
+			// the mirror gets its first frame when the source is redrawn
+			if let Some(source) = mayland.workspaces.output_by_name(&source).cloned() {
+				mayland.queue_redraw(source);
+			}
+
+			return;
+		}
+
+		let compositor = device.create_compositor(&connector, crtc, mode, &output);
+
 		output.user_data().insert_if_missing(|| output_info.clone());
 		output.user_data().insert_if_missing(|| UdevOutputState {
 			device_id: device.id,
 			crtc,
 		});
 
-		let compositor = DrmCompositor::new(
-			OutputModeSource::Auto(output.downgrade()),
-			surface,
-			Some(planes),
-			allocator,
-			GbmFramebufferExporter::new(device.gbm.clone(), NodeFilter::Node(device.render_node)),
-			SUPPORTED_COLOR_FORMATS.iter().copied(),
-			device.formats.clone(),
-			device.drm.cursor_size(),
-			Some(device.gbm.clone()),
-		)
-		.unwrap();
-
 		let surface = Surface {
 			info: output_info,
 			compositor,
@@ -582,17 +837,57 @@ impl Udev {
 		tracing::info!("disconnecting connector {:?}", connector);
 		let device = self.output_device.as_mut().unwrap();
 
+		if device.mirrors.remove(&crtc).is_some() {
+			return;
+		}
+
 		if device.surfaces.remove(&crtc).is_none() {
 			tracing::info!("crtc wasn't enabled");
 			return;
 		}
 
 		let output = mayland.workspaces.udev_output(device.id, crtc).unwrap().clone();
+		device.clear_mirrors(&output.name());
 		mayland.remove_output(&output);
 	}
 
+	/// turn off the crtc of a connector that is still connected
+	fn disable_crtc(&mut self, crtc: crtc::Handle, mayland: &mut Mayland) {
+		let device = self.output_device.as_mut().unwrap();
+
+		if let Some(mut mirror) = device.mirrors.remove(&crtc) {
+			if let Err(err) = mirror.compositor.clear() {
+				tracing::warn!("failed to turn off mirror: {:?}", err);
+			}
+		} else if let Some(mut surface) = device.surfaces.remove(&crtc) {
+			if let Err(err) = surface.compositor.clear() {
+				tracing::warn!("failed to turn off output: {:?}", err);
+			}
+
+			let output = mayland.workspaces.udev_output(device.id, crtc).unwrap().clone();
+			device.clear_mirrors(&output.name());
+			mayland.remove_output(&output);
+		}
+	}
+
 	fn on_vblank(&mut self, mayland: &mut Mayland, crtc: crtc::Handle, meta: DrmEventMetadata) {
 		let device = self.output_device.as_mut().unwrap();
+
+		if let Some(mirror) = device.mirrors.get_mut(&crtc) {
+			if let Err(err) = mirror.compositor.frame_submitted() {
+				tracing::error!("error marking frame as submitted {}", err);
+			}
+
+			mirror.queued = false;
+			if std::mem::take(&mut mirror.damaged)
+				&& let Some(source) = mayland.workspaces.output_by_name(&mirror.source).cloned()
+			{
+				mayland.queue_redraw(source);
+			}
+
+			return;
+		}
+
 		let Some(surface) = device.surfaces.get_mut(&crtc) else {
 			tracing::warn!("missing crtc {:?} in vblannk callback", crtc);
 			return;
@@ -659,6 +954,11 @@ fn output_info(drm: &DrmDevice, connector: &connector::Info) -> OutputInfo {
 	}
 }
 
+/// the connector name of the output to mirror, an output can't mirror itself
+fn mirror_source<'a>(config: &'a mayland_config::outputs::Output, info: &OutputInfo) -> Option<&'a str> {
+	(config.mirror.as_deref()).filter(|source| *source != info.connector)
+}
+
 fn pick_mode(connector: &connector::Info, target: Option<mayland_config::outputs::Mode>) -> control::Mode {
 	// try to match a mode from config
 	if let Some(target) = target {
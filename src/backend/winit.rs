@@ -1,7 +1,7 @@
 use crate::{
 	render::{MaylandRenderElements, shaders},
 	state::{Mayland, State},
-	utils::logical_output,
+	utils::{logical_output, output_scale},
 };
 use mayland_config::outputs::OutputInfo;
 use smithay::{
@@ -49,16 +49,20 @@ impl Winit {
 			},
 		);
 
-		let _global = output.create_global::<State>(&mayland.display_handle);
-		output.change_current_state(Some(mode), Some(Transform::Flipped180), None, None);
-		output.set_preferred(mode);
-
-		output.user_data().insert_if_missing(|| OutputInfo {
+		let output_info = OutputInfo {
 			connector: "winit".to_owned(),
 			make: "may".to_owned(),
 			model: "winit".to_owned(),
 			serial: None,
-		});
+		};
+
+		let scale = output_scale(mayland.config.output.get_output(&output_info));
+
+		let _global = output.create_global::<State>(&mayland.display_handle);
+		output.change_current_state(Some(mode), Some(Transform::Flipped180), Some(scale), None);
+		output.set_preferred(mode);
+
+		output.user_data().insert_if_missing(|| output_info);
 
 		mayland.add_output(output.clone());
 
@@ -106,7 +110,12 @@ impl Winit {
 }
 
 impl Winit {
-	pub fn render(&mut self, mayland: &mut Mayland, output: &Output, elements: &[MaylandRenderElements]) {
+	pub fn render(
+		&mut self,
+		mayland: &mut Mayland,
+		output: &Output,
+		elements: &[MaylandRenderElements],
+	) -> bool {
 		let result = {
 			let (renderer, mut fb) = self.backend.bind().unwrap();
 			self.damage_tracker
@@ -122,6 +131,8 @@ impl Winit {
 			// ask for redraw to schedule new frame.
 			self.backend.window().request_redraw();
 		}
+
+		true
 	}
 
 	pub fn renderer(&mut self) -> &mut GlowRenderer {
@@ -161,9 +172,18 @@ impl Winit {
 		vec![output]
 	}
 
-	pub fn reload_output_config(&mut self, mayland: &mut Mayland) {
+	pub fn reload_output_config(&mut self, mayland: &mut Mayland) -> bool {
+		let output_info = self.output.user_data().get::<OutputInfo>().unwrap();
+		let scale = output_scale(mayland.config.output.get_output(output_info));
+		if self.output.current_scale() != scale {
+			self.output.change_current_state(None, None, Some(scale), None);
+			mayland.output_size_changed(&self.output);
+		}
+
 		mayland.reconfigure_outputs();
 		mayland.queue_redraw(self.output.clone());
+
+		true
 	}
 }
 
@@ -1,17 +1,28 @@
-use crate::State;
+use crate::{State, screenshot::screenshot_name};
 use calloop::{LoopHandle, io::Async};
-use futures_util::{AsyncBufReadExt, AsyncWriteExt};
-use mayland_comm::{Request, Response};
+use futures_util::{
+	AsyncBufReadExt, AsyncWriteExt,
+	future::{Either, select},
+	io::BufReader,
+};
+use mayland_comm::{Event, Request, Response};
 use mayland_config::{Action, CONFIG_PATH, bind::CompMod};
 use smithay::reexports::calloop::{Interest, Mode, PostAction, generic::Generic};
 use std::{
 	os::unix::net::{UnixListener, UnixStream},
 	path::PathBuf,
+	pin::pin,
 };
 
+/// the amount of events that can be queued for a subscriber,
+/// subscribers that fall further behind get disconnected
+const EVENT_QUEUE_SIZE: usize = 256;
+
 #[derive(Debug)]
 pub struct MaySocket {
 	pub path: PathBuf,
+	/// clients subscribed to the event stream
+	subscribers: Vec<async_channel::Sender<String>>,
 }
 
 fn socket_path(wayland_socket_name: &str) -> PathBuf {
@@ -42,7 +53,23 @@ impl MaySocket {
 			})
 			.unwrap();
 
-		MaySocket { path: socket_path }
+		MaySocket {
+			path: socket_path,
+			subscribers: Vec::new(),
+		}
+	}
+
+	/// send an [`Event`] to all subscribed clients
+	pub fn send_event(&mut self, event: Event) {
+		if self.subscribers.is_empty() {
+			return;
+		}
+
+		let event = serde_json::to_string(&event).unwrap();
+		// the channel is closed once the subscriber disconnected,
+		// and full if the subscriber stopped reading events
+		self.subscribers
+			.retain(|subscriber| subscriber.try_send(event.clone()).is_ok());
 	}
 }
 
@@ -68,12 +95,36 @@ struct SocketState {
 }
 
 async fn handle_client(mut stream: Async<'_, UnixStream>, state: SocketState) -> Result<(), std::io::Error> {
-	let mut read = futures_util::io::BufReader::new(&mut stream);
+	let mut read = BufReader::new(&mut stream);
 	let mut buf = String::new();
-	read.read_line(&mut buf).await?;
 
-	let request = serde_json::from_str::<Request>(&buf);
-	let response = match request {
+	// every line is a separate request, which are answered in order
+	loop {
+		buf.clear();
+		if read.read_line(&mut buf).await? == 0 {
+			return Ok(());
+		}
+
+		if buf.trim().is_empty() {
+			continue;
+		}
+
+		let request = serde_json::from_str::<Request>(&buf);
+		if let Ok(Request::Subscribe) = request {
+			return subscribe(&mut read, &state).await;
+		}
+
+		let response = handle_request(request, &state).await;
+
+		let response = serde_json::to_vec(&response).unwrap();
+		let stream = read.get_mut();
+		stream.write_all(&response).await?;
+		stream.write_all(b"\n").await?;
+	}
+}
+
+async fn handle_request(request: Result<Request, serde_json::Error>, state: &SocketState) -> Response {
+	match request {
 		Ok(Request::Dispatch(action)) => {
 			let action = Action::from(action);
 			let (tx, rx) = async_channel::bounded(1);
@@ -88,6 +139,22 @@ async fn handle_client(mut stream: Async<'_, UnixStream>, state: SocketState) ->
 				Err(err) => Response::Err(err),
 			}
 		}
+		Ok(Request::Batch(actions)) => {
+			let (tx, rx) = async_channel::bounded(1);
+			// all actions are handled in the same idle callback,
+			// so nothing else can happen in between them
+			state.event_loop.insert_idle(move |state| {
+				let ret =
+					(actions.into_iter()).try_for_each(|action| state.handle_action(Action::from(action)));
+				let _ = tx.send_blocking(ret);
+			});
+
+			let ret = rx.recv().await.unwrap();
+			match ret {
+				Ok(()) => Response::Dispatch,
+				Err(err) => Response::Err(err),
+			}
+		}
 		Ok(Request::Reload) => 'reload: {
 			let Ok(config) = mayland_config::Config::read(&CONFIG_PATH, state.comp_mod) else {
 				break 'reload Response::Err(mayland_comm::Error::FailedToReadConfig(CONFIG_PATH.clone()));
@@ -170,13 +237,141 @@ async fn handle_client(mut stream: Async<'_, UnixStream>, state: SocketState) ->
 			let workspaces = rx.recv().await.unwrap();
 			Response::Workspaces(workspaces)
 		}
+		Ok(Request::Screenshot { output, path }) => 'screenshot: {
+			let (tx, rx) = async_channel::bounded(1);
+			state.event_loop.insert_idle(move |state| {
+				let ret = state.screenshot_output(output.as_deref()).map(|screenshot| {
+					let path = path.unwrap_or_else(|| {
+						let mut path = state.mayland.config.screenshot.directory();
+						path.push(screenshot_name());
+						path
+					});
+
+					(screenshot, path)
+				});
+
+				let _ = tx.send_blocking(ret);
+			});
+
+			let (screenshot, path) = match rx.recv().await.unwrap() {
+				Ok(ret) => ret,
+				Err(err) => break 'screenshot Response::Err(err),
+			};
+
+			match screenshot.save_in_background(path.clone()).recv().await.unwrap() {
+				Ok(()) => Response::Screenshot(path),
+				Err(err) => Response::Err(mayland_comm::Error::ScreenshotFailed(err)),
+			}
+		}
+		Ok(Request::ConfigureOutput {
+			name,
+			mode,
+			position,
+			active,
+			scale,
+			transform,
+		}) => {
+			let (tx, rx) = async_channel::bounded(1);
+			state.event_loop.insert_idle(move |state| {
+				let ret = state.configure_output(&name, |config| {
+					if let Some(mode) = mode {
+						config.mode = Some(mayland_config::outputs::Mode::from(mode));
+					}
+					if let Some(position) = position {
+						config.position = Some(position);
+					}
+					// an inactive output is turned off, and an active one stops mirroring to get mapped
+					if let Some(active) = active {
+						config.active = active;
+						config.disable = !active;
+						if active {
+							config.mirror = None;
+						}
+					}
+					if let Some(scale) = scale {
+						config.scale = Some(scale);
+					}
+					if let Some(transform) = transform {
+						config.transform = mayland_config::outputs::Transform::from(transform);
+					}
+				});
+
+				if ret.is_ok()
+					&& active == Some(true)
+					&& let Some(output) = state.mayland.workspaces.output_by_name(&name).cloned()
+				{
+					state.focus_output(&output);
+				}
+
+				let _ = tx.send_blocking(ret);
+			});
+
+			match rx.recv().await.unwrap() {
+				Ok(_) => Response::ConfigureOutput,
+				Err(err) => Response::Err(err),
+			}
+		}
+		Ok(Request::Subscribe) => unreachable!("subscribe is handled by the caller"),
 		Err(_) => Response::Err(mayland_comm::Error::InvalidRequest),
-	};
+	}
+}
+
+async fn subscribe(
+	read: &mut BufReader<&mut Async<'_, UnixStream>>,
+	state: &SocketState,
+) -> Result<(), std::io::Error> {
+	let (tx, rx) = async_channel::bounded(EVENT_QUEUE_SIZE);
+	state.event_loop.insert_idle(move |state| {
+		state.mayland.may_socket.subscribers.push(tx);
+	});
 
-	let response = serde_json::to_vec(&response).unwrap();
+	let response = serde_json::to_vec(&Response::Subscribe).unwrap();
+	let stream = read.get_mut();
 	stream.write_all(&response).await?;
 	stream.write_all(b"\n").await?;
 
+	// the connection only carries events after subscribing, so anything
+	// else the client sends is read and skipped, until it shuts down writing
+	let mut shutdown = false;
+	loop {
+		let received = if shutdown {
+			Ok(rx.recv().await)
+		} else {
+			let recv = pin!(rx.recv());
+			match select(recv, read.fill_buf()).await {
+				Either::Left((event, _)) => Ok(event),
+				Either::Right((buf, _)) => Err(buf?.len()),
+			}
+		};
+
+		let event = match received {
+			Ok(Ok(event)) => event,
+			// the subscriber fell too far behind and got dropped
+			Ok(Err(_)) => break,
+			Err(0) => {
+				shutdown = true;
+				continue;
+			}
+			Err(len) => {
+				read.consume_unpin(len);
+				continue;
+			}
+		};
+
+		let stream = read.get_mut();
+		let written = async {
+			stream.write_all(event.as_bytes()).await?;
+			stream.write_all(b"\n").await
+		};
+
+		match written.await {
+			Ok(()) => {}
+			// the subscriber disconnected
+			Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => break,
+			Err(err) => return Err(err),
+		}
+	}
+
 	Ok(())
 }
 
@@ -28,7 +28,7 @@ pub struct Cursor {
 	theme: CursorTheme,
 	size: u32,
 
-	cache: HashMap<CursorIcon, XCursor>,
+	cache: HashMap<(CursorIcon, i32), XCursor>,
 }
 
 impl Cursor {
@@ -59,21 +59,24 @@ impl Cursor {
 		self.cache.clear();
 	}
 
-	fn get_named_cursor(&mut self, icon: CursorIcon) -> &XCursor {
-		self.cache.entry(icon).or_insert_with(|| {
-			// todo scale
-			let size = self.size;
+	fn get_named_cursor(&mut self, icon: CursorIcon, scale: i32) -> &XCursor {
+		self.cache.entry((icon, scale)).or_insert_with(|| {
+			let size = self.size * scale.unsigned_abs();
 
-			XCursor::load(&self.theme, icon, size)
-				.or_else(|| XCursor::load(&self.theme, CursorIcon::Default, size))
+			XCursor::load(&self.theme, icon, size, scale)
+				.or_else(|| XCursor::load(&self.theme, CursorIcon::Default, size, scale))
 				.unwrap_or_else(XCursor::fallback_cursor)
 		})
 	}
 
-	// todo scale
-	pub fn get_render_cursor(&mut self, _scale: i32) -> RenderCursor<'_> {
+	/// get the default cursor, used as the root cursor for xwayland
+	pub fn default_cursor(&mut self) -> &XCursor {
+		self.get_named_cursor(CursorIcon::Default, 1)
+	}
+
+	pub fn get_render_cursor(&mut self, scale: i32) -> RenderCursor<'_> {
 		if let Some(icon) = self.icon {
-			let xcursor = self.get_named_cursor(icon);
+			let xcursor = self.get_named_cursor(icon, scale);
 			return RenderCursor::Named(xcursor);
 		}
 
@@ -93,7 +96,7 @@ impl Cursor {
 				RenderCursor::Surface { hotspot, surface }
 			}
 			CursorImageStatus::Named(icon) => {
-				let xcursor = self.get_named_cursor(icon);
+				let xcursor = self.get_named_cursor(icon, scale);
 				RenderCursor::Named(xcursor)
 			}
 		}
@@ -122,17 +125,28 @@ fn load_cursor_theme(
 #[derive(Debug)]
 pub struct Frame {
 	image: Image,
+	/// the buffer scale of the image
+	scale: i32,
 	buffer: OnceCell<MemoryRenderBuffer>,
 }
 
 impl Frame {
-	pub fn new(image: Image) -> Self {
+	pub fn new(image: Image, scale: i32) -> Self {
 		Frame {
 			image,
+			scale,
 			buffer: OnceCell::new(),
 		}
 	}
 
+	pub fn scale(&self) -> i32 {
+		self.scale
+	}
+
+	pub fn image(&self) -> &Image {
+		&self.image
+	}
+
 	pub fn hotspot(&self) -> Point<i32, Physical> {
 		Point::new(self.image.xhot as i32, self.image.yhot as i32)
 	}
@@ -143,7 +157,7 @@ impl Frame {
 				&self.image.pixels_rgba,
 				Fourcc::Argb8888,
 				(self.image.width as i32, self.image.height as i32),
-				1,
+				self.scale,
 				Transform::Normal,
 				None,
 			)
@@ -158,7 +172,7 @@ pub struct XCursor {
 }
 
 impl XCursor {
-	fn load(theme: &CursorTheme, icon: CursorIcon, size: u32) -> Option<Self> {
+	fn load(theme: &CursorTheme, icon: CursorIcon, size: u32, scale: i32) -> Option<Self> {
 		let icon_path = theme.load_icon(icon.name())?;
 		let mut cursor_file = std::fs::File::open(icon_path).ok()?;
 		let mut cursor_data = Vec::new();
@@ -175,7 +189,7 @@ impl XCursor {
 		images.retain(|image| image.width == width && image.height == height);
 
 		let animation_duration = images.iter().fold(0, |acc, image| acc + image.delay);
-		let frames = images.into_iter().map(Frame::new).collect();
+		let frames = images.into_iter().map(|image| Frame::new(image, scale)).collect();
 
 		Some(XCursor {
 			frames,
@@ -194,7 +208,8 @@ impl XCursor {
 			pixels_rgba: Vec::from(FALLBACK_CURSOR_DATA),
 			pixels_argb: Vec::new(),
 		};
-		let frame = Frame::new(icon);
+		// the fallback cursor is not scaled
+		let frame = Frame::new(icon, 1);
 
 		XCursor {
 			frames: vec![frame],
@@ -1,5 +1,6 @@
 use self::device::InputDevice;
 use crate::{
+	layout::workspace::WindowMode,
 	shell::{
 		focus::{KeyboardFocusTarget, PointerFocusTarget},
 		window::MappedWindow,
@@ -7,13 +8,19 @@ use crate::{
 	state::State,
 	utils::{RectExt, spawn},
 };
-use mayland_config::{Action, input::TabletMapping};
+use mayland_comm::Event;
+use mayland_config::{
+	Action,
+	bind::{BTN_LEFT, CycleDirection, Direction, MouseAction},
+	input::TabletMapping,
+};
 use smithay::{
 	backend::input::{
-		AbsolutePositionEvent, Axis, AxisSource, Device, DeviceCapability, Event, InputBackend, InputEvent,
-		KeyState, KeyboardKeyEvent, Keycode, PointerAxisEvent, PointerButtonEvent, PointerMotionEvent,
-		ProximityState, TabletToolButtonEvent, TabletToolEvent, TabletToolProximityEvent, TabletToolTipEvent,
-		TabletToolTipState,
+		AbsolutePositionEvent, Axis, AxisSource, Device, DeviceCapability, Event, GestureBeginEvent,
+		GestureEndEvent, GesturePinchUpdateEvent as _, GestureSwipeUpdateEvent as _, InputBackend,
+		InputEvent, KeyState, KeyboardKeyEvent, Keycode, PointerAxisEvent, PointerButtonEvent,
+		PointerMotionEvent, ProximityState, TabletToolButtonEvent, TabletToolEvent, TabletToolProximityEvent,
+		TabletToolTipEvent, TabletToolTipState, TouchEvent,
 	},
 	desktop::{LayerSurface, WindowSurfaceType, layer_map_for_output},
 	input::{
@@ -21,14 +28,15 @@ use smithay::{
 			FilterResult, KeyboardHandle, KeysymHandle, ModifiersState,
 			keysyms::{KEY_XF86Switch_VT_1, KEY_XF86Switch_VT_12},
 		},
-		pointer::{AxisFrame, ButtonEvent, MotionEvent, RelativeMotionEvent},
+		pointer::{self, AxisFrame, ButtonEvent, GrabStartData, MotionEvent, RelativeMotionEvent},
+		touch::{DownEvent, MotionEvent as TouchMotionEvent, UpEvent},
 	},
 	output::Output,
 	reexports::{
 		input as libinput,
 		wayland_server::protocol::{wl_pointer, wl_surface::WlSurface},
 	},
-	utils::{Logical, Point, SERIAL_COUNTER, Serial},
+	utils::{Logical, Point, Rectangle, SERIAL_COUNTER, Serial},
 	wayland::{
 		input_method::InputMethodSeat,
 		seat::WaylandFocus,
@@ -40,11 +48,39 @@ use std::any::Any;
 
 pub mod device;
 
+/// the amount a grow or shrink action changes the split ratio by
+const SPLIT_STEP: f64 = 0.05;
+
+/// the distance a swipe gesture has to travel to trigger its bind
+const SWIPE_THRESHOLD: f64 = 50.;
+
+/// a swipe gesture that is handled by the compositor
+/// instead of being sent to the focussed client
+#[derive(Debug)]
+pub struct SwipeGesture {
+	fingers: u32,
+	delta: Point<f64, Logical>,
+}
+
 impl State {
 	pub fn handle_input_event<I: InputBackend>(&mut self, event: InputEvent<I>)
 	where
 		I::Device: 'static,
 	{
+		// plugging devices in or out doesn't count as activity
+		if !matches!(
+			event,
+			InputEvent::DeviceAdded { .. } | InputEvent::DeviceRemoved { .. }
+		) {
+			self.mayland
+				.idle_notifier_state
+				.notify_activity(&self.mayland.seat);
+
+			if self.mayland.config.input.wake_outputs {
+				self.set_outputs_power(true);
+			}
+		}
+
 		match event {
 			InputEvent::DeviceAdded { device } => self.on_device_added(device),
 			InputEvent::DeviceRemoved { device } => self.on_device_removed(device),
@@ -57,22 +93,22 @@ impl State {
 			InputEvent::PointerButton { event } => self.on_pointer_button::<I>(event),
 			InputEvent::PointerAxis { event } => self.on_pointer_axis::<I>(event),
 
-			InputEvent::GestureSwipeBegin { .. } => (),
-			InputEvent::GestureSwipeUpdate { .. } => (),
-			InputEvent::GestureSwipeEnd { .. } => (),
+			InputEvent::GestureSwipeBegin { event } => self.on_gesture_swipe_begin::<I>(event),
+			InputEvent::GestureSwipeUpdate { event } => self.on_gesture_swipe_update::<I>(event),
+			InputEvent::GestureSwipeEnd { event } => self.on_gesture_swipe_end::<I>(event),
 
-			InputEvent::GesturePinchBegin { .. } => (),
-			InputEvent::GesturePinchUpdate { .. } => (),
-			InputEvent::GesturePinchEnd { .. } => (),
+			InputEvent::GesturePinchBegin { event } => self.on_gesture_pinch_begin::<I>(event),
+			InputEvent::GesturePinchUpdate { event } => self.on_gesture_pinch_update::<I>(event),
+			InputEvent::GesturePinchEnd { event } => self.on_gesture_pinch_end::<I>(event),
 
-			InputEvent::GestureHoldBegin { .. } => (),
-			InputEvent::GestureHoldEnd { .. } => (),
+			InputEvent::GestureHoldBegin { event } => self.on_gesture_hold_begin::<I>(event),
+			InputEvent::GestureHoldEnd { event } => self.on_gesture_hold_end::<I>(event),
 
-			InputEvent::TouchDown { .. } => tracing::info!("touch down"),
-			InputEvent::TouchMotion { .. } => tracing::info!("touch motion"),
-			InputEvent::TouchUp { .. } => tracing::info!("touch up"),
-			InputEvent::TouchCancel { .. } => tracing::info!("touch cancel"),
-			InputEvent::TouchFrame { .. } => tracing::info!("touch frame"),
+			InputEvent::TouchDown { event } => self.on_touch_down::<I>(event),
+			InputEvent::TouchMotion { event } => self.on_touch_motion::<I>(event),
+			InputEvent::TouchUp { event } => self.on_touch_up::<I>(event),
+			InputEvent::TouchCancel { .. } => self.on_touch_cancel(),
+			InputEvent::TouchFrame { .. } => self.on_touch_frame(),
 
 			InputEvent::TabletToolAxis { event } => self.on_tablet_tool_axis::<I>(event),
 			InputEvent::TabletToolProximity { event } => self.on_tablet_tool_proximity::<I>(event),
@@ -193,11 +229,33 @@ impl State {
 		let button = event.button_code();
 		let state = wl_pointer::ButtonState::from(event.state());
 
+		let pointer = self.mayland.pointer.clone();
+
 		if state == wl_pointer::ButtonState::Pressed {
-			self.update_keyboard_focus(self.mayland.pointer.current_location(), serial);
+			let location = pointer.current_location();
+			self.update_keyboard_focus(location, serial);
+
+			if !pointer.is_grabbed() {
+				let start_data = GrabStartData {
+					focus: None,
+					button,
+					location,
+				};
+
+				let mods = self.mayland.keyboard.modifier_state();
+				let action = self.mayland.config.bind.find_mouse_action(&mods, button);
+
+				if let Some(action) = action
+					&& !self.mayland.is_locked()
+				{
+					self.handle_mouse_action(action, start_data, serial);
+				} else if button == BTN_LEFT && !self.mayland.is_locked() && self.split_under(location) {
+					// drag the tiling split with the left mouse button
+					self.split_grab(start_data, serial);
+				}
+			}
 		}
 
-		let pointer = self.mayland.pointer.clone();
 		pointer.button(
 			self,
 			&ButtonEvent {
@@ -222,6 +280,18 @@ impl State {
 			.or_else(|| vertical_amount_v120.map(|amt| amt * 15. / 120.))
 			.unwrap_or(0.0);
 
+		// only scroll wheels trigger scroll binds
+		if matches!(event.source(), AxisSource::Wheel | AxisSource::WheelTilt) && !self.mayland.is_locked() {
+			let horizontal = (horizontal_amount_v120.filter(|amount| *amount != 0.))
+				.is_some_and(|amount| self.on_scroll_bind(Axis::Horizontal, amount));
+			let vertical = (vertical_amount_v120.filter(|amount| *amount != 0.))
+				.is_some_and(|amount| self.on_scroll_bind(Axis::Vertical, amount));
+
+			if horizontal || vertical {
+				return;
+			}
+		}
+
 		let mut frame = AxisFrame::new(event.time_msec()).source(event.source());
 		if horizontal_amount != 0.0 {
 			frame = frame.relative_direction(Axis::Horizontal, event.relative_direction(Axis::Horizontal));
@@ -251,6 +321,331 @@ impl State {
 		pointer.frame(self);
 	}
 
+	/// run the scroll bind for the scroll direction, if there is one
+	///
+	/// returns `false` if there is no scroll bind, and the event should be forwarded
+	fn on_scroll_bind(&mut self, axis: Axis, amount_v120: f64) -> bool {
+		let direction = match axis {
+			Axis::Horizontal if amount_v120 < 0. => Direction::Left,
+			Axis::Horizontal => Direction::Right,
+			Axis::Vertical if amount_v120 < 0. => Direction::Up,
+			Axis::Vertical => Direction::Down,
+		};
+
+		let mods = self.mayland.keyboard.modifier_state();
+		let Some(action) = self.mayland.config.bind.find_scroll_action(&mods, direction) else {
+			return false;
+		};
+
+		let accumulated = match axis {
+			Axis::Horizontal => &mut self.mayland.scroll_v120.0,
+			Axis::Vertical => &mut self.mayland.scroll_v120.1,
+		};
+
+		// high resolution scroll wheels send fractions of a wheel click
+		if accumulated.signum() != amount_v120.signum() {
+			*accumulated = 0.;
+		}
+		*accumulated += amount_v120;
+
+		let clicks = (*accumulated / 120.).trunc();
+		*accumulated -= clicks * 120.;
+
+		for _ in 0..clicks.abs() as usize {
+			self.handle_action(action.clone())
+				.expect("config should have validated");
+		}
+
+		true
+	}
+
+	/// is the location on the tiling split, without any surface above it?
+	fn split_under(&self, location: Point<f64, Logical>) -> bool {
+		// layer surfaces and override redirect windows above the split take the click
+		let under = self.surface_under(location);
+		self.mayland.workspaces.split_under(location)
+			&& matches!(under, Some((PointerFocusTarget::Window(_), _)))
+	}
+
+	/// start the grab of the [`MouseAction`] on the window under the pointer
+	fn handle_mouse_action(&mut self, action: MouseAction, start_data: GrabStartData<State>, serial: Serial) {
+		let Some((PointerFocusTarget::Window(window), _)) = self.surface_under(start_data.location) else {
+			return;
+		};
+
+		// fullscreen and maximized windows can't be moved or resized
+		if self.mayland.workspaces.window_mode(&window).is_some() {
+			return;
+		}
+
+		let Some(workspace) = self.mayland.workspaces.workspace() else {
+			return;
+		};
+
+		if !workspace.has_window(&window) {
+			return;
+		}
+
+		let is_floating = workspace.is_floating(&window);
+		let has_split = workspace.has_split();
+
+		match action {
+			MouseAction::Move if is_floating => self.floating_move_grab(window, start_data, serial),
+			MouseAction::Move => self.tiling_move_grab(window, start_data, serial),
+			MouseAction::Resize if is_floating => self.floating_resize_grab(window, start_data, serial),
+			MouseAction::Resize if has_split => self.split_grab(start_data, serial),
+			MouseAction::Resize => {}
+		}
+	}
+
+	fn on_gesture_swipe_begin<I: InputBackend>(&mut self, event: I::GestureSwipeBeginEvent) {
+		let fingers = event.fingers();
+
+		// swipes with bound fingers never reach the client, regardless of their direction
+		let mods = self.mayland.keyboard.modifier_state();
+		if !self.mayland.is_locked() && self.mayland.config.bind.has_swipe(&mods, fingers) {
+			self.mayland.swipe_gesture = Some(SwipeGesture {
+				fingers,
+				delta: Point::default(),
+			});
+			return;
+		}
+
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_swipe_begin(
+			self,
+			&pointer::GestureSwipeBeginEvent {
+				serial: SERIAL_COUNTER.next_serial(),
+				time: event.time_msec(),
+				fingers,
+			},
+		);
+	}
+
+	fn on_gesture_swipe_update<I: InputBackend>(&mut self, event: I::GestureSwipeUpdateEvent) {
+		if let Some(swipe) = &mut self.mayland.swipe_gesture {
+			swipe.delta += event.delta();
+			return;
+		}
+
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_swipe_update(
+			self,
+			&pointer::GestureSwipeUpdateEvent {
+				time: event.time_msec(),
+				delta: event.delta(),
+			},
+		);
+	}
+
+	fn on_gesture_swipe_end<I: InputBackend>(&mut self, event: I::GestureSwipeEndEvent) {
+		if let Some(swipe) = self.mayland.swipe_gesture.take() {
+			if !event.cancelled() {
+				self.on_swipe_bind(swipe);
+			}
+			return;
+		}
+
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_swipe_end(
+			self,
+			&pointer::GestureSwipeEndEvent {
+				serial: SERIAL_COUNTER.next_serial(),
+				time: event.time_msec(),
+				cancelled: event.cancelled(),
+			},
+		);
+	}
+
+	/// run the bind for the direction of the finished swipe gesture
+	fn on_swipe_bind(&mut self, swipe: SwipeGesture) {
+		let SwipeGesture { fingers, delta } = swipe;
+
+		let direction = match (delta.x, delta.y) {
+			(x, y) if x.abs().max(y.abs()) < SWIPE_THRESHOLD => return,
+			(x, y) if x.abs() >= y.abs() && x < 0. => Direction::Left,
+			(x, y) if x.abs() >= y.abs() => Direction::Right,
+			(_, y) if y < 0. => Direction::Up,
+			_ => Direction::Down,
+		};
+
+		let mods = self.mayland.keyboard.modifier_state();
+		let bind = &self.mayland.config.bind;
+		if let Some(action) = bind.find_swipe_action(&mods, fingers, direction) {
+			self.handle_action(action).expect("config should have validated");
+		}
+	}
+
+	fn on_gesture_pinch_begin<I: InputBackend>(&mut self, event: I::GesturePinchBeginEvent) {
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_pinch_begin(
+			self,
+			&pointer::GesturePinchBeginEvent {
+				serial: SERIAL_COUNTER.next_serial(),
+				time: event.time_msec(),
+				fingers: event.fingers(),
+			},
+		);
+	}
+
+	fn on_gesture_pinch_update<I: InputBackend>(&mut self, event: I::GesturePinchUpdateEvent) {
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_pinch_update(
+			self,
+			&pointer::GesturePinchUpdateEvent {
+				time: event.time_msec(),
+				delta: event.delta(),
+				scale: event.scale(),
+				rotation: event.rotation(),
+			},
+		);
+	}
+
+	fn on_gesture_pinch_end<I: InputBackend>(&mut self, event: I::GesturePinchEndEvent) {
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_pinch_end(
+			self,
+			&pointer::GesturePinchEndEvent {
+				serial: SERIAL_COUNTER.next_serial(),
+				time: event.time_msec(),
+				cancelled: event.cancelled(),
+			},
+		);
+	}
+
+	fn on_gesture_hold_begin<I: InputBackend>(&mut self, event: I::GestureHoldBeginEvent) {
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_hold_begin(
+			self,
+			&pointer::GestureHoldBeginEvent {
+				serial: SERIAL_COUNTER.next_serial(),
+				time: event.time_msec(),
+				fingers: event.fingers(),
+			},
+		);
+	}
+
+	fn on_gesture_hold_end<I: InputBackend>(&mut self, event: I::GestureHoldEndEvent) {
+		let pointer = self.mayland.pointer.clone();
+		pointer.gesture_hold_end(
+			self,
+			&pointer::GestureHoldEndEvent {
+				serial: SERIAL_COUNTER.next_serial(),
+				time: event.time_msec(),
+				cancelled: event.cancelled(),
+			},
+		);
+	}
+
+	fn on_touch_down<I: InputBackend>(&mut self, event: I::TouchDownEvent)
+	where
+		I::Device: 'static,
+	{
+		let Some(location) = self.compute_touch_location(&event) else {
+			return;
+		};
+
+		let serial = SERIAL_COUNTER.next_serial();
+		self.update_active_output(location, serial);
+		// a tap focuses the surface under it, just like a click
+		self.update_keyboard_focus(location, serial);
+
+		let under = self.touch_surface_under(location);
+
+		let touch = self.mayland.touch.clone();
+		touch.down(
+			self,
+			under,
+			&DownEvent {
+				slot: event.slot(),
+				location,
+				serial,
+				time: event.time_msec(),
+			},
+		);
+	}
+
+	fn on_touch_motion<I: InputBackend>(&mut self, event: I::TouchMotionEvent)
+	where
+		I::Device: 'static,
+	{
+		let Some(location) = self.compute_touch_location(&event) else {
+			return;
+		};
+
+		let under = self.touch_surface_under(location);
+		let touch = self.mayland.touch.clone();
+
+		// the dnd icon of a touch drag follows the touch point that started it
+		if let Some(icon) = &mut self.mayland.dnd_icon
+			&& icon.touch_location.is_some()
+			&& (touch.grab_start_data()).is_some_and(|start_data| start_data.slot == event.slot())
+		{
+			icon.touch_location = Some(location);
+		}
+
+		touch.motion(
+			self,
+			under,
+			&TouchMotionEvent {
+				slot: event.slot(),
+				location,
+				time: event.time_msec(),
+			},
+		);
+
+		self.mayland.queue_redraw_all();
+	}
+
+	fn on_touch_up<I: InputBackend>(&mut self, event: I::TouchUpEvent) {
+		let serial = SERIAL_COUNTER.next_serial();
+
+		let touch = self.mayland.touch.clone();
+		touch.up(
+			self,
+			&UpEvent {
+				slot: event.slot(),
+				serial,
+				time: event.time_msec(),
+			},
+		);
+	}
+
+	fn on_touch_cancel(&mut self) {
+		let touch = self.mayland.touch.clone();
+		touch.cancel(self);
+	}
+
+	fn on_touch_frame(&mut self) {
+		let touch = self.mayland.touch.clone();
+		touch.frame(self);
+	}
+
+	fn compute_touch_location<I, E>(&self, event: &E) -> Option<Point<f64, Logical>>
+	where
+		I: InputBackend,
+		I::Device: 'static,
+		E: AbsolutePositionEvent<I>,
+	{
+		let config = if let Some(device) = (&event.device() as &dyn Any).downcast_ref::<libinput::Device>() {
+			let name = device.name();
+			self.mayland.config.input.touch(&name)
+		} else {
+			&self.mayland.config.input.touch
+		};
+
+		let geometry = self.mapping_geometry(&config.map_to)?;
+		Some(event.position_transformed(geometry.size) + geometry.loc.to_f64())
+	}
+
+	/// touch points only focus surfaces, so the window under
+	/// the touch point gets resolved to its surface
+	fn touch_surface_under(&self, location: Point<f64, Logical>) -> Option<(WlSurface, Point<f64, Logical>)> {
+		let (target, location) = self.surface_under(location)?;
+		let surface = target.wl_surface()?.into_owned();
+		Some((surface, location))
+	}
+
 	fn on_tablet_tool_axis<I: InputBackend>(&mut self, event: I::TabletToolAxisEvent)
 	where
 		I::Device: 'static,
@@ -374,24 +769,30 @@ impl State {
 			let bbox = self.mayland.workspaces.bbox()?;
 			bbox.clamp(location)
 		} else {
-			let bbox = match &config.map_to {
-				TabletMapping::All => self.mayland.workspaces.bbox()?,
-				TabletMapping::Active => {
-					let active = self.mayland.workspaces.active_output()?;
-					self.mayland.workspaces.output_geometry(active).unwrap()
-				}
-				TabletMapping::Output(output) => {
-					let output = self.mayland.workspaces.output_by_name(output)?;
-					self.mayland.workspaces.output_geometry(output).unwrap()
-				}
-			};
-
+			let bbox = self.mapping_geometry(&config.map_to)?;
 			event.position_transformed(bbox.size) + bbox.loc.to_f64()
 		};
 
 		Some(location)
 	}
 
+	/// the area that an absolute input device with the [`TabletMapping`] maps to
+	fn mapping_geometry(&self, mapping: &TabletMapping) -> Option<Rectangle<i32, Logical>> {
+		let geometry = match mapping {
+			TabletMapping::All => self.mayland.workspaces.bbox()?,
+			TabletMapping::Active => {
+				let active = self.mayland.workspaces.active_output()?;
+				self.mayland.workspaces.output_geometry(active).unwrap()
+			}
+			TabletMapping::Output(output) => {
+				let output = self.mayland.workspaces.output_by_name(output)?;
+				self.mayland.workspaces.output_geometry(output).unwrap()
+			}
+		};
+
+		Some(geometry)
+	}
+
 	fn handle_key(
 		&mut self,
 		code: Keycode,
@@ -420,6 +821,11 @@ impl State {
 			}
 		}
 
+		// every other key goes to the lock client
+		if self.mayland.is_locked() {
+			return FilterResult::Forward;
+		}
+
 		let action = self.mayland.config.bind.find_action(mods, raw_sym);
 
 		if let Some(action) = action {
@@ -436,6 +842,12 @@ impl State {
 				self.mayland.loop_signal.stop();
 				self.mayland.loop_signal.wakeup();
 			}
+
+			Action::PowerOff => self.set_outputs_power(false),
+			Action::PowerOn => self.set_outputs_power(true),
+
+			Action::Screenshot(target) => self.screenshot(target),
+
 			Action::CloseWindow => {
 				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
 					return Ok(());
@@ -452,6 +864,28 @@ impl State {
 				self.mayland.workspaces.toggle_floating(window, pointer);
 				self.mayland.queue_redraw_all();
 			}
+			Action::ToggleFullscreen => {
+				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
+					return Ok(());
+				};
+
+				self.mayland
+					.workspaces
+					.toggle_window_mode(&window, WindowMode::Fullscreen);
+				self.refresh_pointer_focus();
+				self.mayland.queue_redraw_all();
+			}
+			Action::ToggleMaximize => {
+				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
+					return Ok(());
+				};
+
+				self.mayland
+					.workspaces
+					.toggle_window_mode(&window, WindowMode::Maximized);
+				self.refresh_pointer_focus();
+				self.mayland.queue_redraw_all();
+			}
 			Action::Cycle(direction) => {
 				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
 					return Ok(());
@@ -479,9 +913,39 @@ impl State {
 					self.mayland.queue_redraw_all();
 				}
 			}
+			Action::CycleWorkspace(direction) => {
+				let Some(workspace) = self.mayland.workspaces.workspace() else {
+					return Ok(());
+				};
+
+				let idx = match direction {
+					CycleDirection::Next => workspace.idx + 1,
+					CycleDirection::Prev => {
+						let Some(idx) = workspace.idx.checked_sub(1) else {
+							return Ok(());
+						};
+						idx
+					}
+				};
+
+				self.handle_action(Action::Workspace(idx))?;
+			}
+			Action::GrowSplit => {
+				self.mayland.workspaces.resize_split(SPLIT_STEP);
+				self.mayland.queue_redraw_all();
+			}
+			Action::ShrinkSplit => {
+				self.mayland.workspaces.resize_split(-SPLIT_STEP);
+				self.mayland.queue_redraw_all();
+			}
 			Action::Workspace(idx) => {
+				let prev = self.mayland.workspaces.workspace().map(|workspace| workspace.idx);
 				let location = self.mayland.workspaces.switch_to_workspace(idx);
 
+				if prev.is_some_and(|prev| prev != idx) {
+					self.mayland.may_socket.send_event(Event::WorkspaceSwitched(idx));
+				}
+
 				if let Some(location) = location {
 					self.move_pointer(location.to_f64());
 					self.mayland.queue_redraw_all();
@@ -489,6 +953,37 @@ impl State {
 
 				self.reset_focus();
 			}
+			Action::MoveToWorkspace(idx) => {
+				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
+					return Ok(());
+				};
+
+				let pointer = self.mayland.pointer.current_location();
+				if self.mayland.workspaces.move_to_workspace(&window, idx, pointer) {
+					self.reset_focus();
+
+					// the pointer might not be above any other window
+					let keyboard = self.mayland.keyboard.clone();
+					if keyboard.current_focus() == Some(KeyboardFocusTarget::Window(window)) {
+						keyboard.set_focus(self, None, SERIAL_COUNTER.next_serial());
+					}
+
+					self.mayland.queue_redraw_all();
+				}
+			}
+			Action::MoveToWorkspaceAndFollow(idx) => {
+				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
+					return Ok(());
+				};
+
+				let pointer = self.mayland.pointer.current_location();
+				if self.mayland.workspaces.move_to_workspace(&window, idx, pointer) {
+					self.handle_action(Action::Workspace(idx))?;
+					self.focus_window(window);
+
+					self.mayland.queue_redraw_all();
+				}
+			}
 			Action::Spawn(command) => {
 				return spawn(command, &self.mayland);
 			}
@@ -497,10 +992,20 @@ impl State {
 		Ok(())
 	}
 
-	fn update_active_output(&mut self, location: Point<f64, Logical>, serial: Serial) {
+	pub fn update_active_output(&mut self, location: Point<f64, Logical>, serial: Serial) {
+		let prev = self.mayland.workspaces.workspace().map(|workspace| workspace.idx);
+
 		if self.mayland.workspaces.update_active_output(location) {
 			let workspace = self.mayland.workspaces.workspace();
-			if workspace.is_none_or(|ws| ws.is_empty()) {
+
+			// the active workspace changes with the active output
+			if let Some(idx) = workspace.map(|workspace| workspace.idx)
+				&& prev != Some(idx)
+			{
+				self.mayland.may_socket.send_event(Event::WorkspaceSwitched(idx));
+			}
+
+			if workspace.is_none_or(|ws| ws.is_empty()) && !self.mayland.is_locked() {
 				let keyboard = self.mayland.keyboard.clone();
 				keyboard.set_focus(self, None, serial);
 			} else {
@@ -519,16 +1024,24 @@ impl State {
 			return;
 		}
 
+		if self.mayland.is_locked() {
+			self.focus_lock_surface();
+			return;
+		}
+
 		let Some(output) = self.mayland.workspaces.output_under(location) else {
 			return;
 		};
 
-		let target = if let Some((layer, _, _)) = self.layer_surface_under(
-			output,
-			location,
-			&[WlrLayer::Overlay, WlrLayer::Top],
-			SurfaceFocus::Keyboard,
-		) {
+		let target = if let Some((layer, _, _)) =
+			self.layer_surface_under(output, location, &[WlrLayer::Overlay], SurfaceFocus::Keyboard)
+		{
+			KeyboardFocusTarget::LayerSurface(layer)
+		} else if let Some((window, _)) = self.mayland.workspaces.fullscreen_under(location) {
+			KeyboardFocusTarget::Window(window.clone())
+		} else if let Some((layer, _, _)) =
+			self.layer_surface_under(output, location, &[WlrLayer::Top], SurfaceFocus::Keyboard)
+		{
 			KeyboardFocusTarget::LayerSurface(layer)
 		} else if let Some((window, _)) = self
 			.mayland
@@ -558,6 +1071,11 @@ impl State {
 	}
 
 	fn set_focus(&mut self, target: KeyboardFocusTarget, keyboard: KeyboardHandle<State>, serial: Serial) {
+		// the focus can't leave the lock surface
+		if self.mayland.is_locked() {
+			return;
+		}
+
 		if let KeyboardFocusTarget::Window(window) = &target {
 			self.mayland.workspaces.activate_window(window);
 		}
@@ -575,18 +1093,49 @@ impl State {
 		self.refresh_pointer_focus();
 	}
 
+	/// switch to the workspace of the [`MappedWindow`] and focus it
+	pub fn activate_window(&mut self, window: MappedWindow) {
+		let workspace =
+			(self.mayland.workspaces.workspaces()).find(|workspace| workspace.has_window(&window));
+		let Some(idx) = workspace.map(|workspace| workspace.idx) else {
+			return;
+		};
+
+		if let Err(err) = self.handle_action(Action::Workspace(idx)) {
+			tracing::warn!("failed to switch to the workspace of the window: {}", err);
+			return;
+		}
+		self.focus_window(window);
+
+		self.mayland.queue_redraw_all();
+	}
+
 	pub fn surface_under(
 		&self,
 		location: Point<f64, Logical>,
 	) -> Option<(PointerFocusTarget, Point<f64, Logical>)> {
 		let output = self.mayland.workspaces.output_under(location)?;
 
-		if let Some((_, surface, location)) = self.layer_surface_under(
-			output,
-			location,
-			&[WlrLayer::Overlay, WlrLayer::Top],
-			SurfaceFocus::Pointer,
-		) {
+		if self.mayland.is_locked() {
+			let surface = self.mayland.lock_surface(output)?;
+			let output_geometry = self.mayland.workspaces.output_geometry(output)?;
+			return Some((
+				PointerFocusTarget::WlSurface(surface.wl_surface().clone()),
+				output_geometry.loc.to_f64(),
+			));
+		}
+
+		if let Some((surface, location)) = self.mayland.override_redirect_under(location) {
+			Some((PointerFocusTarget::WlSurface(surface), location))
+		} else if let Some((_, surface, location)) =
+			self.layer_surface_under(output, location, &[WlrLayer::Overlay], SurfaceFocus::Pointer)
+		{
+			Some((PointerFocusTarget::WlSurface(surface), location.to_f64()))
+		} else if let Some((window, location)) = self.mayland.workspaces.fullscreen_under(location) {
+			Some((PointerFocusTarget::Window(window.clone()), location.to_f64()))
+		} else if let Some((_, surface, location)) =
+			self.layer_surface_under(output, location, &[WlrLayer::Top], SurfaceFocus::Pointer)
+		{
 			Some((PointerFocusTarget::WlSurface(surface), location.to_f64()))
 		} else if let Some((window, location)) = self.mayland.workspaces.window_under(location) {
 			Some((PointerFocusTarget::Window(window.clone()), location.to_f64()))
@@ -116,7 +116,7 @@ impl Floating {
 		focus: Option<&'b MappedWindow>,
 	) -> impl Iterator<Item = MaylandRenderElements> + use<'_, 'a, 'b> {
 		self.windows_geometry().rev().flat_map(move |(window, geom)| {
-			let render_location = window.render_location(geom.loc).to_physical_precise_round(1);
+			let render_location = window.render_location(geom.loc).to_physical_precise_round(scale);
 			let mut elements = window.render_elements(renderer, render_location, scale.into(), 1.0);
 
 			let color = if focus == Some(window) {
@@ -125,7 +125,7 @@ impl Floating {
 				decoration.focus.inactive
 			};
 
-			let focus_ring = FocusRing::element(renderer, geom, color, decoration.focus.thickness);
+			let focus_ring = FocusRing::element(renderer, geom, color, decoration.focus.thickness, scale);
 			elements.push(MaylandRenderElements::FocusElement(focus_ring));
 
 			elements
@@ -101,6 +101,10 @@ impl OutputSpace {
 		}
 	}
 
+	/// lay out the outputs in the output space
+	///
+	/// the outputs are positioned with their logical size, which already
+	/// accounts for the output scale and transform
 	fn reposition(&mut self, config: &mayland_config::Outputs) {
 		let outputs = self.outputs.drain(..).map(|(output, _)| {
 			let output_info = output.user_data().get::<OutputInfo>().unwrap();
@@ -129,24 +133,26 @@ impl OutputSpace {
 		});
 
 		for (output, position) in outputs {
-			if let Some(position) = position {
-				let point = Point::new(position[0], position[1]);
+			// outputs that would overlap are placed like outputs without a position
+			let position = position.map(|[x, y]| Point::new(x, y)).filter(|point| {
 				let size = output_size(&output);
-				let rect = Rectangle { loc: point, size };
-
-				if let Some(overlaps) = self.overlaps(rect) {
-					panic!(
-						"new output {:?} at position {:?} overlaps with output {:?} at {:?}",
-						output.name(),
-						rect,
-						overlaps.0.name(),
-						overlaps
-					);
-				}
-
-				output.change_current_state(None, None, None, Some(point));
-				self.outputs.push((output, point));
-			} else {
+				let rect = Rectangle { loc: *point, size };
+
+				let Some((overlaps, location)) = self.overlaps(rect) else {
+					return true;
+				};
+
+				tracing::warn!(
+					"output {:?} at position {:?} overlaps with output {:?} at {:?}",
+					output.name(),
+					rect,
+					overlaps.name(),
+					location
+				);
+				false
+			});
+
+			let point = position.unwrap_or_else(|| {
 				let x = self
 					.outputs
 					.iter()
@@ -157,10 +163,11 @@ impl OutputSpace {
 					.max()
 					.unwrap_or(0);
 
-				let point = Point::new(x, 0);
-				output.change_current_state(None, None, None, Some(point));
-				self.outputs.push((output, point));
-			}
+				Point::new(x, 0)
+			});
+
+			output.change_current_state(None, None, None, Some(point));
+			self.outputs.push((output, point));
 		}
 	}
 
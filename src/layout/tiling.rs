@@ -20,6 +20,11 @@ impl WindowLayout {
 	}
 }
 
+/// the minimum split ratio
+const MIN_RATIO: f64 = 0.1;
+/// the maximum split ratio
+const MAX_RATIO: f64 = 0.9;
+
 #[derive(Debug)]
 struct Layout {
 	/// the output working area, excluding layer-shell
@@ -37,6 +42,8 @@ struct Layout {
 
 	/// split point between 0 and 1
 	ratio: f64,
+	/// the configured split ratio
+	default_ratio: f64,
 	split: Point<i32, Logical>,
 }
 
@@ -56,7 +63,8 @@ impl Layout {
 			gaps: i32::from(tiling.gaps),
 			ring: i32::from(decoration.focus.thickness),
 
-			ratio: 0.5,
+			ratio: tiling.ratio.clamp(MIN_RATIO, MAX_RATIO),
+			default_ratio: tiling.ratio,
 			split: Point::new(0, 0),
 		}
 	}
@@ -67,6 +75,13 @@ impl Layout {
 		self.gaps = i32::from(tiling.gaps);
 		self.ring = i32::from(decoration.focus.thickness);
 
+		// only reset the split, if the configured ratio changed,
+		// so that reloading the config keeps manual adjustments
+		if self.default_ratio != tiling.ratio {
+			self.default_ratio = tiling.ratio;
+			self.ratio = tiling.ratio.clamp(MIN_RATIO, MAX_RATIO);
+		}
+
 		self.useable_area = self.working_area.borderless(self.border);
 		self.resplit();
 	}
@@ -77,6 +92,11 @@ impl Layout {
 		self.resplit();
 	}
 
+	fn set_ratio(&mut self, ratio: f64) {
+		self.ratio = ratio.clamp(MIN_RATIO, MAX_RATIO);
+		self.resplit();
+	}
+
 	fn resplit(&mut self) {
 		let x = self.useable_area.size.w as f64 * self.ratio;
 		let x = x.round() as i32;
@@ -90,28 +110,74 @@ impl Layout {
 		self.useable_area.borderless(self.ring)
 	}
 
-	fn double(&self) -> [Rectangle<i32, Logical>; 2] {
+	/// split the useable area at the split point into
+	/// the master and the stack column
+	fn columns(&self) -> [Rectangle<i32, Logical>; 2] {
 		let split = self.split;
 		let rel_split = split - self.useable_area.loc;
 		let size = self.useable_area.size;
 
 		let gap = self.gaps / 2;
 
-		let one = {
+		let master = {
 			let size = Size::new(i32::max(rel_split.x - gap, 0), size.h);
 			let loc = self.useable_area.loc;
 
 			Rectangle { loc, size }
 		};
 
-		let two = {
-			let size = Size::new(i32::max(size.w - one.size.w - self.gaps, 0), size.h);
+		let stack = {
+			let size = Size::new(i32::max(size.w - master.size.w - self.gaps, 0), size.h);
 			let loc = Point::new(split.x + gap, split.y);
 
 			Rectangle { loc, size }
 		};
 
-		[one.borderless(self.ring), two.borderless(self.ring)]
+		[master, stack]
+	}
+
+	/// split the stack column into `count` windows stacked on
+	/// top of each other
+	fn stack(
+		&self,
+		area: Rectangle<i32, Logical>,
+		count: usize,
+	) -> impl Iterator<Item = Rectangle<i32, Logical>> {
+		let count = count as i32;
+		let gaps = self.gaps;
+
+		let height = i32::max(area.size.h - gaps * (count - 1), 0) / count;
+
+		(0..count).map(move |i| {
+			let y = area.loc.y + i * (height + gaps);
+			// the last window gets the space lost to rounding
+			let height = if i == count - 1 {
+				i32::max(area.loc.y + area.size.h - y, 0)
+			} else {
+				height
+			};
+
+			Rectangle {
+				loc: Point::new(area.loc.x, y),
+				size: Size::new(area.size.w, height),
+			}
+		})
+	}
+
+	/// get the window rectangles for `n` tiled windows
+	///
+	/// the first window is the master window, that is mapped left of the
+	/// split, all other windows are stacked right of the split
+	fn tiles(&self, n: usize) -> Vec<Rectangle<i32, Logical>> {
+		if n <= 1 {
+			return vec![self.single(); n];
+		}
+
+		let [master, stack] = self.columns();
+		std::iter::once(master)
+			.chain(self.stack(stack, n - 1))
+			.map(|tile| tile.borderless(self.ring))
+			.collect()
 	}
 }
 
@@ -129,12 +195,33 @@ impl Layout {
 			Position::Right
 		}
 	}
+
+	/// is the location on the gap between the master and the stack column?
+	fn is_split(&self, location: Point<f64, Logical>) -> bool {
+		let split = self.split.to_f64();
+		let reach = f64::from(self.gaps / 2 + self.ring);
+
+		let y = f64::from(self.useable_area.loc.y)
+			..=f64::from(self.useable_area.loc.y + self.useable_area.size.h);
+		(location.x - split.x).abs() <= reach && y.contains(&location.y)
+	}
+
+	/// get the index of the window in the stack under the location
+	fn stack_index(&self, location: Point<f64, Logical>, count: usize) -> usize {
+		let [_, stack] = self.columns();
+
+		let height = f64::from(stack.size.h + self.gaps) / count as f64;
+		let relative = location.y - f64::from(stack.loc.y) + f64::from(self.gaps) / 2.;
+
+		let idx = (relative / height).floor().max(0.) as usize;
+		usize::min(idx, count - 1)
+	}
 }
 
 #[derive(Debug)]
 pub struct Tiling {
 	layout: Layout,
-	windows: [Option<WindowLayout>; 2],
+	windows: Vec<WindowLayout>,
 }
 
 impl Tiling {
@@ -143,7 +230,7 @@ impl Tiling {
 
 		Tiling {
 			layout,
-			windows: [None, None],
+			windows: Vec::new(),
 		}
 	}
 
@@ -158,72 +245,42 @@ impl Tiling {
 }
 
 impl Tiling {
-	pub fn add_window(&mut self, window: MappedWindow, pointer: Point<f64, Logical>) -> Option<MappedWindow> {
-		match &mut self.windows {
-			[Some(_), Some(_)] => Some(window),
-			[None, Some(_)] => unreachable!(),
-			[Some(prev), empty @ None] => {
-				let [one, two] = self.layout.double();
-				let position = self.layout.position(pointer);
-
-				window.set_activate(true);
-
-				match position {
-					Position::Left => {
-						prev.resize(two);
-
-						window.resize(one);
-						*empty = Some(WindowLayout(window, one));
-
-						self.windows.swap(0, 1);
-					}
-					Position::Right => {
-						prev.resize(one);
-
-						window.resize(two);
-						*empty = Some(WindowLayout(window, two));
-					}
-				}
-
-				None
+	pub fn add_window(&mut self, window: MappedWindow, pointer: Point<f64, Logical>) {
+		// mapping a window left of the split makes it the new master window,
+		// otherwise it gets put in the stack below the window under the pointer
+		let idx = match self.layout.position(pointer) {
+			_ if self.windows.is_empty() => 0,
+			Position::Left => 0,
+			Position::Right if self.windows.len() == 1 => 1,
+			Position::Right => {
+				let count = self.windows.len() - 1;
+				self.layout.stack_index(pointer, count) + 2
 			}
-			[empty @ None, None] => {
-				let one = self.layout.single();
+		};
 
-				window.set_activate(true);
-				window.resize(one);
-				*empty = Some(WindowLayout(window, one));
+		window.set_activate(true);
+		self.windows.insert(idx, WindowLayout(window, Rectangle::zero()));
 
-				None
-			}
-		}
+		self.resize_windows();
 	}
 
-	pub fn remove_window(&mut self, window: &MappedWindow) -> bool {
-		match &mut self.windows {
-			[Some(w1), Some(w2)] if &w1.0 == window => {
-				let one = self.layout.single();
-				w2.resize(one);
+	/// insert the window at the index, to put a window back to where it was
+	pub fn insert_window(&mut self, idx: usize, window: MappedWindow) {
+		let idx = idx.min(self.windows.len());
+		self.windows.insert(idx, WindowLayout(window, Rectangle::zero()));
 
-				self.windows.swap(0, 1);
-				self.windows[1] = None;
+		self.resize_windows();
+	}
 
-				true
-			}
-			[Some(w1), Some(w2)] if &w2.0 == window => {
-				let one = self.layout.single();
-				w1.resize(one);
+	pub fn remove_window(&mut self, window: &MappedWindow) -> bool {
+		let Some(idx) = self.windows.iter().position(|w| &w.0 == window) else {
+			return false;
+		};
 
-				self.windows[1] = None;
-				true
-			}
-			[None, Some(_)] => unreachable!(),
-			[Some(prev), None] if &prev.0 == window => {
-				self.windows[0] = None;
-				true
-			}
-			_ => false,
-		}
+		self.windows.remove(idx);
+		self.resize_windows();
+
+		true
 	}
 
 	pub fn map_output(&mut self, output: &Output) {
@@ -241,68 +298,100 @@ impl Tiling {
 		self.resize_windows();
 	}
 
-	fn resize_windows(&mut self) {
-		match &mut self.windows {
-			[Some(w1), Some(w2)] => {
-				let [one, two] = self.layout.double();
+	/// grow the split by `delta`, shrinking it if `delta` is negative
+	pub fn resize_split(&mut self, delta: f64) {
+		self.layout.set_ratio(self.layout.ratio + delta);
+		self.resize_windows();
+	}
 
-				w1.resize(one);
-				w2.resize(two);
-			}
-			[Some(window), None] => {
-				let one = self.layout.single();
-				window.resize(one);
-			}
-			[None, Some(_)] => unreachable!(),
-			[None, None] => (),
+	/// move the split to the x coordinate of the location
+	pub fn move_split(&mut self, location: Point<f64, Logical>) {
+		let area = self.layout.useable_area;
+		if area.size.w == 0 {
+			return;
 		}
+
+		let ratio = (location.x - f64::from(area.loc.x)) / f64::from(area.size.w);
+		self.layout.set_ratio(ratio);
+		self.resize_windows();
 	}
-}
 
-impl Tiling {
-	pub fn windows(&self) -> impl DoubleEndedIterator<Item = &MappedWindow> + Clone {
-		self.windows.iter().flatten().map(|w| &w.0)
+	/// swap the window with the window under the location
+	///
+	/// returns `false` if the layout didn't change
+	pub fn swap_under(&mut self, window: &MappedWindow, location: Point<f64, Logical>) -> bool {
+		let Some(idx) = self.window_index(window) else {
+			return false;
+		};
+		let Some(under) = self.index_under(location) else {
+			return false;
+		};
+
+		if idx == under {
+			return false;
+		}
+
+		self.windows.swap(idx, under);
+		self.resize_windows();
+
+		true
 	}
 
-	pub fn is_full(&self) -> bool {
-		match self.windows {
-			[None, None] | [Some(_), None] => false,
-			[None, Some(_)] => unreachable!(),
-			[Some(_), Some(_)] => true,
+	fn resize_windows(&mut self) {
+		let tiles = self.layout.tiles(self.windows.len());
+		for (window, tile) in self.windows.iter_mut().zip(tiles) {
+			window.resize(tile);
 		}
 	}
+}
+
+impl Tiling {
+	pub fn windows(&self) -> impl DoubleEndedIterator<Item = &MappedWindow> + Clone {
+		self.windows.iter().map(|w| &w.0)
+	}
 
 	pub fn windows_geometry(
 		&self,
 	) -> impl DoubleEndedIterator<Item = (&MappedWindow, Rectangle<i32, Logical>)> {
-		self.windows.iter().flatten().map(|WindowLayout(w, g)| (w, *g))
+		self.windows.iter().map(|WindowLayout(w, g)| (w, *g))
+	}
+
+	pub fn window_index(&self, window: &MappedWindow) -> Option<usize> {
+		self.windows.iter().position(|w| &w.0 == window)
+	}
+
+	/// are there both a master and a stack column?
+	pub fn has_split(&self) -> bool {
+		self.windows.len() >= 2
+	}
+
+	/// is the location on the split between the master and the stack?
+	pub fn split_under(&self, location: Point<f64, Logical>) -> bool {
+		self.has_split() && self.layout.is_split(location)
+	}
+
+	/// get the index of the window under the location
+	fn index_under(&self, location: Point<f64, Logical>) -> Option<usize> {
+		match self.layout.position(location) {
+			_ if self.windows.is_empty() => None,
+			_ if self.windows.len() == 1 => Some(0),
+			Position::Left => Some(0),
+			Position::Right => {
+				let count = self.windows.len() - 1;
+				let idx = self.layout.stack_index(location, count);
+				Some(idx + 1)
+			}
+		}
 	}
 
 	pub fn window_under(
 		&self,
 		location: Point<f64, Logical>,
 	) -> Option<(&MappedWindow, Point<i32, Logical>)> {
-		match &self.windows {
-			[Some(w1), Some(w2)] => {
-				let position = self.layout.position(location);
-				match position {
-					Position::Left => {
-						let loc = w1.0.render_location(w1.1.loc);
-						Some((&w1.0, loc))
-					}
-					Position::Right => {
-						let loc = w2.0.render_location(w2.1.loc);
-						Some((&w2.0, loc))
-					}
-				}
-			}
-			[Some(window), None] => {
-				let loc = window.0.render_location(window.1.loc);
-				Some((&window.0, loc))
-			}
-			[None, Some(_)] => unreachable!(),
-			[None, None] => None,
-		}
+		let window = &self.windows[self.index_under(location)?];
+
+		let loc = window.0.render_location(window.1.loc);
+		Some((&window.0, loc))
 	}
 }
 
@@ -315,7 +404,7 @@ impl Tiling {
 		focus: Option<&'b MappedWindow>,
 	) -> impl Iterator<Item = MaylandRenderElements> + use<'_, 'a, 'b> {
 		self.windows_geometry().flat_map(move |(window, geom)| {
-			let render_rect = window.render_rectangle(geom).to_physical_precise_round(1);
+			let render_rect = window.render_rectangle(geom).to_physical_precise_round(scale);
 			let mut elements = window.crop_render_elements(renderer, render_rect, scale.into(), 1.);
 
 			let color = if focus == Some(window) {
@@ -324,7 +413,7 @@ impl Tiling {
 				decoration.focus.inactive
 			};
 
-			let focus_ring = FocusRing::element(renderer, geom, color, decoration.focus.thickness);
+			let focus_ring = FocusRing::element(renderer, geom, color, decoration.focus.thickness, scale);
 			elements.push(MaylandRenderElements::FocusElement(focus_ring));
 
 			elements
@@ -118,6 +118,66 @@ impl WorkspaceManager {
 		}
 	}
 
+	/// move the [`MappedWindow`] to the workspace with the index `idx`,
+	/// creating the workspace if it doesn't exist yet
+	///
+	/// floating windows stay floating and keep their location.
+	/// returns `false` if the window was already on that workspace
+	#[instrument(skip_all)]
+	pub fn move_to_workspace(
+		&mut self,
+		window: &MappedWindow,
+		idx: usize,
+		pointer: Point<f64, Logical>,
+	) -> bool {
+		let Some(active_output) = self.outputs.active.clone() else {
+			return false;
+		};
+
+		let Some(current) = (self.workspaces.values()).find(|ws| ws.has_window(window)) else {
+			tracing::warn!("window is not on any workspace?");
+			return false;
+		};
+
+		let current = current.idx;
+		if current == idx {
+			return false;
+		}
+
+		let workspace = self.workspaces.get_mut(&current).unwrap();
+		workspace.restore(window);
+
+		let floating = workspace.floating.window_location(window);
+		workspace.remove_window(window);
+
+		// an empty workspace that isn't visible anywhere would never get cleaned up
+		if workspace.is_empty() && !self.output_map.values().any(|&ws| ws == current) {
+			self.workspaces.remove(&current);
+		}
+
+		let target = self.workspaces.entry(idx).or_insert_with(|| {
+			let mut workspace = Workspace::new(idx, &self.layout, &self.decoration);
+			// lay the new workspace out for the active output, so that the window
+			// doesn't have to be resized again once the workspace is shown there
+			workspace.tiling.map_output(&active_output);
+			workspace
+		});
+
+		let output = target.output.as_ref().unwrap_or(&active_output);
+		let output_position = self.outputs.output_position(output).unwrap_or_default();
+		let pointer = pointer - output_position.to_f64();
+
+		match floating {
+			Some(location) => target.floating.map_window(window.clone(), location),
+			None => target.tiling.add_window(window.clone(), pointer),
+		}
+
+		// the window gets activated again if it gets focussed
+		window.set_activate(false);
+
+		true
+	}
+
 	pub fn workspaces(&self) -> impl DoubleEndedIterator<Item = &Workspace> + ExactSizeIterator {
 		self.workspaces.values()
 	}
@@ -336,6 +396,12 @@ impl WorkspaceManager {
 		self.workspaces.values().flat_map(|workspace| workspace.windows())
 	}
 
+	/// is the [`MappedWindow`] in the tiling space of any workspace?
+	pub fn is_tiled(&self, window: &MappedWindow) -> bool {
+		(self.workspaces.values())
+			.any(|workspace| workspace.has_window(window) && !workspace.is_floating(window))
+	}
+
 	pub fn windows_for_output(&self, output: &Output) -> impl DoubleEndedIterator<Item = &MappedWindow> {
 		let workspace = self.output_map[output];
 		let workspace = &self.workspaces[&workspace];
@@ -361,6 +427,108 @@ impl WorkspaceManager {
 	}
 }
 
+impl WorkspaceManager {
+	/// grow the tiling split of the active workspace by `delta`
+	pub fn resize_split(&mut self, delta: f64) {
+		if let Some(workspace) = self.workspace_mut() {
+			workspace.tiling.resize_split(delta);
+		}
+	}
+
+	/// move the tiling split of the workspace on the [`Output`] to the location
+	pub fn move_split(&mut self, output: &Output, location: Point<f64, Logical>) {
+		let Some(output_position) = self.outputs.output_position(output) else {
+			return;
+		};
+
+		let location = location - output_position.to_f64();
+
+		let workspace = self.output_map[output];
+		let workspace = self.workspaces.get_mut(&workspace).unwrap();
+		workspace.tiling.move_split(location);
+	}
+
+	/// swap the tiled [`MappedWindow`] with the tiled window under the location
+	///
+	/// returns `false` if the layout didn't change
+	pub fn swap_tiled(&mut self, window: &MappedWindow, location: Point<f64, Logical>) -> bool {
+		let Some(output) = self.output_under(location).cloned() else {
+			return false;
+		};
+
+		let output_position = self.outputs.output_position(&output).unwrap();
+		let location = location - output_position.to_f64();
+
+		let workspace = self.output_map[&output];
+		let workspace = self.workspaces.get_mut(&workspace).unwrap();
+
+		// the tiled windows are covered by fullscreen and maximized windows
+		workspace.raised.is_none() && workspace.tiling.swap_under(window, location)
+	}
+
+	/// is the location on the tiling split of a workspace?
+	pub fn split_under(&self, location: Point<f64, Logical>) -> bool {
+		if let Some(output) = self.output_under(location) {
+			let output_position = self.outputs.output_position(output).unwrap();
+			let location = location - output_position.to_f64();
+
+			let workspace = &self.output_map[output];
+			let workspace = &self.workspaces[workspace];
+
+			// the split is covered by fullscreen, maximized and floating windows
+			workspace.raised.is_none()
+				&& workspace.floating.window_under(location).is_none()
+				&& workspace.tiling.split_under(location)
+		} else {
+			false
+		}
+	}
+}
+
+impl WorkspaceManager {
+	/// get the [`WindowMode`] of the [`MappedWindow`], if it is fullscreen or maximized
+	pub fn window_mode(&self, window: &MappedWindow) -> Option<WindowMode> {
+		(self.workspaces.values()).find_map(|workspace| workspace.window_mode(window))
+	}
+
+	/// make the [`MappedWindow`] fullscreen or maximized
+	pub fn set_window_mode(&mut self, window: &MappedWindow, mode: WindowMode) {
+		if let Some(workspace) = self.workspaces.values_mut().find(|ws| ws.has_window(window)) {
+			workspace.set_mode(window, mode);
+		}
+	}
+
+	/// put the fullscreen or maximized [`MappedWindow`] back into the layout
+	pub fn restore_window(&mut self, window: &MappedWindow) {
+		if let Some(workspace) = self.workspaces.values_mut().find(|ws| ws.has_window(window)) {
+			workspace.restore(window);
+		}
+	}
+
+	pub fn toggle_window_mode(&mut self, window: &MappedWindow, mode: WindowMode) {
+		if self.window_mode(window) == Some(mode) {
+			self.restore_window(window);
+		} else {
+			self.set_window_mode(window, mode);
+		}
+	}
+
+	/// get the fullscreen window on the output under the location
+	pub fn fullscreen_under(
+		&self,
+		location: Point<f64, Logical>,
+	) -> Option<(&MappedWindow, Point<i32, Logical>)> {
+		let output = self.output_under(location)?;
+		let output_position = self.outputs.output_position(output).unwrap();
+
+		let workspace = &self.output_map[output];
+		let workspace = &self.workspaces[workspace];
+
+		let (window, geometry) = workspace.fullscreen_window()?;
+		Some((window, window.render_location(geometry.loc) + output_position))
+	}
+}
+
 #[derive(Debug)]
 pub struct NextWindow {
 	pub window: MappedWindow,
@@ -425,6 +593,33 @@ impl WorkspaceManager {
 	}
 }
 
+/// a window state, where the window is taken out of the layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMode {
+	/// the window covers the entire output, including layer-shell surfaces
+	/// on the top layer
+	Fullscreen,
+	/// the window fills the working area of the output
+	Maximized,
+}
+
+/// a fullscreen or maximized window
+#[derive(Debug)]
+struct Raised {
+	window: MappedWindow,
+	mode: WindowMode,
+	/// where to put the window back, once it's restored
+	restore: Restore,
+}
+
+#[derive(Debug)]
+enum Restore {
+	/// the index in the tiling layout
+	Tiling(usize),
+	/// the floating geometry
+	Floating(Rectangle<i32, Logical>),
+}
+
 #[derive(Debug)]
 pub struct Workspace {
 	pub idx: usize,
@@ -432,6 +627,7 @@ pub struct Workspace {
 
 	tiling: Tiling,
 	floating: Floating,
+	raised: Option<Raised>,
 }
 
 impl Workspace {
@@ -445,6 +641,7 @@ impl Workspace {
 
 			tiling,
 			floating,
+			raised: None,
 		}
 	}
 
@@ -457,6 +654,7 @@ impl Workspace {
 	fn map_output(&mut self, output: &Output) {
 		self.output = Some(output.clone());
 		self.tiling.map_output(output);
+		self.resize_raised();
 	}
 
 	fn remove_output(&mut self, output: &Output) {
@@ -466,12 +664,21 @@ impl Workspace {
 
 	fn output_area_changed(&mut self, output: &Output) {
 		self.tiling.output_area_changed(output);
+		self.resize_raised();
 	}
 
 	fn refresh(&self) {
 		for window in self.windows() {
 			window.refresh();
 		}
+
+		// x11 windows need to know their absolute location
+		if let Some(output) = &self.output {
+			let output_location = output.current_location();
+			for (window, geometry) in self.windows_geometry() {
+				window.set_x11_location(geometry.loc + output_location);
+			}
+		}
 	}
 
 	/// get the [`Point`], that the window would have to be mapped to
@@ -496,20 +703,37 @@ impl Workspace {
 
 impl Workspace {
 	pub fn add_window(&mut self, window: MappedWindow, pointer: Point<f64, Logical>) {
-		let center = self.relative_center(window.geometry().size);
-		if window.is_non_resizable() || window.windowrules.floating().unwrap_or(false) {
-			self.floating.map_window(window, center);
-		} else if let Some(window) = self.tiling.add_window(window, pointer) {
+		let floating = window.is_non_resizable() || window.windowrules.floating().unwrap_or(false);
+
+		// don't hide new windows below a fullscreen window,
+		// or below a maximized window if they are tiled
+		if let Some(raised) = &self.raised
+			&& (raised.mode == WindowMode::Fullscreen || !floating)
+		{
+			self.restore_raised();
+		}
+
+		if floating {
+			let center = self.relative_center(window.geometry().size);
 			self.floating.map_window(window, center);
+		} else {
+			self.tiling.add_window(window, pointer);
 		}
 	}
 
 	pub fn remove_window(&mut self, window: &MappedWindow) {
-		if !self.tiling.remove_window(window) {
+		if self.window_mode(window).is_some() {
+			self.raised = None;
+		} else if !self.tiling.remove_window(window) {
 			self.floating.remove_window(window);
 		}
 	}
 
+	/// can the tiling split be resized?
+	pub fn has_split(&self) -> bool {
+		self.tiling.has_split()
+	}
+
 	/// is the [`MappedWindow`] in the floating space?
 	pub fn is_floating(&self, window: &MappedWindow) -> bool {
 		self.floating.windows().any(|w| w == window)
@@ -543,14 +767,16 @@ impl Workspace {
 	}
 
 	pub fn windows(&self) -> impl DoubleEndedIterator<Item = &MappedWindow> {
-		self.floating.windows().chain(self.tiling.windows())
+		let raised = self.raised.iter().map(|raised| &raised.window);
+		raised.chain(self.floating.windows()).chain(self.tiling.windows())
 	}
 
 	pub fn windows_geometry(
 		&self,
 	) -> impl DoubleEndedIterator<Item = (&MappedWindow, Rectangle<i32, Logical>)> {
-		self.floating
-			.windows_geometry()
+		let raised = (self.raised.iter()).map(|raised| (&raised.window, self.mode_geometry(raised.mode)));
+		raised
+			.chain(self.floating.windows_geometry())
 			.chain(self.tiling.windows_geometry())
 	}
 
@@ -564,7 +790,17 @@ impl Workspace {
 		&self,
 		location: Point<f64, Logical>,
 	) -> Option<(&MappedWindow, Point<i32, Logical>)> {
-		(self.floating.window_under(location))
+		let raised = (self.raised.as_ref())
+			.map(|raised| (raised, self.mode_geometry(raised.mode)))
+			.filter(|(_, geometry)| geometry.to_f64().contains(location));
+		let raised_under = |mode: WindowMode| {
+			(raised.filter(|(raised, _)| raised.mode == mode))
+				.map(|(raised, geometry)| (&raised.window, raised.window.render_location(geometry.loc)))
+		};
+
+		(raised_under(WindowMode::Fullscreen))
+			.or_else(|| self.floating.window_under(location))
+			.or_else(|| raised_under(WindowMode::Maximized))
 			.or_else(|| self.tiling.window_under(location))
 			.or_else(|| {
 				self.floating.windows().next_back().map(|w| {
@@ -576,7 +812,94 @@ impl Workspace {
 }
 
 impl Workspace {
+	/// the geometry of a window in the [`WindowMode`], relative to the output
+	fn mode_geometry(&self, mode: WindowMode) -> Rectangle<i32, Logical> {
+		let Some(output) = &self.output else {
+			return Rectangle::zero();
+		};
+
+		match mode {
+			WindowMode::Fullscreen => Rectangle::from_size(output_size(output)),
+			WindowMode::Maximized => layer_map_for_output(output).non_exclusive_zone(),
+		}
+	}
+
+	pub fn window_mode(&self, window: &MappedWindow) -> Option<WindowMode> {
+		(self.raised.as_ref())
+			.filter(|raised| raised.window == *window)
+			.map(|raised| raised.mode)
+	}
+
+	fn fullscreen_window(&self) -> Option<(&MappedWindow, Rectangle<i32, Logical>)> {
+		(self.raised.as_ref())
+			.filter(|raised| raised.mode == WindowMode::Fullscreen)
+			.map(|raised| (&raised.window, self.mode_geometry(raised.mode)))
+	}
+
+	/// take the window out of the layout and make it fullscreen or maximized
+	fn set_mode(&mut self, window: &MappedWindow, mode: WindowMode) {
+		if let Some(raised) = &mut self.raised
+			&& raised.window == *window
+		{
+			raised.mode = mode;
+		} else {
+			// there can only be one fullscreen or maximized window per workspace
+			self.restore_raised();
+
+			let restore = if let Some(location) = self.floating.window_location(window) {
+				let geometry = Rectangle::new(location, window.geometry().size);
+				self.floating.remove_window(window);
+				Restore::Floating(geometry)
+			} else if let Some(idx) = self.tiling.window_index(window) {
+				self.tiling.remove_window(window);
+				Restore::Tiling(idx)
+			} else {
+				return;
+			};
+
+			let window = window.clone();
+			self.raised = Some(Raised {
+				window,
+				mode,
+				restore,
+			});
+		}
+
+		window.set_mode(Some(mode));
+		window.resize(self.mode_geometry(mode));
+	}
+
+	/// put the window back into the layout, if it is fullscreen or maximized
+	fn restore(&mut self, window: &MappedWindow) {
+		if self.window_mode(window).is_some() {
+			self.restore_raised();
+		}
+	}
+
+	fn restore_raised(&mut self) {
+		let Some(Raised { window, restore, .. }) = self.raised.take() else {
+			return;
+		};
+
+		window.set_mode(None);
+		match restore {
+			Restore::Tiling(idx) => self.tiling.insert_window(idx, window),
+			Restore::Floating(geometry) => {
+				window.resize(geometry);
+				self.floating.map_window(window, geometry.loc);
+			}
+		}
+	}
+
+	fn resize_raised(&self) {
+		if let Some(raised) = &self.raised {
+			raised.window.resize(self.mode_geometry(raised.mode));
+		}
+	}
+
 	fn toggle_floating(&mut self, window: MappedWindow, pointer: Point<f64, Logical>) {
+		self.restore(&window);
+
 		if self.tiling.remove_window(&window) {
 			let (min, max) = window.min_max_size();
 			let output_size = self.output.as_ref().map(output_size).unwrap_or_default();
@@ -590,7 +913,7 @@ impl Workspace {
 			window.resize(Rectangle::new(center, size));
 
 			self.floating.map_window(window, center);
-		} else if !self.tiling.is_full() {
+		} else {
 			debug_assert!(self.floating.window_location(&window).is_some());
 
 			self.floating.remove_window(&window);
@@ -599,7 +922,8 @@ impl Workspace {
 	}
 
 	fn cycle_window(&self, prev: &MappedWindow, direction: CycleDirection) -> Option<NextWindow> {
-		let windows = self.tiling.windows().chain(self.floating.insertion_order());
+		let raised = self.raised.iter().map(|raised| &raised.window);
+		let windows = (raised.chain(self.tiling.windows())).chain(self.floating.insertion_order());
 		let window = match direction {
 			CycleDirection::Next => windows.twice().next_after(|w| *w == prev).unwrap(),
 			CycleDirection::Prev => windows.rev().twice().next_after(|w| *w == prev).unwrap(),
@@ -635,14 +959,24 @@ impl Workspace {
 		let scale = output.current_scale().fractional_scale();
 		let layer_map = layer_map_for_output(output);
 
-		let (lower, upper) = Workspace::layer_elements(&layer_map, scale);
+		let overlay = Workspace::layer_elements(&layer_map, &[Layer::Overlay], scale);
+		let top = Workspace::layer_elements(&layer_map, &[Layer::Top], scale);
+		let lower = Workspace::layer_elements(&layer_map, &[Layer::Bottom, Layer::Background], scale);
+
+		render_elements.extend(overlay.flat_map(|(surface, location)| {
+			surface.render_elements(renderer, location, Scale::from(scale), 1.)
+		}));
+
+		// fullscreen windows are drawn above the top layer
+		render_elements.extend(self.render_raised(renderer, scale, WindowMode::Fullscreen));
 
-		render_elements.extend(upper.flat_map(|(surface, location)| {
+		render_elements.extend(top.flat_map(|(surface, location)| {
 			surface.render_elements(renderer, location, Scale::from(scale), 1.)
 		}));
 
 		let focus = focus.as_ref();
 		render_elements.extend(self.floating.render(renderer, scale, decoration, focus));
+		render_elements.extend(self.render_raised(renderer, scale, WindowMode::Maximized));
 		render_elements.extend(self.tiling.render(renderer, scale, decoration, focus));
 
 		render_elements.extend(lower.flat_map(|(surface, location)| {
@@ -652,30 +986,35 @@ impl Workspace {
 		render_elements.into_iter()
 	}
 
-	fn layer_elements(
-		layer_map: &LayerMap,
+	fn render_raised(
+		&self,
+		renderer: &mut GlowRenderer,
 		scale: f64,
-	) -> (
-		impl Iterator<Item = LayerSurfacePoint<'_>>,
-		impl Iterator<Item = LayerSurfacePoint<'_>>,
-	) {
-		let upper = layer_map
-			.layers()
-			.filter(|surface| matches!(surface.layer(), Layer::Top | Layer::Overlay))
-			.filter_map(move |surface| {
-				let geometry = layer_map.layer_geometry(surface)?;
-				Some((surface, geometry.loc.to_physical_precise_round(scale)))
-			});
+		mode: WindowMode,
+	) -> Vec<MaylandRenderElements> {
+		let Some(raised) = self.raised.as_ref().filter(|raised| raised.mode == mode) else {
+			return Vec::new();
+		};
+
+		let window = &raised.window;
+		let geometry = self.mode_geometry(mode);
+
+		let render_rect = window.render_rectangle(geometry).to_physical_precise_round(scale);
+		window.crop_render_elements(renderer, render_rect, scale.into(), 1.)
+	}
 
-		let lower = layer_map
+	fn layer_elements<'a>(
+		layer_map: &'a LayerMap,
+		layers: &'a [Layer],
+		scale: f64,
+	) -> impl Iterator<Item = LayerSurfacePoint<'a>> {
+		layer_map
 			.layers()
-			.filter(|surface| matches!(surface.layer(), Layer::Background | Layer::Bottom))
+			.filter(move |surface| layers.contains(&surface.layer()))
 			.filter_map(move |surface| {
 				let geometry = layer_map.layer_geometry(surface)?;
 				Some((surface, geometry.loc.to_physical_precise_round(scale)))
-			});
-
-		(lower, upper)
+			})
 	}
 }
 
@@ -8,9 +8,13 @@ mod comm;
 mod cursor;
 mod input;
 mod layout;
+mod protocols;
 mod render;
+mod screenshot;
 mod shell;
 mod state;
+#[cfg(test)]
+mod tests;
 mod trace;
 mod utils;
 
@@ -26,6 +30,9 @@ pub struct Args {
 	/// enable debug logs
 	#[arg(long)]
 	debug: bool,
+	/// run without a display or gpu, e.g. for automated testing
+	#[arg(long)]
+	headless: bool,
 
 	/// print help
 	#[arg(long, short, action = clap::ArgAction::Help, global = true)]
@@ -57,6 +64,8 @@ fn main() {
 		("GDK_BACKEND".to_owned(), "wayland".to_owned()),
 	]);
 
+	state.mayland.start_xwayland();
+
 	event_loop
 		.run(None, &mut state, |state| {
 			state.refresh_and_redraw();
@@ -0,0 +1,4 @@
+pub mod foreign_toplevel;
+pub mod output_management;
+pub mod output_power;
+pub mod screencopy;
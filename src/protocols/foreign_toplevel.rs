@@ -0,0 +1,362 @@
+//! wlr-foreign-toplevel-management-unstable-v1 and ext-foreign-toplevel-list-v1,
+//! which let taskbars and docks list and control windows
+
+use crate::{
+	layout::workspace::WindowMode,
+	shell::window::MappedWindow,
+	state::{Mayland, State},
+};
+use smithay::{
+	delegate_foreign_toplevel_list,
+	output::Output,
+	reexports::{
+		wayland_protocols_wlr::foreign_toplevel::v1::server::{
+			zwlr_foreign_toplevel_handle_v1::{self, ZwlrForeignToplevelHandleV1},
+			zwlr_foreign_toplevel_manager_v1::{self, ZwlrForeignToplevelManagerV1},
+		},
+		wayland_server::{
+			Client, DataInit, Dispatch, DisplayHandle, GlobalDispatch, New, Resource, backend::ClientId,
+			protocol::wl_output::WlOutput,
+		},
+	},
+	utils::IsAlive,
+	wayland::foreign_toplevel_list::{
+		ForeignToplevelHandle, ForeignToplevelListHandler, ForeignToplevelListState,
+	},
+};
+
+const VERSION: u32 = 3;
+
+#[derive(Debug)]
+pub struct ForeignToplevelManagerState {
+	managers: Vec<ZwlrForeignToplevelManagerV1>,
+	toplevels: Vec<Toplevel>,
+}
+
+#[derive(Debug)]
+struct Toplevel {
+	window: MappedWindow,
+	/// the last state that was sent to the clients
+	info: ToplevelInfo,
+	handles: Vec<ZwlrForeignToplevelHandleV1>,
+	list_handle: ForeignToplevelHandle,
+}
+
+/// the state of a window that is published to taskbars
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ToplevelInfo {
+	app_id: Option<String>,
+	title: Option<String>,
+	states: Vec<zwlr_foreign_toplevel_handle_v1::State>,
+	/// the output the window is visible on
+	output: Option<Output>,
+}
+
+impl ToplevelInfo {
+	fn new(mayland: &Mayland, window: &MappedWindow) -> Self {
+		let mayland_comm::workspace::Window { app_id, title } = mayland_comm::workspace::Window::from(window);
+
+		let mut states = Vec::new();
+		if mayland
+			.keyboard
+			.current_focus()
+			.is_some_and(|focus| focus == *window)
+		{
+			states.push(zwlr_foreign_toplevel_handle_v1::State::Activated);
+		}
+
+		match mayland.workspaces.window_mode(window) {
+			Some(WindowMode::Fullscreen) => states.push(zwlr_foreign_toplevel_handle_v1::State::Fullscreen),
+			Some(WindowMode::Maximized) => states.push(zwlr_foreign_toplevel_handle_v1::State::Maximized),
+			None => {}
+		}
+
+		let output = (mayland.workspaces.outputs())
+			.find(|output| {
+				let mut windows = mayland.workspaces.windows_for_output(output);
+				windows.any(|w| w == window)
+			})
+			.cloned();
+
+		ToplevelInfo {
+			app_id,
+			title,
+			states,
+			output,
+		}
+	}
+
+	/// send everything that changed since `prev` to the handle
+	fn send(&self, handle: &ZwlrForeignToplevelHandleV1, prev: Option<&ToplevelInfo>) {
+		if let Some(title) = &self.title
+			&& prev.is_none_or(|prev| prev.title != self.title)
+		{
+			handle.title(title.clone());
+		}
+
+		if let Some(app_id) = &self.app_id
+			&& prev.is_none_or(|prev| prev.app_id != self.app_id)
+		{
+			handle.app_id(app_id.clone());
+		}
+
+		if prev.is_none_or(|prev| prev.states != self.states) {
+			let states = (self.states.iter())
+				.flat_map(|state| (*state as u32).to_ne_bytes())
+				.collect();
+			handle.state(states);
+		}
+
+		let prev_output = prev.and_then(|prev| prev.output.as_ref());
+		if prev_output != self.output.as_ref()
+			&& let Some(client) = handle.client()
+		{
+			if let Some(output) = prev_output {
+				for wl_output in output.client_outputs(&client) {
+					handle.output_leave(&wl_output);
+				}
+			}
+
+			if let Some(output) = &self.output {
+				for wl_output in output.client_outputs(&client) {
+					handle.output_enter(&wl_output);
+				}
+			}
+		}
+
+		handle.done();
+	}
+
+	/// send the changed title and app id to the ext-foreign-toplevel-list handle
+	fn send_list(&self, handle: &ForeignToplevelHandle, prev: &ToplevelInfo) {
+		if prev.title == self.title && prev.app_id == self.app_id {
+			return;
+		}
+
+		if prev.title != self.title {
+			handle.send_title(self.title.as_deref().unwrap_or_default());
+		}
+
+		if prev.app_id != self.app_id {
+			handle.send_app_id(self.app_id.as_deref().unwrap_or_default());
+		}
+
+		handle.send_done();
+	}
+}
+
+/// create a new handle for the manager and send the initial state
+fn new_handle(
+	display_handle: &DisplayHandle,
+	manager: &ZwlrForeignToplevelManagerV1,
+	info: &ToplevelInfo,
+) -> Option<ZwlrForeignToplevelHandleV1> {
+	let client = manager.client()?;
+	let handle = client
+		.create_resource::<ZwlrForeignToplevelHandleV1, _, State>(display_handle, manager.version(), ())
+		.ok()?;
+
+	manager.toplevel(&handle);
+	info.send(&handle, None);
+
+	Some(handle)
+}
+
+impl ForeignToplevelManagerState {
+	pub fn new(display_handle: &DisplayHandle) -> Self {
+		display_handle.create_global::<State, ZwlrForeignToplevelManagerV1, _>(VERSION, ());
+
+		ForeignToplevelManagerState {
+			managers: Vec::new(),
+			toplevels: Vec::new(),
+		}
+	}
+}
+
+impl Mayland {
+	/// publish new, changed and closed windows to taskbars
+	pub fn refresh_foreign_toplevels(&mut self) {
+		let windows = (self.workspaces.workspaces())
+			.flat_map(|workspace| workspace.windows())
+			.cloned()
+			.collect::<Vec<_>>();
+
+		let list_state = &mut self.foreign_toplevel_list_state;
+		self.foreign_toplevel_state.toplevels.retain(|toplevel| {
+			if toplevel.window.alive() && windows.contains(&toplevel.window) {
+				return true;
+			}
+
+			for handle in &toplevel.handles {
+				handle.closed();
+			}
+			list_state.remove_toplevel(&toplevel.list_handle);
+
+			false
+		});
+
+		for window in &windows {
+			self.refresh_foreign_toplevel(window);
+		}
+	}
+
+	/// publish the current state of the window to taskbars
+	pub fn refresh_foreign_toplevel(&mut self, window: &MappedWindow) {
+		let info = ToplevelInfo::new(self, window);
+
+		let state = &mut self.foreign_toplevel_state;
+		if let Some(toplevel) = state
+			.toplevels
+			.iter_mut()
+			.find(|toplevel| toplevel.window == *window)
+		{
+			if toplevel.info == info {
+				return;
+			}
+
+			for handle in &toplevel.handles {
+				info.send(handle, Some(&toplevel.info));
+			}
+			info.send_list(&toplevel.list_handle, &toplevel.info);
+
+			toplevel.info = info;
+		} else {
+			let list_handle = self.foreign_toplevel_list_state.new_toplevel::<State>(
+				info.title.clone().unwrap_or_default(),
+				info.app_id.clone().unwrap_or_default(),
+			);
+
+			let handles = (state.managers.iter())
+				.filter_map(|manager| new_handle(&self.display_handle, manager, &info))
+				.collect();
+
+			state.toplevels.push(Toplevel {
+				window: window.clone(),
+				info,
+				handles,
+				list_handle,
+			});
+		}
+	}
+
+	/// a client bound the [`Output`] after the windows on it were published
+	pub fn foreign_toplevel_output_bound(&self, output: &Output, wl_output: &WlOutput) {
+		let Some(client) = wl_output.client() else {
+			return;
+		};
+
+		let toplevels = (self.foreign_toplevel_state.toplevels.iter())
+			.filter(|toplevel| toplevel.info.output.as_ref() == Some(output));
+
+		for toplevel in toplevels {
+			for handle in &toplevel.handles {
+				if handle.client().as_ref() == Some(&client) {
+					handle.output_enter(wl_output);
+					handle.done();
+				}
+			}
+		}
+	}
+}
+
+impl GlobalDispatch<ZwlrForeignToplevelManagerV1, ()> for State {
+	fn bind(
+		state: &mut Self,
+		handle: &DisplayHandle,
+		_client: &Client,
+		resource: New<ZwlrForeignToplevelManagerV1>,
+		_global_data: &(),
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		let manager = data_init.init(resource, ());
+
+		for toplevel in &mut state.mayland.foreign_toplevel_state.toplevels {
+			if let Some(toplevel_handle) = new_handle(handle, &manager, &toplevel.info) {
+				toplevel.handles.push(toplevel_handle);
+			}
+		}
+
+		state.mayland.foreign_toplevel_state.managers.push(manager);
+	}
+}
+
+impl Dispatch<ZwlrForeignToplevelManagerV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		manager: &ZwlrForeignToplevelManagerV1,
+		request: zwlr_foreign_toplevel_manager_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_foreign_toplevel_manager_v1::Request::Stop => {
+				(state.mayland.foreign_toplevel_state.managers).retain(|m| m != manager);
+				manager.finished();
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, manager: &ZwlrForeignToplevelManagerV1, _data: &()) {
+		(state.mayland.foreign_toplevel_state.managers).retain(|m| m != manager);
+	}
+}
+
+impl Dispatch<ZwlrForeignToplevelHandleV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		handle: &ZwlrForeignToplevelHandleV1,
+		request: zwlr_foreign_toplevel_handle_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		// the window might have already been closed
+		let Some(window) = (state.mayland.foreign_toplevel_state.toplevels.iter())
+			.find(|toplevel| toplevel.handles.contains(handle))
+			.map(|toplevel| toplevel.window.clone())
+		else {
+			return;
+		};
+
+		match request {
+			zwlr_foreign_toplevel_handle_v1::Request::Activate { .. } => state.activate_window(window),
+			zwlr_foreign_toplevel_handle_v1::Request::Close => window.close(),
+			zwlr_foreign_toplevel_handle_v1::Request::SetMaximized => {
+				state.window_mode_request(&window, WindowMode::Maximized, true);
+			}
+			zwlr_foreign_toplevel_handle_v1::Request::UnsetMaximized => {
+				state.window_mode_request(&window, WindowMode::Maximized, false);
+			}
+			zwlr_foreign_toplevel_handle_v1::Request::SetFullscreen { .. } => {
+				state.window_mode_request(&window, WindowMode::Fullscreen, true);
+			}
+			zwlr_foreign_toplevel_handle_v1::Request::UnsetFullscreen => {
+				state.window_mode_request(&window, WindowMode::Fullscreen, false);
+			}
+			// mayland has no concept of minimized windows
+			zwlr_foreign_toplevel_handle_v1::Request::SetMinimized
+			| zwlr_foreign_toplevel_handle_v1::Request::UnsetMinimized => {}
+			zwlr_foreign_toplevel_handle_v1::Request::SetRectangle { .. } => {}
+			zwlr_foreign_toplevel_handle_v1::Request::Destroy => {}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, handle: &ZwlrForeignToplevelHandleV1, _data: &()) {
+		for toplevel in &mut state.mayland.foreign_toplevel_state.toplevels {
+			toplevel.handles.retain(|h| h != handle);
+		}
+	}
+}
+
+impl ForeignToplevelListHandler for State {
+	fn foreign_toplevel_list_state(&mut self) -> &mut ForeignToplevelListState {
+		&mut self.mayland.foreign_toplevel_list_state
+	}
+}
+
+delegate_foreign_toplevel_list!(State);
@@ -0,0 +1,838 @@
+//! wlr-output-management-unstable-v1, which lets clients like kanshi and wdisplays configure outputs
+//!
+//! applied configurations are written to the output config, so they are
+//! lost when the config file is reloaded
+
+use crate::{state::State, utils::output_scale};
+use smithay::{
+	reexports::{
+		wayland_protocols_wlr::output_management::v1::server::{
+			zwlr_output_configuration_head_v1::{self, ZwlrOutputConfigurationHeadV1},
+			zwlr_output_configuration_v1::{self, ZwlrOutputConfigurationV1},
+			zwlr_output_head_v1::{self, ZwlrOutputHeadV1},
+			zwlr_output_manager_v1::{self, ZwlrOutputManagerV1},
+			zwlr_output_mode_v1::{self, ZwlrOutputModeV1},
+		},
+		wayland_server::{
+			Client, DataInit, Dispatch, DisplayHandle, GlobalDispatch, New, Resource, WEnum,
+			backend::ClientId,
+		},
+	},
+	utils::{Logical, Physical, Point, Rectangle, Size, Transform},
+};
+use std::collections::HashMap;
+
+const VERSION: u32 = 4;
+
+#[derive(Debug)]
+pub struct OutputManagementState {
+	managers: HashMap<ZwlrOutputManagerV1, Vec<HeadHandle>>,
+	configurations: HashMap<ZwlrOutputConfigurationV1, Configuration>,
+	configuration_heads: HashMap<ZwlrOutputConfigurationHeadV1, (ZwlrOutputConfigurationV1, String)>,
+	/// the heads that were last sent to the clients
+	heads: Vec<Head>,
+	serial: u32,
+	/// the outputs have changed since the heads were last sent
+	dirty: bool,
+}
+
+/// a head and its modes, as sent to a single manager
+#[derive(Debug)]
+struct HeadHandle {
+	name: String,
+	head: ZwlrOutputHeadV1,
+	modes: Vec<ZwlrOutputModeV1>,
+}
+
+impl HeadHandle {
+	fn finished(&self) {
+		for mode in &self.modes {
+			mode.finished();
+		}
+		self.head.finished();
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Head {
+	name: String,
+	make: String,
+	model: String,
+	serial: Option<String>,
+	size: Option<(u32, u32)>,
+	modes: Vec<HeadMode>,
+	/// the index of the current mode in `modes`
+	current_mode: Option<usize>,
+	/// `None` if the output isn't mapped
+	logical: Option<HeadLogical>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HeadMode {
+	w: i32,
+	h: i32,
+	/// refresh rate in mhz
+	refresh: i32,
+	preferred: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeadLogical {
+	position: Point<i32, Logical>,
+	transform: Transform,
+	scale: f64,
+}
+
+impl From<mayland_comm::Output> for Head {
+	fn from(output: mayland_comm::Output) -> Self {
+		let modes = (output.modes.iter())
+			.map(|mode| HeadMode {
+				w: i32::from(mode.w),
+				h: i32::from(mode.h),
+				refresh: mode.refresh as i32,
+				preferred: mode.preferred,
+			})
+			.collect::<Vec<_>>();
+
+		let current_mode = output.mode.and_then(|current| {
+			(modes.iter()).position(|mode| {
+				mode.w == i32::from(current.w)
+					&& mode.h == i32::from(current.h)
+					&& mode.refresh == current.refresh as i32
+			})
+		});
+
+		let logical = output.logical.map(|logical| HeadLogical {
+			position: Point::new(logical.x, logical.y),
+			transform: transform(logical.transform),
+			scale: logical.scale,
+		});
+
+		Head {
+			name: output.name,
+			make: output.make,
+			model: output.model,
+			serial: output.serial,
+			size: output.size,
+			modes,
+			current_mode,
+			logical,
+		}
+	}
+}
+
+fn transform(transform: mayland_comm::output::Transform) -> Transform {
+	match transform {
+		mayland_comm::output::Transform::Normal => Transform::Normal,
+		mayland_comm::output::Transform::_90 => Transform::_90,
+		mayland_comm::output::Transform::_180 => Transform::_180,
+		mayland_comm::output::Transform::_270 => Transform::_270,
+		mayland_comm::output::Transform::Flipped => Transform::Flipped,
+		mayland_comm::output::Transform::Flipped90 => Transform::Flipped90,
+		mayland_comm::output::Transform::Flipped180 => Transform::Flipped180,
+		mayland_comm::output::Transform::Flipped270 => Transform::Flipped270,
+	}
+}
+
+impl Head {
+	/// the head still describes the same output with the same modes,
+	/// so only the current state has to be sent again
+	fn same_device(&self, other: &Head) -> bool {
+		self.name == other.name
+			&& self.make == other.make
+			&& self.model == other.model
+			&& self.serial == other.serial
+			&& self.size == other.size
+			&& self.modes == other.modes
+	}
+
+	/// create the head and its modes for the manager and send the initial state
+	fn create(&self, display_handle: &DisplayHandle, manager: &ZwlrOutputManagerV1) -> Option<HeadHandle> {
+		let client = manager.client()?;
+		let head = client
+			.create_resource::<ZwlrOutputHeadV1, _, State>(display_handle, manager.version(), ())
+			.ok()?;
+		manager.head(&head);
+
+		head.name(self.name.clone());
+		head.description(format!("{} {} ({})", self.make, self.model, self.name));
+		if let Some((w, h)) = self.size {
+			head.physical_size(w as i32, h as i32);
+		}
+
+		let mut modes = Vec::with_capacity(self.modes.len());
+		for head_mode in &self.modes {
+			let Ok(mode) =
+				client.create_resource::<ZwlrOutputModeV1, _, State>(display_handle, head.version(), ())
+			else {
+				continue;
+			};
+			head.mode(&mode);
+
+			mode.size(head_mode.w, head_mode.h);
+			mode.refresh(head_mode.refresh);
+			if head_mode.preferred {
+				mode.preferred();
+			}
+
+			modes.push(mode);
+		}
+
+		if head.version() >= zwlr_output_head_v1::EVT_MAKE_SINCE {
+			head.make(self.make.clone());
+			head.model(self.model.clone());
+			if let Some(serial) = &self.serial {
+				head.serial_number(serial.clone());
+			}
+		}
+
+		let handle = HeadHandle {
+			name: self.name.clone(),
+			head,
+			modes,
+		};
+		self.send_state(&handle, None);
+
+		Some(handle)
+	}
+
+	/// send the current state that changed since `prev`
+	fn send_state(&self, handle: &HeadHandle, prev: Option<&Head>) {
+		let head = &handle.head;
+
+		let enabled = self.logical.is_some();
+		if prev.is_none_or(|prev| prev.logical.is_some() != enabled) {
+			head.enabled(i32::from(enabled));
+		}
+
+		let Some(logical) = &self.logical else {
+			return;
+		};
+
+		// everything has to be sent again after the head was enabled
+		let prev_logical = prev.and_then(|prev| prev.logical.as_ref());
+		let prev_mode = prev
+			.filter(|_| prev_logical.is_some())
+			.and_then(|prev| prev.current_mode);
+
+		if prev_mode != self.current_mode
+			&& let Some(mode) = self.current_mode.and_then(|idx| handle.modes.get(idx))
+		{
+			head.current_mode(mode);
+		}
+
+		if prev_logical.is_none_or(|prev| prev.position != logical.position) {
+			head.position(logical.position.x, logical.position.y);
+		}
+
+		if prev_logical.is_none_or(|prev| prev.transform != logical.transform) {
+			head.transform(logical.transform.into());
+		}
+
+		if prev_logical.is_none_or(|prev| prev.scale != logical.scale) {
+			head.scale(logical.scale);
+		}
+
+		if prev_logical.is_none() && head.version() >= zwlr_output_head_v1::EVT_ADAPTIVE_SYNC_SINCE {
+			head.adaptive_sync(zwlr_output_head_v1::AdaptiveSyncState::Disabled);
+		}
+	}
+}
+
+/// a configuration that was created by a client, but not yet applied
+#[derive(Debug)]
+struct Configuration {
+	serial: u32,
+	/// the configured heads, `None` if the head should be disabled
+	heads: HashMap<String, Option<HeadConfig>>,
+	/// the configuration was already applied or tested
+	used: bool,
+}
+
+#[derive(Debug, Default)]
+struct HeadConfig {
+	mode: Option<ConfigMode>,
+	position: Option<Point<i32, Logical>>,
+	transform: Option<Transform>,
+	scale: Option<f64>,
+	adaptive_sync: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ConfigMode {
+	w: i32,
+	h: i32,
+	/// refresh rate in mhz, 0 if any refresh rate is fine
+	refresh: i32,
+}
+
+impl OutputManagementState {
+	pub fn new(display_handle: &DisplayHandle) -> Self {
+		display_handle.create_global::<State, ZwlrOutputManagerV1, _>(VERSION, ());
+
+		OutputManagementState {
+			managers: HashMap::new(),
+			configurations: HashMap::new(),
+			configuration_heads: HashMap::new(),
+			heads: Vec::new(),
+			serial: 0,
+			dirty: true,
+		}
+	}
+
+	/// an output was added, removed or reconfigured
+	pub fn outputs_changed(&mut self) {
+		self.dirty = true;
+	}
+
+	/// send the heads that changed to every manager
+	fn update(&mut self, display_handle: &DisplayHandle, heads: Vec<Head>) {
+		if self.heads == heads {
+			return;
+		}
+
+		self.serial = self.serial.wrapping_add(1);
+
+		for (manager, handles) in &mut self.managers {
+			handles.retain(|handle| {
+				let prev = (self.heads.iter()).find(|head| head.name == handle.name);
+				let new = (heads.iter()).find(|head| head.name == handle.name);
+
+				match (prev, new) {
+					(Some(prev), Some(new)) if new.same_device(prev) => {
+						new.send_state(handle, Some(prev));
+						true
+					}
+					_ => {
+						handle.finished();
+						false
+					}
+				}
+			});
+
+			for head in &heads {
+				if !handles.iter().any(|handle| handle.name == head.name)
+					&& let Some(handle) = head.create(display_handle, manager)
+				{
+					handles.push(handle);
+				}
+			}
+
+			manager.done(self.serial);
+		}
+
+		self.heads = heads;
+	}
+
+	fn head_name(&self, head: &ZwlrOutputHeadV1) -> Option<String> {
+		(self.managers.values().flatten())
+			.find(|handle| handle.head == *head)
+			.map(|handle| handle.name.clone())
+	}
+
+	fn mode(&self, mode: &ZwlrOutputModeV1) -> Option<HeadMode> {
+		let (handle, idx) = (self.managers.values().flatten())
+			.find_map(|handle| Some((handle, handle.modes.iter().position(|m| m == mode)?)))?;
+
+		let head = (self.heads.iter()).find(|head| head.name == handle.name)?;
+		head.modes.get(idx).copied()
+	}
+
+	/// build the output config that results from applying the configuration
+	fn configured_outputs(
+		&self,
+		configuration: &Configuration,
+		current: &mayland_config::Outputs,
+	) -> Result<mayland_config::Outputs, String> {
+		let mut outputs = current.clone();
+		let mut geometries = Vec::<(&str, Rectangle<i32, Logical>)>::new();
+
+		for head in &self.heads {
+			let mut output_config = (current.get_output_by_name(&head.name).cloned()).unwrap_or_default();
+
+			let Some(head_config) = &configuration.heads[&head.name] else {
+				// mirroring outputs are advertised as disabled heads,
+				// so clients send them back disabled without meaning to change them
+				let mirroring = head.logical.is_none() && output_config.mirror.is_some();
+				if !mirroring {
+					output_config.disable = true;
+				}
+				outputs.set_output(head.name.clone(), output_config);
+				continue;
+			};
+
+			if head_config.adaptive_sync == Some(true) {
+				return Err("adaptive sync is not supported".to_owned());
+			}
+
+			// an enabled output stops mirroring, so that it gets mapped
+			output_config.disable = false;
+			output_config.mirror = None;
+
+			// a refresh rate of 0 lets mayland pick the highest one
+			let mode = match head_config.mode {
+				Some(target) => {
+					let mode = (head.modes.iter())
+						.find(|mode| {
+							mode.w == target.w
+								&& mode.h == target.h && (target.refresh == 0 || mode.refresh == target.refresh)
+						})
+						.ok_or_else(|| format!("output {:?} doesn't support the mode", head.name))?;
+					Some((mode, (target.refresh != 0).then_some(mode.refresh)))
+				}
+				None => (head.current_mode.and_then(|idx| head.modes.get(idx)))
+					.map(|mode| (mode, Some(mode.refresh))),
+			};
+
+			// outputs that weren't enabled before keep their configured values
+			if let Some((mode, refresh)) = mode {
+				output_config.mode = Some(mayland_config::outputs::Mode {
+					width: u16::try_from(mode.w).map_err(|err| err.to_string())?,
+					height: u16::try_from(mode.h).map_err(|err| err.to_string())?,
+					refresh,
+				});
+			}
+
+			let position = (head_config.position).or(head.logical.map(|logical| logical.position));
+			if let Some(position) = position {
+				output_config.position = Some([position.x, position.y]);
+			}
+
+			if let Some(transform) = (head_config.transform).or(head.logical.map(|logical| logical.transform))
+			{
+				output_config.transform = mayland_config::outputs::Transform::from(transform);
+			}
+
+			if let Some(scale) = (head_config.scale).or(head.logical.map(|logical| logical.scale)) {
+				output_config.scale = Some(scale);
+			}
+
+			// the outputs are laid out with their logical size
+			if let (Some(position), Some((mode, _))) = (position, mode) {
+				let transform = Transform::from(output_config.transform);
+				let scale = output_scale(Some(&output_config)).fractional_scale();
+				let size = (transform.transform_size(Size::<i32, Physical>::from((mode.w, mode.h))))
+					.to_f64()
+					.to_logical(scale)
+					.to_i32_round();
+				let geometry = Rectangle::new(position, size);
+
+				if let Some((other, _)) = (geometries.iter()).find(|(_, other)| other.overlaps(geometry)) {
+					return Err(format!("output {:?} overlaps with output {:?}", head.name, other));
+				}
+				geometries.push((&head.name, geometry));
+			}
+
+			outputs.set_output(head.name.clone(), output_config);
+		}
+
+		Ok(outputs)
+	}
+}
+
+impl State {
+	/// send the current state of the outputs to the clients, if it changed
+	pub fn refresh_output_management(&mut self) {
+		if !std::mem::take(&mut self.mayland.output_management_state.dirty) {
+			return;
+		}
+
+		let outputs = self.backend.comm_outputs(&self.mayland);
+		let heads = outputs.into_iter().map(Head::from).collect();
+
+		let display_handle = &self.mayland.display_handle;
+		self.mayland.output_management_state.update(display_handle, heads);
+	}
+
+	/// test or apply the configuration, which is rolled back if applying it fails
+	fn apply_output_configuration(&mut self, configuration: &ZwlrOutputConfigurationV1, test: bool) {
+		let state = &mut self.mayland.output_management_state;
+		let Some(pending) = state.configurations.get_mut(configuration) else {
+			return;
+		};
+
+		if pending.used {
+			configuration.post_error(
+				zwlr_output_configuration_v1::Error::AlreadyUsed,
+				"configuration was already used",
+			);
+			return;
+		}
+		pending.used = true;
+		let pending = &state.configurations[configuration];
+
+		// the outputs changed since the client created the configuration
+		if pending.serial != state.serial || state.dirty {
+			configuration.cancelled();
+			return;
+		}
+
+		if let Some(head) = (state.heads.iter()).find(|head| !pending.heads.contains_key(&head.name)) {
+			configuration.post_error(
+				zwlr_output_configuration_v1::Error::UnconfiguredHead,
+				format!("output {:?} is not configured", head.name),
+			);
+			return;
+		}
+
+		let outputs = match state.configured_outputs(pending, &self.mayland.config.output) {
+			Ok(outputs) => outputs,
+			Err(err) => {
+				tracing::info!("rejected output configuration: {}", err);
+				configuration.failed();
+				return;
+			}
+		};
+
+		if test {
+			configuration.succeeded();
+			return;
+		}
+
+		match self.override_output_config(outputs) {
+			Ok(()) => configuration.succeeded(),
+			Err(err) => {
+				tracing::warn!("failed to apply output configuration: {}", err);
+				configuration.failed();
+			}
+		}
+	}
+}
+
+impl GlobalDispatch<ZwlrOutputManagerV1, ()> for State {
+	fn bind(
+		state: &mut Self,
+		handle: &DisplayHandle,
+		_client: &Client,
+		resource: New<ZwlrOutputManagerV1>,
+		_global_data: &(),
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		let manager = data_init.init(resource, ());
+
+		let output_management_state = &mut state.mayland.output_management_state;
+		let handles = (output_management_state.heads.iter())
+			.filter_map(|head| head.create(handle, &manager))
+			.collect();
+		manager.done(output_management_state.serial);
+
+		output_management_state.managers.insert(manager, handles);
+	}
+}
+
+impl Dispatch<ZwlrOutputManagerV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		manager: &ZwlrOutputManagerV1,
+		request: zwlr_output_manager_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_output_manager_v1::Request::CreateConfiguration { id, serial } => {
+				let configuration = data_init.init(id, ());
+
+				let pending = Configuration {
+					serial,
+					heads: HashMap::new(),
+					used: false,
+				};
+				(state.mayland.output_management_state.configurations).insert(configuration, pending);
+			}
+			zwlr_output_manager_v1::Request::Stop => {
+				state.mayland.output_management_state.managers.remove(manager);
+				manager.finished();
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, manager: &ZwlrOutputManagerV1, _data: &()) {
+		state.mayland.output_management_state.managers.remove(manager);
+	}
+}
+
+impl Dispatch<ZwlrOutputHeadV1, ()> for State {
+	fn request(
+		_state: &mut Self,
+		_client: &Client,
+		_head: &ZwlrOutputHeadV1,
+		request: zwlr_output_head_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_output_head_v1::Request::Release => {}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, head: &ZwlrOutputHeadV1, _data: &()) {
+		for handles in state.mayland.output_management_state.managers.values_mut() {
+			handles.retain(|handle| handle.head != *head);
+		}
+	}
+}
+
+impl Dispatch<ZwlrOutputModeV1, ()> for State {
+	fn request(
+		_state: &mut Self,
+		_client: &Client,
+		_mode: &ZwlrOutputModeV1,
+		request: zwlr_output_mode_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_output_mode_v1::Request::Release => {}
+			_ => unreachable!(),
+		}
+	}
+}
+
+impl Dispatch<ZwlrOutputConfigurationV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		configuration: &ZwlrOutputConfigurationV1,
+		request: zwlr_output_configuration_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		let output_management_state = &mut state.mayland.output_management_state;
+
+		match request {
+			zwlr_output_configuration_v1::Request::EnableHead { id, head } => {
+				let configuration_head = data_init.init(id, ());
+
+				// the head might have already been removed, in which case
+				// the configuration is cancelled once it is applied
+				let Some(name) = output_management_state.head_name(&head) else {
+					return;
+				};
+
+				let Some(pending) = output_management_state.configurations.get_mut(configuration) else {
+					return;
+				};
+
+				if pending.used {
+					configuration.post_error(
+						zwlr_output_configuration_v1::Error::AlreadyUsed,
+						"configuration was already used",
+					);
+					return;
+				}
+
+				if pending.heads.contains_key(&name) {
+					configuration.post_error(
+						zwlr_output_configuration_v1::Error::AlreadyConfiguredHead,
+						format!("output {name:?} was already configured"),
+					);
+					return;
+				}
+
+				pending.heads.insert(name.clone(), Some(HeadConfig::default()));
+				(output_management_state.configuration_heads)
+					.insert(configuration_head, (configuration.clone(), name));
+			}
+			zwlr_output_configuration_v1::Request::DisableHead { head } => {
+				let Some(name) = output_management_state.head_name(&head) else {
+					return;
+				};
+
+				let Some(pending) = output_management_state.configurations.get_mut(configuration) else {
+					return;
+				};
+
+				if pending.used {
+					configuration.post_error(
+						zwlr_output_configuration_v1::Error::AlreadyUsed,
+						"configuration was already used",
+					);
+					return;
+				}
+
+				if pending.heads.contains_key(&name) {
+					configuration.post_error(
+						zwlr_output_configuration_v1::Error::AlreadyConfiguredHead,
+						format!("output {name:?} was already configured"),
+					);
+					return;
+				}
+
+				pending.heads.insert(name, None);
+			}
+			zwlr_output_configuration_v1::Request::Apply => {
+				state.apply_output_configuration(configuration, false)
+			}
+			zwlr_output_configuration_v1::Request::Test => {
+				state.apply_output_configuration(configuration, true)
+			}
+			zwlr_output_configuration_v1::Request::Destroy => {}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, configuration: &ZwlrOutputConfigurationV1, _data: &()) {
+		let output_management_state = &mut state.mayland.output_management_state;
+		output_management_state.configurations.remove(configuration);
+		(output_management_state.configuration_heads).retain(|_, (c, _)| c != configuration);
+	}
+}
+
+impl Dispatch<ZwlrOutputConfigurationHeadV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		configuration_head: &ZwlrOutputConfigurationHeadV1,
+		request: zwlr_output_configuration_head_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		let output_management_state = &mut state.mayland.output_management_state;
+
+		// the mode has to be looked up before borrowing the configuration
+		let mode = match &request {
+			zwlr_output_configuration_head_v1::Request::SetMode { mode } => {
+				output_management_state.mode(mode)
+			}
+			_ => None,
+		};
+
+		// the head was removed before it was enabled
+		let Some((configuration, name)) = output_management_state
+			.configuration_heads
+			.get(configuration_head)
+		else {
+			return;
+		};
+
+		let Some(Some(head_config)) = (output_management_state.configurations.get_mut(configuration))
+			.filter(|pending| !pending.used)
+			.and_then(|pending| pending.heads.get_mut(name))
+		else {
+			return;
+		};
+
+		let already_set = |field: &str| {
+			configuration_head.post_error(
+				zwlr_output_configuration_head_v1::Error::AlreadySet,
+				format!("{field} was already set"),
+			);
+		};
+
+		match request {
+			zwlr_output_configuration_head_v1::Request::SetMode { .. } => {
+				if head_config.mode.is_some() {
+					return already_set("mode");
+				}
+
+				// the mode belongs to a head that was removed, so the configuration is cancelled anyway
+				let Some(mode) = mode else {
+					return;
+				};
+
+				head_config.mode = Some(ConfigMode {
+					w: mode.w,
+					h: mode.h,
+					refresh: mode.refresh,
+				});
+			}
+			zwlr_output_configuration_head_v1::Request::SetCustomMode {
+				width,
+				height,
+				refresh,
+			} => {
+				if head_config.mode.is_some() {
+					return already_set("mode");
+				}
+
+				if width <= 0 || height <= 0 || refresh < 0 {
+					configuration_head.post_error(
+						zwlr_output_configuration_head_v1::Error::InvalidCustomMode,
+						"invalid custom mode",
+					);
+					return;
+				}
+
+				head_config.mode = Some(ConfigMode {
+					w: width,
+					h: height,
+					refresh,
+				});
+			}
+			zwlr_output_configuration_head_v1::Request::SetPosition { x, y } => {
+				if head_config.position.is_some() {
+					return already_set("position");
+				}
+
+				head_config.position = Some(Point::new(x, y));
+			}
+			zwlr_output_configuration_head_v1::Request::SetTransform { transform } => {
+				if head_config.transform.is_some() {
+					return already_set("transform");
+				}
+
+				let WEnum::Value(transform) = transform else {
+					configuration_head.post_error(
+						zwlr_output_configuration_head_v1::Error::InvalidTransform,
+						"invalid transform",
+					);
+					return;
+				};
+
+				head_config.transform = Some(transform.into());
+			}
+			zwlr_output_configuration_head_v1::Request::SetScale { scale } => {
+				if head_config.scale.is_some() {
+					return already_set("scale");
+				}
+
+				if scale <= 0. || !scale.is_finite() {
+					configuration_head.post_error(
+						zwlr_output_configuration_head_v1::Error::InvalidScale,
+						"invalid scale",
+					);
+					return;
+				}
+
+				head_config.scale = Some(scale);
+			}
+			zwlr_output_configuration_head_v1::Request::SetAdaptiveSync { state } => {
+				if head_config.adaptive_sync.is_some() {
+					return already_set("adaptive sync");
+				}
+
+				let adaptive_sync = match state {
+					WEnum::Value(zwlr_output_head_v1::AdaptiveSyncState::Enabled) => true,
+					WEnum::Value(zwlr_output_head_v1::AdaptiveSyncState::Disabled) => false,
+					_ => {
+						configuration_head.post_error(
+							zwlr_output_configuration_head_v1::Error::InvalidAdaptiveSyncState,
+							"invalid adaptive sync state",
+						);
+						return;
+					}
+				};
+
+				head_config.adaptive_sync = Some(adaptive_sync);
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(
+		state: &mut Self,
+		_client: ClientId,
+		configuration_head: &ZwlrOutputConfigurationHeadV1,
+		_data: &(),
+	) {
+		(state.mayland.output_management_state.configuration_heads).remove(configuration_head);
+	}
+}
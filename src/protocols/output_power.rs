@@ -0,0 +1,141 @@
+//! wlr-output-power-management-v1, which lets clients like idle daemons turn outputs off
+
+use crate::state::State;
+use smithay::{
+	output::Output,
+	reexports::{
+		wayland_protocols_wlr::output_power_management::v1::server::{
+			zwlr_output_power_manager_v1::{self, ZwlrOutputPowerManagerV1},
+			zwlr_output_power_v1::{self, ZwlrOutputPowerV1},
+		},
+		wayland_server::{
+			Client, DataInit, Dispatch, DisplayHandle, GlobalDispatch, New, Resource, WEnum,
+			backend::ClientId,
+		},
+	},
+};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct OutputPowerManagementState {
+	powers: HashMap<ZwlrOutputPowerV1, Output>,
+}
+
+impl OutputPowerManagementState {
+	pub fn new(display_handle: &DisplayHandle) -> Self {
+		display_handle.create_global::<State, ZwlrOutputPowerManagerV1, _>(1, ());
+
+		OutputPowerManagementState {
+			powers: HashMap::new(),
+		}
+	}
+
+	/// notify the clients that the [`Output`] was turned on or off
+	pub fn mode_changed(&self, output: &Output, powered: bool) {
+		for (power, _) in self.powers.iter().filter(|(_, o)| *o == output) {
+			power.mode(mode(powered));
+		}
+	}
+
+	/// the power objects of a removed [`Output`] are no longer valid
+	pub fn output_removed(&mut self, output: &Output) {
+		self.powers.retain(|power, o| {
+			if o == output {
+				power.failed();
+				false
+			} else {
+				true
+			}
+		});
+	}
+}
+
+fn mode(powered: bool) -> zwlr_output_power_v1::Mode {
+	if powered {
+		zwlr_output_power_v1::Mode::On
+	} else {
+		zwlr_output_power_v1::Mode::Off
+	}
+}
+
+impl GlobalDispatch<ZwlrOutputPowerManagerV1, ()> for State {
+	fn bind(
+		_state: &mut Self,
+		_handle: &DisplayHandle,
+		_client: &Client,
+		resource: New<ZwlrOutputPowerManagerV1>,
+		_global_data: &(),
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		data_init.init(resource, ());
+	}
+}
+
+impl Dispatch<ZwlrOutputPowerManagerV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		_manager: &ZwlrOutputPowerManagerV1,
+		request: zwlr_output_power_manager_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_output_power_manager_v1::Request::GetOutputPower { id, output } => {
+				let power = data_init.init(id, ());
+
+				// the output might have already been removed
+				let output = Output::from_resource(&output)
+					.filter(|output| state.mayland.output_state.contains_key(output));
+				let Some(output) = output else {
+					power.failed();
+					return;
+				};
+
+				power.mode(mode(state.mayland.output_state[&output].powered));
+				state.mayland.output_power_state.powers.insert(power, output);
+			}
+			zwlr_output_power_manager_v1::Request::Destroy => {}
+			_ => unreachable!(),
+		}
+	}
+}
+
+impl Dispatch<ZwlrOutputPowerV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		power: &ZwlrOutputPowerV1,
+		request: zwlr_output_power_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_output_power_v1::Request::SetMode { mode } => {
+				// the power object has already failed
+				let Some(output) = state.mayland.output_power_state.powers.get(power).cloned() else {
+					return;
+				};
+
+				let powered = match mode {
+					WEnum::Value(zwlr_output_power_v1::Mode::On) => true,
+					WEnum::Value(zwlr_output_power_v1::Mode::Off) => false,
+					_ => {
+						power.post_error(zwlr_output_power_v1::Error::InvalidMode, "invalid power mode");
+						return;
+					}
+				};
+
+				state.set_output_power(&output, powered);
+			}
+			zwlr_output_power_v1::Request::Destroy => {}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, power: &ZwlrOutputPowerV1, _data: &()) {
+		state.mayland.output_power_state.powers.remove(power);
+	}
+}
@@ -0,0 +1,419 @@
+//! wlr-screencopy-unstable-v1, which lets clients like grim or wf-recorder capture outputs
+
+use crate::{
+	backend::Backend,
+	render::MaylandRenderElements,
+	state::{Mayland, State},
+	utils::output_size,
+};
+use smithay::{
+	backend::{
+		allocator::{Buffer as _, Fourcc},
+		renderer::{
+			Bind, ExportMem, Offscreen, TextureMapping,
+			damage::OutputDamageTracker,
+			element::utils::{Relocate, RelocateRenderElement},
+			gles::GlesRenderbuffer,
+			glow::GlowRenderer,
+		},
+	},
+	output::Output,
+	reexports::{
+		wayland_protocols_wlr::screencopy::v1::server::{
+			zwlr_screencopy_frame_v1::{self, ZwlrScreencopyFrameV1},
+			zwlr_screencopy_manager_v1::{self, ZwlrScreencopyManagerV1},
+		},
+		wayland_server::{
+			Client, DataInit, Dispatch, DisplayHandle, GlobalDispatch, New, Resource,
+			backend::ClientId,
+			protocol::{wl_buffer::WlBuffer, wl_output::WlOutput, wl_shm},
+		},
+	},
+	utils::{Buffer, Logical, Physical, Point, Rectangle, Size, Transform},
+	wayland::{
+		dmabuf::get_dmabuf,
+		shm::{with_buffer_contents, with_buffer_contents_mut},
+	},
+};
+use std::{collections::HashMap, time::Duration};
+
+const VERSION: u32 = 3;
+
+/// the only format that is offered for both shm and dmabuf buffers
+const FORMAT: Fourcc = Fourcc::Xrgb8888;
+
+#[derive(Debug)]
+pub struct ScreencopyState {
+	frames: HashMap<ZwlrScreencopyFrameV1, Frame>,
+	/// the damage since the last copy of a client, for `copy_with_damage`
+	damage: HashMap<(ZwlrScreencopyManagerV1, Output), (Rectangle<i32, Physical>, OutputDamageTracker)>,
+}
+
+#[derive(Debug)]
+struct Frame {
+	manager: ZwlrScreencopyManagerV1,
+	output: Output,
+	/// the captured region in the physical coordinates of the output
+	region: Rectangle<i32, Physical>,
+	overlay_cursor: bool,
+	copy: Option<PendingCopy>,
+}
+
+#[derive(Debug)]
+struct PendingCopy {
+	buffer: WlBuffer,
+	with_damage: bool,
+}
+
+impl Frame {
+	/// the size of the buffer, which isn't transformed yet
+	fn buffer_size(&self) -> Size<i32, Physical> {
+		self.output.current_transform().transform_size(self.region.size)
+	}
+}
+
+impl ScreencopyState {
+	pub fn new(display_handle: &DisplayHandle) -> Self {
+		display_handle.create_global::<State, ZwlrScreencopyManagerV1, _>(VERSION, ());
+
+		ScreencopyState {
+			frames: HashMap::new(),
+			damage: HashMap::new(),
+		}
+	}
+}
+
+impl GlobalDispatch<ZwlrScreencopyManagerV1, ()> for State {
+	fn bind(
+		_state: &mut Self,
+		_handle: &DisplayHandle,
+		_client: &Client,
+		resource: New<ZwlrScreencopyManagerV1>,
+		_global_data: &(),
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		data_init.init(resource, ());
+	}
+}
+
+impl Dispatch<ZwlrScreencopyManagerV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		manager: &ZwlrScreencopyManagerV1,
+		request: zwlr_screencopy_manager_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		data_init: &mut DataInit<'_, Self>,
+	) {
+		match request {
+			zwlr_screencopy_manager_v1::Request::CaptureOutput {
+				frame,
+				overlay_cursor,
+				output,
+			} => {
+				let frame = data_init.init(frame, ());
+				state.capture_output(manager, frame, overlay_cursor != 0, &output, None);
+			}
+			zwlr_screencopy_manager_v1::Request::CaptureOutputRegion {
+				frame,
+				overlay_cursor,
+				output,
+				x,
+				y,
+				width,
+				height,
+			} => {
+				let frame = data_init.init(frame, ());
+				let region = Rectangle::new(Point::new(x, y), Size::new(width, height));
+				state.capture_output(manager, frame, overlay_cursor != 0, &output, Some(region));
+			}
+			zwlr_screencopy_manager_v1::Request::Destroy => {}
+			_ => unreachable!(),
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, manager: &ZwlrScreencopyManagerV1, _data: &()) {
+		let screencopy_state = &mut state.mayland.screencopy_state;
+		screencopy_state.damage.retain(|(m, _), _| m != manager);
+	}
+}
+
+impl Dispatch<ZwlrScreencopyFrameV1, ()> for State {
+	fn request(
+		state: &mut Self,
+		_client: &Client,
+		frame: &ZwlrScreencopyFrameV1,
+		request: zwlr_screencopy_frame_v1::Request,
+		_data: &(),
+		_dh: &DisplayHandle,
+		_data_init: &mut DataInit<'_, Self>,
+	) {
+		let (buffer, with_damage) = match request {
+			zwlr_screencopy_frame_v1::Request::Copy { buffer } => (buffer, false),
+			zwlr_screencopy_frame_v1::Request::CopyWithDamage { buffer } => (buffer, true),
+			zwlr_screencopy_frame_v1::Request::Destroy => return,
+			_ => unreachable!(),
+		};
+
+		// the frame has already failed
+		let Some(info) = state.mayland.screencopy_state.frames.get_mut(frame) else {
+			return;
+		};
+
+		if info.copy.is_some() {
+			frame.post_error(
+				zwlr_screencopy_frame_v1::Error::AlreadyUsed,
+				"frame was already used",
+			);
+			return;
+		}
+
+		if !is_valid_buffer(&buffer, info.buffer_size()) {
+			frame.post_error(zwlr_screencopy_frame_v1::Error::InvalidBuffer, "invalid buffer");
+			return;
+		}
+
+		info.copy = Some(PendingCopy { buffer, with_damage });
+
+		// the copy happens the next time the output is redrawn,
+		// but outputs that are turned off don't get redrawn
+		let output = info.output.clone();
+		if state.mayland.output_state[&output].powered {
+			state.mayland.queue_redraw(output);
+		} else {
+			state.mayland.render_screencopy(&mut state.backend, &output);
+		}
+	}
+
+	fn destroyed(state: &mut Self, _client: ClientId, frame: &ZwlrScreencopyFrameV1, _data: &()) {
+		state.mayland.screencopy_state.frames.remove(frame);
+	}
+}
+
+impl State {
+	fn capture_output(
+		&mut self,
+		manager: &ZwlrScreencopyManagerV1,
+		frame: ZwlrScreencopyFrameV1,
+		overlay_cursor: bool,
+		output: &WlOutput,
+		region: Option<Rectangle<i32, Logical>>,
+	) {
+		// the output might have already been removed
+		let output =
+			Output::from_resource(output).filter(|output| self.mayland.output_state.contains_key(output));
+		let Some(output) = output else {
+			frame.failed();
+			return;
+		};
+
+		let output_area = Rectangle::from_size(output_size(&output));
+		let region = match region {
+			Some(region) => region.intersection(output_area),
+			None => Some(output_area),
+		};
+		let Some(region) = region.filter(|region| !region.is_empty()) else {
+			frame.failed();
+			return;
+		};
+
+		let scale = output.current_scale().fractional_scale();
+		let info = Frame {
+			manager: manager.clone(),
+			output,
+			region: region.to_physical_precise_round(scale),
+			overlay_cursor,
+			copy: None,
+		};
+
+		let size = info.buffer_size();
+		let (w, h) = (size.w as u32, size.h as u32);
+		frame.buffer(wl_shm::Format::Xrgb8888, w, h, w * 4);
+
+		if frame.version() >= 3 {
+			// dmabufs can only be rendered to with a renderer
+			if self.backend.renderer().is_some() {
+				frame.linux_dmabuf(FORMAT as u32, w, h);
+			}
+			frame.buffer_done();
+		}
+
+		self.mayland.screencopy_state.frames.insert(frame, info);
+	}
+}
+
+impl Mayland {
+	/// copy the [`Output`] into the buffers of all pending screencopy frames
+	///
+	/// frames that were copied with damage stay pending until the output is damaged
+	pub fn render_screencopy(&mut self, backend: &mut Backend, output: &Output) {
+		let frames = (self.screencopy_state.frames.iter())
+			.filter(|(_, info)| info.output == *output && info.copy.is_some())
+			.map(|(frame, _)| frame.clone())
+			.collect::<Vec<_>>();
+
+		if frames.is_empty() {
+			return;
+		}
+
+		let Some(renderer) = backend.renderer() else {
+			for frame in frames {
+				self.screencopy_state.frames.remove(&frame);
+				frame.failed();
+			}
+			return;
+		};
+
+		let scale = output.current_scale().fractional_scale();
+		let transform = output.current_transform();
+
+		for frame in frames {
+			let info = &self.screencopy_state.frames[&frame];
+			let (manager, region, overlay_cursor) = (info.manager.clone(), info.region, info.overlay_cursor);
+			let size = info.buffer_size();
+
+			let elements = self.elements(renderer, output, overlay_cursor);
+			let elements = (elements.into_iter())
+				.map(|element| {
+					let offset = Point::new(-region.loc.x, -region.loc.y);
+					RelocateRenderElement::from_element(element, offset, Relocate::Relative)
+				})
+				.collect::<Vec<_>>();
+
+			let (tracked, damage_tracker) = (self.screencopy_state.damage)
+				.entry((manager, output.clone()))
+				.or_insert_with(|| (region, OutputDamageTracker::new(size, scale, transform)));
+
+			// the damage of another region doesn't apply
+			if *tracked != region {
+				*tracked = region;
+				*damage_tracker = OutputDamageTracker::new(size, scale, transform);
+			}
+
+			let damage = match damage_tracker.damage_output(1, &elements) {
+				Ok((damage, _)) => damage.map(|damage| damage.to_vec()).unwrap_or_default(),
+				Err(err) => {
+					tracing::warn!("failed to compute screencopy damage: {:?}", err);
+					vec![Rectangle::from_size(region.size)]
+				}
+			};
+
+			let info = self.screencopy_state.frames.get_mut(&frame).unwrap();
+			let copy = info.copy.take().unwrap();
+			if copy.with_damage && damage.is_empty() {
+				info.copy = Some(copy);
+				continue;
+			}
+
+			self.screencopy_state.frames.remove(&frame);
+
+			match render_to_buffer(renderer, &copy.buffer, size, scale, transform, &elements) {
+				Ok(flipped) => {
+					let flags = if flipped {
+						zwlr_screencopy_frame_v1::Flags::YInvert
+					} else {
+						zwlr_screencopy_frame_v1::Flags::empty()
+					};
+					frame.flags(flags);
+
+					if copy.with_damage {
+						let area = region.size.to_logical(1);
+						for damage in damage {
+							let damage = damage.to_logical(1).to_buffer(1, transform, &area);
+							let (x, y) = (damage.loc.x as u32, damage.loc.y as u32);
+							frame.damage(x, y, damage.size.w as u32, damage.size.h as u32);
+						}
+					}
+
+					let time = Duration::from(self.clock.now());
+					let secs = time.as_secs();
+					frame.ready((secs >> 32) as u32, secs as u32, time.subsec_nanos());
+				}
+				Err(err) => {
+					tracing::warn!("failed to copy output: {:?}", err);
+					frame.failed();
+				}
+			}
+		}
+	}
+
+	/// forget about the screencopy frames of a removed [`Output`]
+	pub fn screencopy_output_removed(&mut self, output: &Output) {
+		self.screencopy_state.frames.retain(|frame, info| {
+			if info.output == *output {
+				frame.failed();
+				false
+			} else {
+				true
+			}
+		});
+
+		self.screencopy_state.damage.retain(|(_, o), _| o != output);
+	}
+}
+
+/// is the buffer big enough and in the right format?
+fn is_valid_buffer(buffer: &WlBuffer, size: Size<i32, Physical>) -> bool {
+	if let Ok(dmabuf) = get_dmabuf(buffer) {
+		return dmabuf.size() == Size::new(size.w, size.h) && dmabuf.format().code == FORMAT;
+	}
+
+	with_buffer_contents(buffer, |_, _, data| {
+		data.format == wl_shm::Format::Xrgb8888
+			&& data.width == size.w
+			&& data.height == size.h
+			&& data.stride == size.w * 4
+	})
+	.unwrap_or(false)
+}
+
+/// render the elements into a shm or dmabuf buffer
+///
+/// returns if the buffer contents are flipped vertically
+fn render_to_buffer(
+	renderer: &mut GlowRenderer,
+	buffer: &WlBuffer,
+	size: Size<i32, Physical>,
+	scale: f64,
+	transform: Transform,
+	elements: &[RelocateRenderElement<MaylandRenderElements>],
+) -> Result<bool, Box<dyn std::error::Error>> {
+	// render everything, as the previous contents of the buffer are unknown
+	let mut damage_tracker = OutputDamageTracker::new(size, scale, transform);
+
+	if let Ok(dmabuf) = get_dmabuf(buffer) {
+		let mut dmabuf = dmabuf.clone();
+		let mut framebuffer = renderer.bind(&mut dmabuf)?;
+
+		let result =
+			damage_tracker.render_output(renderer, &mut framebuffer, 0, elements, [0., 0., 0., 1.])?;
+		(result.sync.wait()).map_err(|_| "interrupted while waiting for rendering")?;
+
+		return Ok(false);
+	}
+
+	let buffer_size = Size::<i32, Buffer>::new(size.w, size.h);
+	let mut renderbuffer: GlesRenderbuffer = renderer.create_buffer(FORMAT, buffer_size)?;
+	let mut framebuffer = renderer.bind(&mut renderbuffer)?;
+
+	damage_tracker.render_output(renderer, &mut framebuffer, 0, elements, [0., 0., 0., 1.])?;
+
+	let mapping = renderer.copy_framebuffer(&framebuffer, Rectangle::from_size(buffer_size), FORMAT)?;
+	let flipped = mapping.flipped();
+	let pixels = renderer.map_texture(&mapping)?;
+
+	with_buffer_contents_mut(buffer, |ptr, len, data| {
+		let offset = data.offset as usize;
+		if offset + pixels.len() > len {
+			return Err("shm buffer is too small");
+		}
+
+		// SAFETY: the shm pool is at least `offset + pixels.len()` bytes long,
+		// and the mapped pixels are a separate allocation
+		unsafe { std::ptr::copy_nonoverlapping(pixels.as_ptr(), ptr.add(offset), pixels.len()) };
+		Ok(())
+	})??;
+
+	Ok(flipped)
+}
@@ -18,6 +18,7 @@ impl FocusRing {
 		mut area: Rectangle<i32, Logical>,
 		color: Color,
 		thickness: u8,
+		scale: f64,
 	) -> PixelShaderElement {
 		let t = i32::from(thickness);
 		area.loc -= Point::new(t, t);
@@ -31,7 +32,8 @@ impl FocusRing {
 			1.0,
 			vec![
 				Uniform::new("color", color.as_f32s()),
-				Uniform::new("thickness", f32::from(thickness)),
+				// the shader works in physical pixels
+				Uniform::new("thickness", f32::from(thickness) * scale as f32),
 			],
 			Kind::Unspecified,
 		)
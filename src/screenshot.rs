@@ -0,0 +1,272 @@
+//! built-in screenshots, which are rendered offscreen and saved as png
+
+use crate::{
+	render::MaylandRenderElements,
+	shell::focus::KeyboardFocusTarget,
+	state::{Mayland, State},
+	utils::output_size,
+};
+use mayland_config::bind::ScreenshotTarget;
+use smithay::{
+	backend::{
+		allocator::Fourcc,
+		renderer::{
+			Bind, ExportMem, Offscreen, TextureMapping,
+			damage::OutputDamageTracker,
+			element::{
+				Kind,
+				solid::{SolidColorBuffer, SolidColorRenderElement},
+				utils::{Relocate, RelocateRenderElement},
+			},
+			gles::GlesRenderbuffer,
+			glow::GlowRenderer,
+		},
+	},
+	output::Output,
+	utils::{Buffer, Logical, Physical, Point, Rectangle, Size, Transform},
+};
+use std::{
+	fs::File,
+	io::BufWriter,
+	path::{Path, PathBuf},
+};
+
+/// the format the screenshot is rendered in, which is rgba in memory
+const FORMAT: Fourcc = Fourcc::Abgr8888;
+
+/// the color of the region that is being selected
+const SELECTION_COLOR: [f32; 4] = [0.1, 0.1, 0.15, 0.3];
+
+/// a rendered screenshot with 8-bit rgba pixels
+#[derive(Debug)]
+pub struct Screenshot {
+	pixels: Vec<u8>,
+	size: Size<i32, Physical>,
+}
+
+impl Screenshot {
+	/// encode the screenshot as png and write it to the path
+	fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+
+		let file = BufWriter::new(File::create(path)?);
+		let mut encoder = png::Encoder::new(file, self.size.w as u32, self.size.h as u32);
+		encoder.set_color(png::ColorType::Rgba);
+		encoder.set_depth(png::BitDepth::Eight);
+
+		let mut writer = encoder.write_header()?;
+		writer.write_image_data(&self.pixels)?;
+		writer.finish()?;
+
+		Ok(())
+	}
+
+	/// save the screenshot on a separate thread, as encoding a
+	/// png takes long enough to otherwise stall the compositor
+	pub fn save_in_background(self, path: PathBuf) -> async_channel::Receiver<Result<(), String>> {
+		let (tx, rx) = async_channel::bounded(1);
+
+		std::thread::spawn(move || {
+			let ret = self.save(&path).map_err(|err| err.to_string());
+			match &ret {
+				Ok(()) => tracing::info!("saved screenshot to {}", path.display()),
+				Err(err) => tracing::warn!("failed to save screenshot to {}: {}", path.display(), err),
+			}
+
+			let _ = tx.send_blocking(ret);
+		});
+
+		rx
+	}
+}
+
+/// the region that is currently being selected for a screenshot
+#[derive(Debug)]
+pub struct Selection {
+	pub output: Output,
+	/// the selected region in the logical coordinates of the output
+	region: Rectangle<i32, Logical>,
+	buffer: SolidColorBuffer,
+}
+
+impl Selection {
+	pub fn new(output: Output, region: Rectangle<i32, Logical>) -> Self {
+		let buffer = SolidColorBuffer::new(region.size, SELECTION_COLOR);
+		Selection {
+			output,
+			region,
+			buffer,
+		}
+	}
+
+	pub fn region(&self) -> Rectangle<i32, Logical> {
+		self.region
+	}
+
+	pub fn set_region(&mut self, region: Rectangle<i32, Logical>) {
+		self.region = region;
+		self.buffer.resize(region.size);
+	}
+
+	pub fn element(&self) -> MaylandRenderElements {
+		let scale = self.output.current_scale().fractional_scale();
+		let location: Point<i32, Physical> = self.region.loc.to_physical_precise_round(scale);
+
+		MaylandRenderElements::Solid(SolidColorRenderElement::from_buffer(
+			&self.buffer,
+			location,
+			scale,
+			1.0,
+			Kind::Unspecified,
+		))
+	}
+}
+
+/// the file name of a screenshot taken right now
+pub fn screenshot_name() -> String {
+	let now = jiff::Zoned::now();
+	now.strftime("screenshot_%Y-%m-%d_%H-%M-%S.png").to_string()
+}
+
+impl Mayland {
+	/// render the region of the [`Output`] offscreen, without the cursor
+	///
+	/// the region is in the logical coordinates of the output
+	fn render_screenshot(
+		&mut self,
+		renderer: &mut GlowRenderer,
+		output: &Output,
+		region: Rectangle<i32, Logical>,
+	) -> Result<Screenshot, Box<dyn std::error::Error>> {
+		let scale = output.current_scale().fractional_scale();
+		let region: Rectangle<i32, Physical> = region.to_physical_precise_round(scale);
+		if region.is_empty() {
+			return Err("the region is empty".into());
+		}
+
+		let elements = self.elements(renderer, output, false);
+		let elements = (elements.into_iter())
+			.map(|element| {
+				let offset = Point::new(-region.loc.x, -region.loc.y);
+				RelocateRenderElement::from_element(element, offset, Relocate::Relative)
+			})
+			.collect::<Vec<_>>();
+
+		let buffer_size = Size::<i32, Buffer>::new(region.size.w, region.size.h);
+		let mut renderbuffer: GlesRenderbuffer = renderer.create_buffer(FORMAT, buffer_size)?;
+		let mut framebuffer = renderer.bind(&mut renderbuffer)?;
+
+		// the elements are already upright, so the output transform doesn't apply
+		let mut damage_tracker = OutputDamageTracker::new(region.size, scale, Transform::Normal);
+		damage_tracker.render_output(renderer, &mut framebuffer, 0, &elements, [0., 0., 0., 1.])?;
+
+		let mapping = renderer.copy_framebuffer(&framebuffer, Rectangle::from_size(buffer_size), FORMAT)?;
+		let flipped = mapping.flipped();
+		let pixels = renderer.map_texture(&mapping)?;
+
+		let stride = region.size.w as usize * 4;
+		let pixels = if flipped {
+			(pixels.chunks_exact(stride).rev()).flatten().copied().collect()
+		} else {
+			pixels.to_vec()
+		};
+
+		Ok(Screenshot {
+			pixels,
+			size: region.size,
+		})
+	}
+}
+
+impl State {
+	/// take a screenshot of the target and save it to the screenshot directory
+	pub fn screenshot(&mut self, target: ScreenshotTarget) {
+		match target {
+			ScreenshotTarget::Output => {
+				let Some(output) = self.mayland.workspaces.active_output().cloned() else {
+					return;
+				};
+
+				let region = Rectangle::from_size(output_size(&output));
+				self.save_screenshot(&output, region);
+			}
+			ScreenshotTarget::Window => {
+				let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
+					return;
+				};
+
+				let Some((output, geometry)) = (self.mayland.workspaces.workspaces()).find_map(|workspace| {
+					let output = workspace.output.clone()?;
+					let geometry = workspace.window_geometry(&window)?;
+					Some((output, geometry))
+				}) else {
+					return;
+				};
+
+				// only the part of the window that is on the output is rendered
+				let Some(region) = geometry.intersection(Rectangle::from_size(output_size(&output))) else {
+					return;
+				};
+
+				self.save_screenshot(&output, region);
+			}
+			ScreenshotTarget::Region => {
+				if !self.mayland.is_locked() {
+					self.screenshot_grab();
+				}
+			}
+		}
+	}
+
+	/// take a screenshot of the output with the name, or the active output
+	pub fn screenshot_output(&mut self, name: Option<&str>) -> Result<Screenshot, mayland_comm::Error> {
+		let output = match name {
+			Some(name) => (self.mayland.workspaces.output_by_name(name).cloned())
+				.ok_or_else(|| mayland_comm::Error::OutputNotFound(name.to_owned()))?,
+			None => (self.mayland.workspaces.active_output().cloned())
+				.ok_or_else(|| mayland_comm::Error::ScreenshotFailed("no output is connected".to_owned()))?,
+		};
+
+		let region = Rectangle::from_size(output_size(&output));
+		self.render_screenshot(&output, region)
+	}
+
+	/// take a screenshot of the region of the [`Output`] and save it to the screenshot directory
+	pub fn save_screenshot(&mut self, output: &Output, region: Rectangle<i32, Logical>) {
+		let screenshot = match self.render_screenshot(output, region) {
+			Ok(screenshot) => screenshot,
+			Err(err) => {
+				tracing::warn!("failed to take screenshot: {}", err);
+				return;
+			}
+		};
+
+		let mut path = self.mayland.config.screenshot.directory();
+		path.push(screenshot_name());
+
+		// errors are already logged
+		let _ = screenshot.save_in_background(path);
+	}
+
+	/// render the region of the [`Output`] into a [`Screenshot`]
+	pub fn render_screenshot(
+		&mut self,
+		output: &Output,
+		region: Rectangle<i32, Logical>,
+	) -> Result<Screenshot, mayland_comm::Error> {
+		if !self.mayland.output_state.contains_key(output) {
+			let err = format!("output {:?} was removed", output.name());
+			return Err(mayland_comm::Error::ScreenshotFailed(err));
+		}
+
+		let Some(renderer) = self.backend.renderer() else {
+			let err = "the backend has no renderer".to_owned();
+			return Err(mayland_comm::Error::ScreenshotFailed(err));
+		};
+
+		(self.mayland.render_screenshot(renderer, output, region))
+			.map_err(|err| mayland_comm::Error::ScreenshotFailed(err.to_string()))
+	}
+}
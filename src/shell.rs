@@ -11,20 +11,25 @@ use smithay::{
 	wayland::{
 		buffer::BufferHandler,
 		compositor::{
-			CompositorClientState, CompositorHandler, CompositorState, get_parent, is_sync_subsurface,
+			CompositorClientState, CompositorHandler, CompositorState, SurfaceAttributes, get_parent,
+			is_sync_subsurface, with_states,
 		},
-		shell::wlr_layer::{
-			Layer, LayerSurface as WlrLayerSurface, WlrLayerShellHandler, WlrLayerShellState,
+		shell::{
+			wlr_layer::{Layer, LayerSurface as WlrLayerSurface, WlrLayerShellHandler, WlrLayerShellState},
+			xdg::PopupSurface,
 		},
 		shm::{ShmHandler, ShmState},
 	},
+	xwayland::XWaylandClientData,
 };
 
 pub mod focus;
 pub mod grab;
+pub mod session_lock;
 pub mod window;
 pub mod wlr;
 pub mod xdg;
+pub mod xwayland;
 
 impl BufferHandler for State {
 	fn buffer_destroyed(&mut self, _buffer: &wl_buffer::WlBuffer) {}
@@ -40,6 +45,10 @@ impl CompositorHandler for State {
 			return &state.compositor_state;
 		}
 
+		if let Some(state) = client.get_data::<XWaylandClientData>() {
+			return &state.compositor_state;
+		}
+
 		panic!("unknown client data type")
 	}
 
@@ -65,6 +74,17 @@ impl CompositorHandler for State {
 		// handle wlr layer shell surface commits
 		self.mayland.handle_layer_surface_commit(surface);
 
+		// the dnd icon can be moved with `wl_surface.offset`
+		if let Some(icon) = &mut self.mayland.dnd_icon
+			&& icon.surface == *surface
+		{
+			let delta = with_states(surface, |states| {
+				let mut attributes = states.cached_state.get::<SurfaceAttributes>();
+				attributes.current().buffer_delta.take()
+			});
+			icon.offset += delta.unwrap_or_default();
+		}
+
 		self.mayland.queue_redraw_all();
 	}
 }
@@ -110,6 +130,11 @@ impl WlrLayerShellHandler for State {
 
 		self.reset_focus();
 	}
+
+	fn new_popup(&mut self, _parent: WlrLayerSurface, popup: PopupSurface) {
+		// the parent of the popup is only known now
+		self.unconstrain_popup(&popup);
+	}
 }
 
 impl ShmHandler for State {
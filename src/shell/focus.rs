@@ -25,6 +25,7 @@ pub enum KeyboardFocusTarget {
 	Window(MappedWindow),
 	LayerSurface(LayerSurface),
 	Popup(PopupKind),
+	LockSurface(WlSurface),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -39,6 +40,7 @@ impl IsAlive for KeyboardFocusTarget {
 			KeyboardFocusTarget::Window(w) => w.alive(),
 			KeyboardFocusTarget::LayerSurface(l) => l.alive(),
 			KeyboardFocusTarget::Popup(p) => p.alive(),
+			KeyboardFocusTarget::LockSurface(s) => s.alive(),
 		}
 	}
 }
@@ -59,6 +61,7 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 				WindowSurface::Wayland(w) => {
 					KeyboardTarget::enter(w.wl_surface(), seat, data, keys, serial);
 				}
+				WindowSurface::X11(w) => KeyboardTarget::enter(w, seat, data, keys, serial),
 			},
 			KeyboardFocusTarget::LayerSurface(l) => {
 				KeyboardTarget::enter(l.wl_surface(), seat, data, keys, serial);
@@ -66,6 +69,9 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 			KeyboardFocusTarget::Popup(p) => {
 				KeyboardTarget::enter(p.wl_surface(), seat, data, keys, serial);
 			}
+			KeyboardFocusTarget::LockSurface(s) => {
+				KeyboardTarget::enter(s, seat, data, keys, serial);
+			}
 		}
 	}
 
@@ -75,6 +81,7 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 				WindowSurface::Wayland(w) => {
 					KeyboardTarget::leave(w.wl_surface(), seat, data, serial);
 				}
+				WindowSurface::X11(w) => KeyboardTarget::leave(w, seat, data, serial),
 			},
 			KeyboardFocusTarget::LayerSurface(l) => {
 				KeyboardTarget::leave(l.wl_surface(), seat, data, serial);
@@ -82,6 +89,9 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 			KeyboardFocusTarget::Popup(p) => {
 				KeyboardTarget::leave(p.wl_surface(), seat, data, serial);
 			}
+			KeyboardFocusTarget::LockSurface(s) => {
+				KeyboardTarget::leave(s, seat, data, serial);
+			}
 		}
 	}
 
@@ -99,6 +109,7 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 				WindowSurface::Wayland(w) => {
 					KeyboardTarget::key(w.wl_surface(), seat, data, key, state, serial, time);
 				}
+				WindowSurface::X11(w) => KeyboardTarget::key(w, seat, data, key, state, serial, time),
 			},
 			KeyboardFocusTarget::LayerSurface(l) => {
 				KeyboardTarget::key(l.wl_surface(), seat, data, key, state, serial, time);
@@ -106,6 +117,9 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 			KeyboardFocusTarget::Popup(p) => {
 				KeyboardTarget::key(p.wl_surface(), seat, data, key, state, serial, time);
 			}
+			KeyboardFocusTarget::LockSurface(s) => {
+				KeyboardTarget::key(s, seat, data, key, state, serial, time);
+			}
 		}
 	}
 
@@ -115,6 +129,7 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 				WindowSurface::Wayland(w) => {
 					KeyboardTarget::modifiers(w.wl_surface(), seat, data, modifiers, serial);
 				}
+				WindowSurface::X11(w) => KeyboardTarget::modifiers(w, seat, data, modifiers, serial),
 			},
 			KeyboardFocusTarget::LayerSurface(l) => {
 				KeyboardTarget::modifiers(l.wl_surface(), seat, data, modifiers, serial);
@@ -122,6 +137,9 @@ impl KeyboardTarget<State> for KeyboardFocusTarget {
 			KeyboardFocusTarget::Popup(p) => {
 				KeyboardTarget::modifiers(p.wl_surface(), seat, data, modifiers, serial);
 			}
+			KeyboardFocusTarget::LockSurface(s) => {
+				KeyboardTarget::modifiers(s, seat, data, modifiers, serial);
+			}
 		}
 	}
 }
@@ -335,6 +353,7 @@ impl WaylandFocus for KeyboardFocusTarget {
 			KeyboardFocusTarget::Window(window) => window.wl_surface(),
 			KeyboardFocusTarget::LayerSurface(layer) => Some(Cow::Borrowed(layer.wl_surface())),
 			KeyboardFocusTarget::Popup(popup) => Some(Cow::Borrowed(popup.wl_surface())),
+			KeyboardFocusTarget::LockSurface(surface) => Some(Cow::Borrowed(surface)),
 		}
 	}
 
@@ -343,6 +362,7 @@ impl WaylandFocus for KeyboardFocusTarget {
 			KeyboardFocusTarget::Window(window) => window.same_client_as(object_id),
 			KeyboardFocusTarget::LayerSurface(layer) => layer.wl_surface().id().same_client_as(object_id),
 			KeyboardFocusTarget::Popup(popup) => popup.wl_surface().id().same_client_as(object_id),
+			KeyboardFocusTarget::LockSurface(surface) => surface.id().same_client_as(object_id),
 		}
 	}
 }
@@ -369,6 +389,19 @@ impl From<PopupKind> for KeyboardFocusTarget {
 	}
 }
 
+impl From<KeyboardFocusTarget> for PointerFocusTarget {
+	fn from(target: KeyboardFocusTarget) -> Self {
+		match target {
+			KeyboardFocusTarget::Window(window) => PointerFocusTarget::Window(window),
+			KeyboardFocusTarget::LayerSurface(layer) => {
+				PointerFocusTarget::WlSurface(layer.wl_surface().clone())
+			}
+			KeyboardFocusTarget::Popup(popup) => PointerFocusTarget::WlSurface(popup.wl_surface().clone()),
+			KeyboardFocusTarget::LockSurface(surface) => PointerFocusTarget::WlSurface(surface),
+		}
+	}
+}
+
 impl PartialEq<MappedWindow> for KeyboardFocusTarget {
 	fn eq(&self, other: &MappedWindow) -> bool {
 		if let KeyboardFocusTarget::Window(window) = self {
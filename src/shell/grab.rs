@@ -2,10 +2,15 @@ use super::window::MappedWindow;
 use crate::state::{Mayland, State};
 use smithay::{
 	desktop::space::SpaceElement,
-	utils::{Logical, Point, Serial, Size},
+	input::pointer::GrabStartData,
+	reexports::wayland_server::Resource,
+	utils::{Logical, Point, SERIAL_COUNTER, Serial, Size},
+	wayland::seat::WaylandFocus,
 };
 
 mod floating;
+mod screenshot;
+mod tiling;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ResizeData {
@@ -61,10 +66,14 @@ impl State {
 			return;
 		};
 
-		if workspace.is_floating(&window) {
-			self.xdg_floating_move(window, serial);
-		} else {
-			tracing::debug!("todo! tiling move");
+		let is_floating = workspace.is_floating(&window);
+		let is_touch = self.mayland.touch.has_grab(serial);
+
+		match (is_floating, is_touch) {
+			(true, false) => self.xdg_floating_move(window, serial),
+			(true, true) => self.xdg_floating_touch_move(window, serial),
+			(false, false) => self.xdg_tiling_move(window, serial),
+			(false, true) => self.xdg_tiling_touch_move(window, serial),
 		}
 	}
 
@@ -75,10 +84,59 @@ impl State {
 
 		if workspace.is_floating(&window) {
 			self.xdg_floating_resize(window, serial);
+		} else if workspace.has_split() {
+			self.xdg_tiling_resize(window, serial);
+		}
+	}
+
+	/// move the x11 window with the pointer, if it holds a button pressed on it
+	pub fn x11_move(&mut self, window: MappedWindow) {
+		let Some(start_data) = self.x11_grab_start_data(&window) else {
+			return;
+		};
+		let Some(workspace) = self.mayland.workspaces.workspace() else {
+			return;
+		};
+
+		let serial = SERIAL_COUNTER.next_serial();
+		if workspace.is_floating(&window) {
+			self.floating_move_grab(window, start_data, serial);
 		} else {
-			tracing::debug!("todo! tiling resize");
+			self.tiling_move_grab(window, start_data, serial);
+		}
+	}
+
+	/// resize the x11 window with the pointer, if it holds a button pressed on it
+	pub fn x11_resize(&mut self, window: MappedWindow) {
+		let Some(start_data) = self.x11_grab_start_data(&window) else {
+			return;
+		};
+		let Some(workspace) = self.mayland.workspaces.workspace() else {
+			return;
+		};
+
+		let serial = SERIAL_COUNTER.next_serial();
+		if workspace.is_floating(&window) {
+			self.floating_resize_grab(window, start_data, serial);
+		} else if workspace.has_split() {
+			self.split_grab(start_data, serial);
 		}
 	}
+
+	/// x11 requests don't come with a serial, so instead of validating the serial
+	/// the implicit grab of the pointer has to be on the window
+	fn x11_grab_start_data(&self, window: &MappedWindow) -> Option<GrabStartData<State>> {
+		let pointer = &self.mayland.pointer;
+		if !pointer.is_grabbed() {
+			return None;
+		}
+
+		let start_data = pointer.grab_start_data()?;
+		let (grab_focus, _) = start_data.focus.as_ref()?;
+		let wl_surface = window.wl_surface()?;
+
+		grab_focus.same_client_as(&wl_surface.id()).then_some(start_data)
+	}
 }
 
 impl Mayland {
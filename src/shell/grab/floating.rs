@@ -10,6 +10,10 @@ use smithay::{
 			GestureSwipeEndEvent, GestureSwipeUpdateEvent, GrabStartData, MotionEvent, PointerGrab,
 			PointerInnerHandle, RelativeMotionEvent,
 		},
+		touch::{
+			DownEvent, GrabStartData as TouchGrabStartData, MotionEvent as TouchMotionEvent,
+			OrientationEvent, ShapeEvent, TouchGrab, TouchInnerHandle, UpEvent,
+		},
 	},
 	reexports::{wayland_protocols::xdg::shell::server::xdg_toplevel, wayland_server::Resource},
 	utils::{IsAlive, Logical, Point, Serial, Size},
@@ -56,7 +60,7 @@ impl PointerGrab<State> for MoveGrab {
 
 	fn button(&mut self, state: &mut State, handle: &mut PointerInnerHandle<'_, State>, event: &ButtonEvent) {
 		handle.button(state, event);
-		if !handle.current_pressed().contains(&272) {
+		if !handle.current_pressed().contains(&self.start_data.button) {
 			handle.unset_grab(self, state, event.serial, event.time, true);
 		}
 	}
@@ -151,6 +155,97 @@ impl PointerGrab<State> for MoveGrab {
 	}
 }
 
+struct TouchMoveGrab {
+	start_data: TouchGrabStartData<State>,
+	window: MappedWindow,
+	window_offset: Point<i32, Logical>,
+}
+
+impl TouchGrab<State> for TouchMoveGrab {
+	fn down(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::TouchFocus, Point<f64, Logical>)>,
+		event: &DownEvent,
+		seq: Serial,
+	) {
+		handle.down(data, None, event, seq);
+	}
+
+	fn up(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		event: &UpEvent,
+		seq: Serial,
+	) {
+		if event.slot != self.start_data.slot {
+			return;
+		}
+
+		handle.up(data, event, seq);
+		handle.unset_grab(self, data);
+	}
+
+	fn motion(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::TouchFocus, Point<f64, Logical>)>,
+		event: &TouchMotionEvent,
+		_seq: Serial,
+	) {
+		if event.slot != self.start_data.slot {
+			return;
+		}
+
+		let new_location = event.location.to_i32_round() + self.window_offset;
+
+		let Some(workspace) = data.mayland.workspaces.workspace_mut() else {
+			handle.unset_grab(self, data);
+			return;
+		};
+
+		workspace.floating_move(self.window.clone(), new_location);
+	}
+
+	fn frame(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>, seq: Serial) {
+		handle.frame(data, seq);
+	}
+
+	fn cancel(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>, seq: Serial) {
+		handle.cancel(data, seq);
+		handle.unset_grab(self, data);
+	}
+
+	fn shape(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		event: &ShapeEvent,
+		seq: Serial,
+	) {
+		handle.shape(data, event, seq);
+	}
+
+	fn orientation(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		event: &OrientationEvent,
+		seq: Serial,
+	) {
+		handle.orientation(data, event, seq);
+	}
+
+	fn start_data(&self) -> &TouchGrabStartData<State> {
+		&self.start_data
+	}
+
+	fn unset(&mut self, _data: &mut State) {}
+}
+
 struct ResizeGrab {
 	start_data: GrabStartData<State>,
 	corner: ResizeCorner,
@@ -193,6 +288,11 @@ impl PointerGrab<State> for ResizeGrab {
 				});
 				xdg.send_pending_configure();
 			}
+			WindowSurface::X11(x11) => {
+				let mut geometry = x11.geometry();
+				geometry.size = self.new_window_size;
+				let _ = x11.configure(geometry);
+			}
 		}
 	}
 
@@ -210,7 +310,7 @@ impl PointerGrab<State> for ResizeGrab {
 	fn button(&mut self, state: &mut State, handle: &mut PointerInnerHandle<'_, State>, event: &ButtonEvent) {
 		handle.button(state, event);
 
-		if !handle.current_pressed().contains(&273) {
+		if !handle.current_pressed().contains(&self.start_data.button) {
 			handle.unset_grab(self, state, event.serial, event.time, true);
 
 			if !self.window.alive() {
@@ -224,12 +324,17 @@ impl PointerGrab<State> for ResizeGrab {
 						state.size = Some(self.new_window_size);
 					});
 					xdg.send_pending_configure();
-
-					let mut guard = self.window.resize_state.lock().unwrap();
-					if let Some(ResizeState::Resizing(data)) = *guard {
-						*guard = Some(ResizeState::WatingForCommit(data));
-					}
 				}
+				WindowSurface::X11(x11) => {
+					let mut geometry = x11.geometry();
+					geometry.size = self.new_window_size;
+					let _ = x11.configure(geometry);
+				}
+			}
+
+			let mut guard = self.window.resize_state.lock().unwrap();
+			if let Some(ResizeState::Resizing(data)) = *guard {
+				*guard = Some(ResizeState::WatingForCommit(data));
 			}
 		}
 	}
@@ -341,6 +446,45 @@ impl State {
 			return;
 		}
 
+		self.floating_move_grab(window, start_data, serial);
+	}
+
+	pub fn xdg_floating_touch_move(&mut self, window: MappedWindow, serial: Serial) {
+		let touch = self.mayland.touch.clone();
+
+		if !touch.has_grab(serial) {
+			return;
+		}
+
+		let start_data = touch.grab_start_data().unwrap();
+		let Some(((grab_focus, _), wl_surface)) = start_data.focus.as_ref().zip(window.wl_surface()) else {
+			return;
+		};
+
+		if !grab_focus.same_client_as(&wl_surface.id()) {
+			return;
+		}
+
+		let workspace = self.mayland.workspaces.workspace().unwrap();
+		let window_geometry = workspace.window_geometry(&window).unwrap();
+		let window_offset = window_geometry.loc - start_data.location.to_i32_round();
+
+		let grab = TouchMoveGrab {
+			start_data,
+			window,
+			window_offset,
+		};
+		touch.set_grab(self, grab, serial);
+	}
+
+	/// move the floating [`MappedWindow`] with the pointer
+	pub fn floating_move_grab(
+		&mut self,
+		window: MappedWindow,
+		start_data: GrabStartData<State>,
+		serial: Serial,
+	) {
+		let pointer = self.mayland.pointer.clone();
 		let pointer_location = pointer.current_location().to_i32_round();
 
 		let workspace = self.mayland.workspaces.workspace().unwrap();
@@ -374,6 +518,18 @@ impl State {
 			return;
 		}
 
+		self.floating_resize_grab(window, start_data, serial);
+	}
+
+	/// resize the floating [`MappedWindow`] from the corner closest to the pointer
+	pub fn floating_resize_grab(
+		&mut self,
+		window: MappedWindow,
+		start_data: GrabStartData<State>,
+		serial: Serial,
+	) {
+		let pointer = self.mayland.pointer.clone();
+
 		let workspace = self.mayland.workspaces.workspace().unwrap();
 		let window_geometry = workspace.window_geometry(&window).unwrap();
 		let pointer_location = pointer.current_location().to_i32_round();
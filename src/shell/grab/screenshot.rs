@@ -0,0 +1,228 @@
+use crate::{screenshot::Selection, state::State};
+use mayland_config::bind::BTN_LEFT;
+use smithay::{
+	backend::input::ButtonState,
+	input::{
+		SeatHandler,
+		pointer::{
+			AxisFrame, ButtonEvent, CursorIcon, Focus, GestureHoldBeginEvent, GestureHoldEndEvent,
+			GesturePinchBeginEvent, GesturePinchEndEvent, GesturePinchUpdateEvent, GestureSwipeBeginEvent,
+			GestureSwipeEndEvent, GestureSwipeUpdateEvent, GrabStartData, MotionEvent, PointerGrab,
+			PointerInnerHandle, RelativeMotionEvent,
+		},
+	},
+	output::Output,
+	utils::{Logical, Point, Rectangle, SERIAL_COUNTER},
+};
+
+/// select a region for a screenshot by dragging with the left mouse button
+struct ScreenshotGrab {
+	start_data: GrabStartData<State>,
+	/// the output and the location the selection was started at
+	anchor: Option<(Output, Point<f64, Logical>)>,
+}
+
+impl ScreenshotGrab {
+	/// update the selected region, which is limited to the output the selection was started on
+	fn update_selection(&self, state: &mut State, location: Point<f64, Logical>) {
+		let Some((output, anchor)) = &self.anchor else {
+			return;
+		};
+
+		let Some(output_geometry) = state.mayland.workspaces.output_geometry(output) else {
+			return;
+		};
+
+		let region = Rectangle::bounding_box([anchor.to_i32_round(), location.to_i32_round()]);
+		let mut region = region.intersection(output_geometry).unwrap_or_default();
+		region.loc -= output_geometry.loc;
+
+		if let Some(selection) = &mut state.mayland.screenshot_selection {
+			selection.set_region(region);
+		}
+
+		state.mayland.queue_redraw(output.clone());
+	}
+}
+
+impl PointerGrab<State> for ScreenshotGrab {
+	fn motion(
+		&mut self,
+		state: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+		event: &MotionEvent,
+	) {
+		// no client has pointer focus while grab is active
+		handle.motion(state, None, event);
+
+		self.update_selection(state, event.location);
+	}
+
+	fn relative_motion(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+		event: &RelativeMotionEvent,
+	) {
+		// no client has pointer focus while grab is active
+		handle.relative_motion(data, None, event);
+	}
+
+	fn button(&mut self, state: &mut State, handle: &mut PointerInnerHandle<'_, State>, event: &ButtonEvent) {
+		handle.button(state, event);
+
+		// every other button cancels the selection
+		if event.button != BTN_LEFT {
+			if event.state == ButtonState::Pressed {
+				handle.unset_grab(self, state, event.serial, event.time, true);
+			}
+			return;
+		}
+
+		match event.state {
+			ButtonState::Pressed => {
+				let location = handle.current_location();
+				let Some(output) = state.mayland.workspaces.output_under(location).cloned() else {
+					return;
+				};
+
+				let selection = Selection::new(output.clone(), Rectangle::default());
+				state.mayland.screenshot_selection = Some(selection);
+
+				self.anchor = Some((output, location));
+				self.update_selection(state, location);
+			}
+			ButtonState::Released => {
+				let Some(selection) = state.mayland.screenshot_selection.take() else {
+					return;
+				};
+
+				// the selection is hidden before taking the screenshot
+				handle.unset_grab(self, state, event.serial, event.time, true);
+
+				// a click without dragging doesn't select anything
+				let region = selection.region();
+				if !region.is_empty() {
+					state.save_screenshot(&selection.output, region);
+				}
+			}
+		}
+	}
+
+	fn axis(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>, details: AxisFrame) {
+		handle.axis(data, details);
+	}
+
+	fn frame(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>) {
+		handle.frame(data);
+	}
+
+	fn gesture_swipe_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeBeginEvent,
+	) {
+		handle.gesture_swipe_begin(data, event);
+	}
+
+	fn gesture_swipe_update(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeUpdateEvent,
+	) {
+		handle.gesture_swipe_update(data, event);
+	}
+
+	fn gesture_swipe_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeEndEvent,
+	) {
+		handle.gesture_swipe_end(data, event);
+	}
+
+	fn gesture_pinch_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchBeginEvent,
+	) {
+		handle.gesture_pinch_begin(data, event);
+	}
+
+	fn gesture_pinch_update(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchUpdateEvent,
+	) {
+		handle.gesture_pinch_update(data, event);
+	}
+
+	fn gesture_pinch_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchEndEvent,
+	) {
+		handle.gesture_pinch_end(data, event);
+	}
+
+	fn gesture_hold_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureHoldBeginEvent,
+	) {
+		handle.gesture_hold_begin(data, event);
+	}
+
+	fn gesture_hold_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureHoldEndEvent,
+	) {
+		handle.gesture_hold_end(data, event);
+	}
+
+	fn start_data(&self) -> &GrabStartData<State> {
+		&self.start_data
+	}
+
+	fn unset(&mut self, state: &mut State) {
+		state.mayland.screenshot_selection = None;
+		state.mayland.cursor.icon = None;
+		state.mayland.queue_redraw_all();
+	}
+}
+
+impl State {
+	/// let the user select a region with the pointer and take a screenshot of it
+	pub fn screenshot_grab(&mut self) {
+		let pointer = self.mayland.pointer.clone();
+		if pointer.is_grabbed() {
+			return;
+		}
+
+		self.mayland.cursor.icon = Some(CursorIcon::Crosshair);
+		self.mayland.queue_redraw_all();
+
+		let start_data = GrabStartData {
+			focus: None,
+			button: BTN_LEFT,
+			location: pointer.current_location(),
+		};
+		let grab = ScreenshotGrab {
+			start_data,
+			anchor: None,
+		};
+
+		pointer.set_grab(self, grab, SERIAL_COUNTER.next_serial(), Focus::Clear);
+	}
+}
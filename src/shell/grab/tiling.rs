@@ -0,0 +1,453 @@
+use crate::{shell::window::MappedWindow, state::State};
+use smithay::{
+	input::{
+		SeatHandler,
+		pointer::{
+			AxisFrame, ButtonEvent, CursorIcon, Focus, GestureHoldBeginEvent, GestureHoldEndEvent,
+			GesturePinchBeginEvent, GesturePinchEndEvent, GesturePinchUpdateEvent, GestureSwipeBeginEvent,
+			GestureSwipeEndEvent, GestureSwipeUpdateEvent, GrabStartData, MotionEvent, PointerGrab,
+			PointerInnerHandle, RelativeMotionEvent,
+		},
+		touch::{
+			DownEvent, GrabStartData as TouchGrabStartData, MotionEvent as TouchMotionEvent,
+			OrientationEvent, ShapeEvent, TouchGrab, TouchInnerHandle, UpEvent,
+		},
+	},
+	output::Output,
+	reexports::wayland_server::Resource,
+	utils::{Logical, Point, Serial},
+	wayland::seat::WaylandFocus,
+};
+
+struct SplitGrab {
+	start_data: GrabStartData<State>,
+	output: Output,
+}
+
+impl PointerGrab<State> for SplitGrab {
+	fn motion(
+		&mut self,
+		state: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+		event: &MotionEvent,
+	) {
+		// no client has pointer focus while grab is active
+		handle.motion(state, None, event);
+
+		state.mayland.workspaces.move_split(&self.output, event.location);
+		state.mayland.queue_redraw(self.output.clone());
+	}
+
+	fn relative_motion(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+		event: &RelativeMotionEvent,
+	) {
+		// no client has pointer focus while grab is active
+		handle.relative_motion(data, None, event);
+	}
+
+	fn button(&mut self, state: &mut State, handle: &mut PointerInnerHandle<'_, State>, event: &ButtonEvent) {
+		handle.button(state, event);
+		if !handle.current_pressed().contains(&self.start_data.button) {
+			handle.unset_grab(self, state, event.serial, event.time, true);
+		}
+	}
+
+	fn axis(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>, details: AxisFrame) {
+		handle.axis(data, details);
+	}
+
+	fn frame(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>) {
+		handle.frame(data);
+	}
+
+	fn gesture_swipe_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeBeginEvent,
+	) {
+		handle.gesture_swipe_begin(data, event);
+	}
+
+	fn gesture_swipe_update(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeUpdateEvent,
+	) {
+		handle.gesture_swipe_update(data, event);
+	}
+
+	fn gesture_swipe_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeEndEvent,
+	) {
+		handle.gesture_swipe_end(data, event);
+	}
+
+	fn gesture_pinch_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchBeginEvent,
+	) {
+		handle.gesture_pinch_begin(data, event);
+	}
+
+	fn gesture_pinch_update(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchUpdateEvent,
+	) {
+		handle.gesture_pinch_update(data, event);
+	}
+
+	fn gesture_pinch_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchEndEvent,
+	) {
+		handle.gesture_pinch_end(data, event);
+	}
+
+	fn gesture_hold_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureHoldBeginEvent,
+	) {
+		handle.gesture_hold_begin(data, event);
+	}
+
+	fn gesture_hold_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureHoldEndEvent,
+	) {
+		handle.gesture_hold_end(data, event);
+	}
+
+	fn start_data(&self) -> &GrabStartData<State> {
+		&self.start_data
+	}
+
+	fn unset(&mut self, state: &mut State) {
+		state.mayland.cursor.icon = None;
+		state.mayland.queue_redraw_all();
+	}
+}
+
+struct MoveGrab {
+	start_data: GrabStartData<State>,
+	window: MappedWindow,
+}
+
+impl PointerGrab<State> for MoveGrab {
+	fn motion(
+		&mut self,
+		state: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+		event: &MotionEvent,
+	) {
+		// no client has pointer focus while grab is active
+		handle.motion(state, None, event);
+
+		if state.mayland.workspaces.swap_tiled(&self.window, event.location) {
+			state.mayland.queue_redraw_all();
+		}
+	}
+
+	fn relative_motion(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+		event: &RelativeMotionEvent,
+	) {
+		// no client has pointer focus while grab is active
+		handle.relative_motion(data, None, event);
+	}
+
+	fn button(&mut self, state: &mut State, handle: &mut PointerInnerHandle<'_, State>, event: &ButtonEvent) {
+		handle.button(state, event);
+		if !handle.current_pressed().contains(&self.start_data.button) {
+			handle.unset_grab(self, state, event.serial, event.time, true);
+		}
+	}
+
+	fn axis(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>, details: AxisFrame) {
+		handle.axis(data, details);
+	}
+
+	fn frame(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>) {
+		handle.frame(data);
+	}
+
+	fn gesture_swipe_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeBeginEvent,
+	) {
+		handle.gesture_swipe_begin(data, event);
+	}
+
+	fn gesture_swipe_update(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeUpdateEvent,
+	) {
+		handle.gesture_swipe_update(data, event);
+	}
+
+	fn gesture_swipe_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureSwipeEndEvent,
+	) {
+		handle.gesture_swipe_end(data, event);
+	}
+
+	fn gesture_pinch_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchBeginEvent,
+	) {
+		handle.gesture_pinch_begin(data, event);
+	}
+
+	fn gesture_pinch_update(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchUpdateEvent,
+	) {
+		handle.gesture_pinch_update(data, event);
+	}
+
+	fn gesture_pinch_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GesturePinchEndEvent,
+	) {
+		handle.gesture_pinch_end(data, event);
+	}
+
+	fn gesture_hold_begin(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureHoldBeginEvent,
+	) {
+		handle.gesture_hold_begin(data, event);
+	}
+
+	fn gesture_hold_end(
+		&mut self,
+		data: &mut State,
+		handle: &mut PointerInnerHandle<'_, State>,
+		event: &GestureHoldEndEvent,
+	) {
+		handle.gesture_hold_end(data, event);
+	}
+
+	fn start_data(&self) -> &GrabStartData<State> {
+		&self.start_data
+	}
+
+	fn unset(&mut self, state: &mut State) {
+		state.mayland.cursor.icon = None;
+		state.mayland.queue_redraw_all();
+	}
+}
+
+struct TouchMoveGrab {
+	start_data: TouchGrabStartData<State>,
+	window: MappedWindow,
+}
+
+impl TouchGrab<State> for TouchMoveGrab {
+	fn down(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::TouchFocus, Point<f64, Logical>)>,
+		event: &DownEvent,
+		seq: Serial,
+	) {
+		handle.down(data, None, event, seq);
+	}
+
+	fn up(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		event: &UpEvent,
+		seq: Serial,
+	) {
+		if event.slot != self.start_data.slot {
+			return;
+		}
+
+		handle.up(data, event, seq);
+		handle.unset_grab(self, data);
+	}
+
+	fn motion(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		_focus: Option<(<State as SeatHandler>::TouchFocus, Point<f64, Logical>)>,
+		event: &TouchMotionEvent,
+		_seq: Serial,
+	) {
+		if event.slot != self.start_data.slot {
+			return;
+		}
+
+		if data.mayland.workspaces.swap_tiled(&self.window, event.location) {
+			data.mayland.queue_redraw_all();
+		}
+	}
+
+	fn frame(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>, seq: Serial) {
+		handle.frame(data, seq);
+	}
+
+	fn cancel(&mut self, data: &mut State, handle: &mut TouchInnerHandle<'_, State>, seq: Serial) {
+		handle.cancel(data, seq);
+		handle.unset_grab(self, data);
+	}
+
+	fn shape(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		event: &ShapeEvent,
+		seq: Serial,
+	) {
+		handle.shape(data, event, seq);
+	}
+
+	fn orientation(
+		&mut self,
+		data: &mut State,
+		handle: &mut TouchInnerHandle<'_, State>,
+		event: &OrientationEvent,
+		seq: Serial,
+	) {
+		handle.orientation(data, event, seq);
+	}
+
+	fn start_data(&self) -> &TouchGrabStartData<State> {
+		&self.start_data
+	}
+
+	fn unset(&mut self, _data: &mut State) {}
+}
+
+impl State {
+	pub fn xdg_tiling_move(&mut self, window: MappedWindow, serial: Serial) {
+		let pointer = self.mayland.pointer.clone();
+
+		if !pointer.has_grab(serial) {
+			return;
+		}
+
+		let start_data = pointer.grab_start_data().unwrap();
+		let Some(((grab_focus, _), wl_surface)) = start_data.focus.as_ref().zip(window.wl_surface()) else {
+			return;
+		};
+
+		if !grab_focus.same_client_as(&wl_surface.id()) {
+			return;
+		}
+
+		self.tiling_move_grab(window, start_data, serial);
+	}
+
+	pub fn xdg_tiling_touch_move(&mut self, window: MappedWindow, serial: Serial) {
+		let touch = self.mayland.touch.clone();
+
+		if !touch.has_grab(serial) {
+			return;
+		}
+
+		let start_data = touch.grab_start_data().unwrap();
+		let Some(((grab_focus, _), wl_surface)) = start_data.focus.as_ref().zip(window.wl_surface()) else {
+			return;
+		};
+
+		if !grab_focus.same_client_as(&wl_surface.id()) {
+			return;
+		}
+
+		let grab = TouchMoveGrab { start_data, window };
+		touch.set_grab(self, grab, serial);
+	}
+
+	/// rearrange the tiled [`MappedWindow`] by swapping it with the window under the pointer
+	pub fn tiling_move_grab(
+		&mut self,
+		window: MappedWindow,
+		start_data: GrabStartData<State>,
+		serial: Serial,
+	) {
+		self.mayland.cursor.icon = Some(CursorIcon::Grabbing);
+		self.mayland.queue_redraw_all();
+
+		let grab = MoveGrab { start_data, window };
+
+		let pointer = self.mayland.pointer.clone();
+		pointer.set_grab(self, grab, serial, Focus::Clear);
+	}
+
+	pub fn xdg_tiling_resize(&mut self, window: MappedWindow, serial: Serial) {
+		let pointer = self.mayland.pointer.clone();
+
+		if !pointer.has_grab(serial) {
+			return;
+		}
+
+		let start_data = pointer.grab_start_data().unwrap();
+		let Some(((grab_focus, _), wl_surface)) = start_data.focus.as_ref().zip(window.wl_surface()) else {
+			return;
+		};
+
+		if !grab_focus.same_client_as(&wl_surface.id()) {
+			return;
+		}
+
+		self.split_grab(start_data, serial);
+	}
+
+	/// start dragging the tiling split under the grab start location
+	pub fn split_grab(&mut self, start_data: GrabStartData<State>, serial: Serial) {
+		let Some(output) = self.mayland.workspaces.output_under(start_data.location).cloned() else {
+			return;
+		};
+
+		self.mayland.cursor.icon = Some(CursorIcon::ColResize);
+		self.mayland.queue_redraw_all();
+
+		let grab = SplitGrab { start_data, output };
+
+		let pointer = self.mayland.pointer.clone();
+		pointer.set_grab(self, grab, serial, Focus::Clear);
+	}
+}
@@ -0,0 +1,221 @@
+use super::focus::KeyboardFocusTarget;
+use crate::{
+	render::MaylandRenderElements,
+	state::{Mayland, State},
+	utils::output_size,
+};
+use smithay::{
+	backend::renderer::{
+		element::{Kind, solid::SolidColorRenderElement, surface::render_elements_from_surface_tree},
+		glow::GlowRenderer,
+	},
+	delegate_session_lock,
+	output::Output,
+	reexports::{
+		wayland_protocols::ext::session_lock::v1::server::ext_session_lock_v1::ExtSessionLockV1,
+		wayland_server::{Resource, protocol::wl_output::WlOutput},
+	},
+	utils::{IsAlive, SERIAL_COUNTER, Size},
+	wayland::session_lock::{LockSurface, SessionLockHandler, SessionLockManagerState, SessionLocker},
+};
+use std::collections::HashSet;
+
+/// the color of the screen if the lock client crashed
+pub const LOCKED_COLOR: [f32; 4] = [0.3, 0.1, 0.1, 1.];
+
+#[derive(Debug, Default)]
+pub enum LockState {
+	#[default]
+	Unlocked,
+	/// the session is locked, but not every output has rendered the
+	/// lock screen yet, so the lock can't be confirmed
+	Locking {
+		locker: SessionLocker,
+		pending: HashSet<Output>,
+	},
+	Locked(ExtSessionLockV1),
+}
+
+impl LockState {
+	/// the lock client died without unlocking the session
+	fn is_crashed(&self) -> bool {
+		matches!(self, LockState::Locked(lock) if !lock.is_alive())
+	}
+}
+
+impl SessionLockHandler for State {
+	fn lock_state(&mut self) -> &mut SessionLockManagerState {
+		&mut self.mayland.session_lock_state
+	}
+
+	fn lock(&mut self, confirmation: SessionLocker) {
+		match &self.mayland.lock_state {
+			LockState::Unlocked => {}
+			LockState::Locking { .. } => {
+				// dropping the locker tells the client that locking failed
+				tracing::info!("refusing to lock, another client is already locking");
+				return;
+			}
+			LockState::Locked(lock) if lock.is_alive() => {
+				tracing::info!("refusing to lock, another client has already locked");
+				return;
+			}
+			// the previous lock client crashed, so the new one can take over
+			LockState::Locked(_) => {}
+		}
+
+		// outputs that are turned off can't render the lock screen
+		let pending = (self.mayland.output_state.iter())
+			.filter(|(_, output_state)| output_state.powered)
+			.map(|(output, _)| output.clone())
+			.collect();
+		self.mayland.lock_state = LockState::Locking {
+			locker: confirmation,
+			pending,
+		};
+
+		self.reset_focus();
+		self.mayland.queue_redraw_all();
+
+		// there might be no outputs to wait for
+		self.mayland.lock_output_done(None);
+	}
+
+	fn unlock(&mut self) {
+		self.mayland.lock_state = LockState::Unlocked;
+		for output_state in self.mayland.output_state.values_mut() {
+			output_state.lock_surface = None;
+		}
+
+		self.reset_focus();
+		self.mayland.queue_redraw_all();
+	}
+
+	fn new_surface(&mut self, surface: LockSurface, output: WlOutput) {
+		let Some(output) = Output::from_resource(&output) else {
+			return;
+		};
+
+		let Some(output_state) = self.mayland.output_state.get_mut(&output) else {
+			return;
+		};
+
+		let size = output_size(&output);
+		surface.with_pending_state(|state| {
+			state.size = Some(Size::new(size.w as u32, size.h as u32));
+		});
+		surface.send_configure();
+
+		output_state.lock_surface = Some(surface);
+
+		self.reset_focus();
+		self.mayland.queue_redraw(output);
+	}
+}
+
+delegate_session_lock!(State);
+
+impl Mayland {
+	pub fn is_locked(&self) -> bool {
+		!matches!(self.lock_state, LockState::Unlocked)
+	}
+
+	/// get the lock surface of the [`Output`], if the lock client has created one
+	pub fn lock_surface(&self, output: &Output) -> Option<&LockSurface> {
+		let output_state = self.output_state.get(output)?;
+		(output_state.lock_surface.as_ref()).filter(|surface| surface.wl_surface().alive())
+	}
+
+	/// the [`Output`] has rendered the lock screen, or was removed
+	///
+	/// once every output has done so, the lock is confirmed to the client
+	pub fn lock_output_done(&mut self, output: Option<&Output>) {
+		let LockState::Locking { pending, .. } = &mut self.lock_state else {
+			return;
+		};
+
+		if let Some(output) = output {
+			pending.remove(output);
+		}
+
+		if !pending.is_empty() {
+			return;
+		}
+
+		let LockState::Locking { locker, .. } = std::mem::take(&mut self.lock_state) else {
+			unreachable!()
+		};
+
+		let lock = locker.ext_session_lock().clone();
+		locker.lock();
+
+		self.lock_state = LockState::Locked(lock);
+	}
+
+	/// resize the lock surface after the output size changed
+	pub fn resize_lock_surface(&self, output: &Output) {
+		let size = output_size(output);
+		let Some(output_state) = self.output_state.get(output) else {
+			return;
+		};
+
+		if let Some(surface) = &output_state.lock_surface {
+			surface.with_pending_state(|state| {
+				state.size = Some(Size::new(size.w as u32, size.h as u32));
+			});
+			surface.send_configure();
+		}
+	}
+
+	/// the elements that are rendered instead of the desktop while the session is locked
+	pub fn lock_elements(&self, renderer: &mut GlowRenderer, output: &Output) -> Vec<MaylandRenderElements> {
+		let mut elements = Vec::new();
+		let scale = output.current_scale().fractional_scale();
+
+		if let Some(surface) = self.lock_surface(output) {
+			elements.extend(render_elements_from_surface_tree(
+				renderer,
+				surface.wl_surface(),
+				(0, 0),
+				scale,
+				1.,
+				Kind::Unspecified,
+			));
+		}
+
+		// never show the desktop, even if the lock client crashed
+		let output_state = &self.output_state[output];
+		let background = if self.lock_state.is_crashed() {
+			&output_state.locked
+		} else {
+			&output_state.background
+		};
+
+		// the buffers are sized in logical coordinates
+		elements.push(MaylandRenderElements::Solid(
+			SolidColorRenderElement::from_buffer(background, (0, 0), scale, 1.0, Kind::Unspecified),
+		));
+
+		elements
+	}
+}
+
+impl State {
+	/// move the keyboard focus to the lock surface of the output under the pointer
+	pub fn focus_lock_surface(&mut self) {
+		let location = self.mayland.pointer.current_location();
+		let output = self.mayland.workspaces.output_under(location);
+
+		let surface = output
+			.and_then(|output| self.mayland.lock_surface(output))
+			.or_else(|| {
+				(self.mayland.workspaces.outputs()).find_map(|output| self.mayland.lock_surface(output))
+			});
+		let target = surface.map(|surface| KeyboardFocusTarget::LockSurface(surface.wl_surface().clone()));
+
+		let keyboard = self.mayland.keyboard.clone();
+		if keyboard.current_focus() != target {
+			keyboard.set_focus(self, target, SERIAL_COUNTER.next_serial());
+		}
+	}
+}
@@ -1,5 +1,9 @@
 use super::{focus::KeyboardFocusTarget, grab::ResizeState};
-use crate::{layout::workspace::Workspace, render::MaylandRenderElements, state::State};
+use crate::{
+	layout::workspace::{WindowMode, Workspace},
+	render::MaylandRenderElements,
+	state::State,
+};
 use mayland_config::windowrules::WindowRule;
 use smithay::{
 	backend::renderer::{
@@ -29,6 +33,7 @@ use smithay::{
 		selection::data_device::WlOfferData,
 		shell::xdg::{SurfaceCachedState, ToplevelSurface, XdgToplevelSurfaceData},
 	},
+	xwayland::X11Surface,
 };
 use std::{
 	borrow::Cow,
@@ -59,6 +64,29 @@ impl MappedWindow {
 				});
 				xdg.send_pending_configure();
 			}
+			WindowSurface::X11(x11) => {
+				let mut geometry = x11.geometry();
+				geometry.size = rect.size;
+				if let Err(err) = x11.configure(geometry) {
+					tracing::warn!("failed to configure x11 window: {:?}", err);
+				}
+			}
+		}
+	}
+
+	/// x11 windows need to know their absolute location on screen,
+	/// or else popups and menus will open in the wrong place
+	pub fn set_x11_location(&self, location: Point<i32, Logical>) {
+		if let WindowSurface::X11(x11) = self.underlying_surface() {
+			let mut geometry = x11.geometry();
+			if geometry.loc == location {
+				return;
+			}
+
+			geometry.loc = location;
+			if let Err(err) = x11.configure(geometry) {
+				tracing::warn!("failed to configure x11 window: {:?}", err);
+			}
 		}
 	}
 }
@@ -75,6 +103,11 @@ impl MappedWindow {
 	pub fn close(&self) {
 		match self.underlying_surface() {
 			WindowSurface::Wayland(xdg) => xdg.send_close(),
+			WindowSurface::X11(x11) => {
+				if let Err(err) = x11.close() {
+					tracing::warn!("failed to close x11 window: {:?}", err);
+				}
+			}
 		}
 	}
 
@@ -123,6 +156,10 @@ impl MappedWindow {
 
 				min.w > 0 && min.h > 0 && min == max
 			}
+			WindowSurface::X11(x11) => {
+				let (min, max) = (x11.min_size(), x11.max_size());
+				min.is_some_and(|min| min.w > 0 && min.h > 0 && Some(min) == max)
+			}
 		}
 	}
 
@@ -147,6 +184,65 @@ impl MappedWindow {
 
 				(data.min_size, max_size)
 			}),
+			WindowSurface::X11(x11) => {
+				let min_size = x11.min_size().unwrap_or_default();
+				let max_size = x11.max_size().unwrap_or(Size::new(i32::MAX, i32::MAX));
+
+				(min_size, max_size)
+			}
+		}
+	}
+
+	/// set the fullscreen and maximized state of the window
+	///
+	/// for wayland windows this only changes the pending state, so it
+	/// has to be followed by a [`MappedWindow::resize`]
+	pub fn set_mode(&self, mode: Option<WindowMode>) {
+		match self.underlying_surface() {
+			WindowSurface::Wayland(xdg) => {
+				xdg.with_pending_state(|state| {
+					state.states.unset(xdg_toplevel::State::Fullscreen);
+					state.states.unset(xdg_toplevel::State::Maximized);
+
+					match mode {
+						Some(WindowMode::Fullscreen) => state.states.set(xdg_toplevel::State::Fullscreen),
+						Some(WindowMode::Maximized) => state.states.set(xdg_toplevel::State::Maximized),
+						None => {}
+					}
+				});
+			}
+			WindowSurface::X11(x11) => {
+				let fullscreen = x11.set_fullscreen(mode == Some(WindowMode::Fullscreen));
+				let maximized = x11.set_maximized(mode == Some(WindowMode::Maximized));
+				if let Err(err) = fullscreen.and(maximized) {
+					tracing::warn!("failed to set x11 window state: {:?}", err);
+				}
+			}
+		}
+	}
+
+	/// the fullscreen or maximized state that the window requested
+	/// before it was mapped
+	pub fn requested_mode(&self) -> Option<WindowMode> {
+		match self.underlying_surface() {
+			WindowSurface::Wayland(xdg) => xdg.with_pending_state(|state| {
+				if state.states.contains(xdg_toplevel::State::Fullscreen) {
+					Some(WindowMode::Fullscreen)
+				} else if state.states.contains(xdg_toplevel::State::Maximized) {
+					Some(WindowMode::Maximized)
+				} else {
+					None
+				}
+			}),
+			WindowSurface::X11(x11) => {
+				if x11.is_fullscreen() {
+					Some(WindowMode::Fullscreen)
+				} else if x11.is_maximized() {
+					Some(WindowMode::Maximized)
+				} else {
+					None
+				}
+			}
 		}
 	}
 
@@ -169,6 +265,8 @@ impl MappedWindow {
 				});
 				xdg.send_pending_configure();
 			}
+			// x11 has no concept of tiled windows
+			WindowSurface::X11(_) => {}
 		}
 	}
 }
@@ -186,6 +284,7 @@ impl MappedWindow {
 
 				config.compute(surface_data.app_id.as_deref(), surface_data.title.as_deref())
 			}),
+			WindowSurface::X11(x11) => config.compute(Some(&x11.class()), Some(&x11.title())),
 		};
 
 		self.windowrules.write(windowrules);
@@ -279,10 +378,17 @@ impl PartialEq<ToplevelSurface> for MappedWindow {
 	fn eq(&self, other: &ToplevelSurface) -> bool {
 		match self.underlying_surface() {
 			WindowSurface::Wayland(xdg) => xdg == other,
+			WindowSurface::X11(_) => false,
 		}
 	}
 }
 
+impl PartialEq<X11Surface> for MappedWindow {
+	fn eq(&self, other: &X11Surface) -> bool {
+		self.window.x11_surface() == Some(other)
+	}
+}
+
 impl PointerTarget<State> for MappedWindow {
 	fn enter(&self, seat: &smithay::input::Seat<State>, data: &mut State, event: &MotionEvent) {
 		if let Some(w) = self.wl_surface() {
@@ -308,24 +414,7 @@ impl PointerTarget<State> for MappedWindow {
 	}
 
 	fn button(&self, seat: &Seat<State>, data: &mut State, event: &ButtonEvent) {
-		let mods = data.mayland.keyboard.modifier_state();
-		if mods == data.mayland.comp_mod {
-			let button = event.button;
-
-			if button == 272 {
-				let serial = event.serial;
-				let window = self.clone();
-				data.mayland
-					.loop_handle
-					.insert_idle(move |state| state.xdg_move(window, serial));
-			} else if button == 273 {
-				let serial = event.serial;
-				let window = self.clone();
-				data.mayland
-					.loop_handle
-					.insert_idle(move |state| state.xdg_resize(window, serial));
-			}
-		} else if let Some(w) = self.wl_surface() {
+		if let Some(w) = self.wl_surface() {
 			PointerTarget::button(&*w, seat, data, event);
 		}
 	}
@@ -456,6 +545,10 @@ impl DndFocus<State> for MappedWindow {
 			WindowSurface::Wayland(xdg) => {
 				DndFocus::enter(xdg.wl_surface(), data, dh, source, seat, location, serial)
 			}
+			WindowSurface::X11(x11) => {
+				let surface = x11.wl_surface()?;
+				DndFocus::enter(&surface, data, dh, source, seat, location, serial)
+			}
 		}
 	}
 
@@ -471,6 +564,11 @@ impl DndFocus<State> for MappedWindow {
 			WindowSurface::Wayland(xdg) => {
 				DndFocus::motion(xdg.wl_surface(), data, offer, seat, location, time);
 			}
+			WindowSurface::X11(x11) => {
+				if let Some(surface) = x11.wl_surface() {
+					DndFocus::motion(&surface, data, offer, seat, location, time);
+				}
+			}
 		}
 	}
 
@@ -482,6 +580,11 @@ impl DndFocus<State> for MappedWindow {
 	) {
 		match self.underlying_surface() {
 			WindowSurface::Wayland(xdg) => DndFocus::leave(xdg.wl_surface(), data, offer, seat),
+			WindowSurface::X11(x11) => {
+				if let Some(surface) = x11.wl_surface() {
+					DndFocus::leave(&surface, data, offer, seat);
+				}
+			}
 		}
 	}
 
@@ -493,6 +596,11 @@ impl DndFocus<State> for MappedWindow {
 	) {
 		match self.underlying_surface() {
 			WindowSurface::Wayland(xdg) => DndFocus::drop(xdg.wl_surface(), data, offer, seat),
+			WindowSurface::X11(x11) => {
+				if let Some(surface) = x11.wl_surface() {
+					DndFocus::drop(&surface, data, offer, seat);
+				}
+			}
 		}
 	}
 }
@@ -561,6 +669,19 @@ impl MappedWindow {
 					xwayland: false,
 				}
 			}),
+			WindowSurface::X11(x11) => mayland_comm::Window {
+				relative,
+				absolute,
+
+				app_id: Some(x11.class()),
+				title: Some(x11.title()),
+				pid: x11.pid().map(|pid| pid as i32),
+
+				workspace: workspace.idx,
+				active,
+
+				xwayland: true,
+			},
 		}
 	}
 }
@@ -584,6 +705,10 @@ impl From<&MappedWindow> for mayland_comm::workspace::Window {
 					}
 				})
 			}
+			WindowSurface::X11(x11) => mayland_comm::workspace::Window {
+				app_id: Some(x11.class()),
+				title: Some(x11.title()),
+			},
 		}
 	}
 }
@@ -608,6 +733,7 @@ impl UnmappedSurface {
 
 				windowrules.compute(surface_data.app_id.as_deref(), surface_data.title.as_deref())
 			}),
+			WindowSurface::X11(x11) => windowrules.compute(Some(&x11.class()), Some(&x11.title())),
 		}
 	}
 }
@@ -628,6 +754,7 @@ impl PartialEq<ToplevelSurface> for UnmappedSurface {
 	fn eq(&self, other: &ToplevelSurface) -> bool {
 		match self.0.underlying_surface() {
 			WindowSurface::Wayland(toplevel) => toplevel == other,
+			WindowSurface::X11(_) => false,
 		}
 	}
 }
@@ -645,6 +772,13 @@ impl From<ToplevelSurface> for UnmappedSurface {
 	}
 }
 
+impl From<X11Surface> for UnmappedSurface {
+	fn from(surface: X11Surface) -> Self {
+		let window = Window::new_x11_window(surface);
+		UnmappedSurface(window)
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct ResolvedWindowRule(Arc<RwLock<WindowRule>>);
 
@@ -1,11 +1,26 @@
-use super::window::{MappedWindow, UnmappedSurface};
-use crate::state::{Mayland, State};
+use super::{
+	focus::KeyboardFocusTarget,
+	window::{MappedWindow, UnmappedSurface},
+};
+use crate::{
+	layout::workspace::WindowMode,
+	state::{Mayland, State},
+	utils::output_size,
+};
+use mayland_comm::Event;
 use smithay::{
 	backend::renderer::utils::with_renderer_surface_state,
 	delegate_presentation, delegate_xdg_shell,
-	desktop::PopupKind,
-	reexports::wayland_server::protocol::{wl_seat::WlSeat, wl_surface::WlSurface},
-	utils::Serial,
+	desktop::{
+		PopupKeyboardGrab, PopupKind, PopupPointerGrab, PopupUngrabStrategy, WindowSurfaceType,
+		find_popup_root_surface, get_popup_toplevel_coords, layer_map_for_output,
+	},
+	input::{Seat, pointer::Focus},
+	reexports::{
+		wayland_protocols::xdg::shell::server::xdg_toplevel,
+		wayland_server::protocol::{wl_output::WlOutput, wl_seat::WlSeat, wl_surface::WlSurface},
+	},
+	utils::{Rectangle, Serial},
 	wayland::{
 		compositor::with_states,
 		shell::xdg::{
@@ -28,7 +43,11 @@ impl XdgShellHandler for State {
 	}
 
 	fn new_popup(&mut self, surface: PopupSurface, _positioner: PositionerState) {
-		let _ = self.mayland.popups.track_popup(PopupKind::Xdg(surface));
+		self.unconstrain_popup(&surface);
+
+		if let Err(err) = self.mayland.popups.track_popup(PopupKind::Xdg(surface)) {
+			tracing::warn!("failed to track popup: {:?}", err);
+		}
 	}
 
 	fn reposition_request(&mut self, surface: PopupSurface, positioner: PositionerState, token: u32) {
@@ -38,12 +57,104 @@ impl XdgShellHandler for State {
 			state.positioner = positioner;
 		});
 
+		self.unconstrain_popup(&surface);
 		surface.send_repositioned(token);
 	}
 
-	fn grab(&mut self, surface: PopupSurface, _seat: WlSeat, _serial: Serial) {
-		// todo
-		tracing::info!("XdgShellHandler::grab {:?}", surface);
+	fn grab(&mut self, surface: PopupSurface, seat: WlSeat, serial: Serial) {
+		let Some(seat) = Seat::<State>::from_resource(&seat) else {
+			return;
+		};
+
+		// the focus can't leave the lock surface
+		if self.mayland.is_locked() {
+			surface.send_popup_done();
+			return;
+		}
+
+		let popup = PopupKind::Xdg(surface.clone());
+		let Some(root) = self.popup_root(&popup) else {
+			tracing::warn!("couldn't find the root of the popup");
+			return;
+		};
+
+		// the grab has to belong to a current input grab, or to a surface with keyboard focus,
+		// otherwise a background client could take the focus with any serial
+		if !self.may_grab_popup(&root, serial) {
+			surface.send_popup_done();
+			return;
+		}
+
+		let mut grab = match self.mayland.popups.grab_popup(root, popup, &seat, serial) {
+			Ok(grab) => grab,
+			Err(err) => {
+				tracing::warn!("failed to grab popup: {:?}", err);
+				return;
+			}
+		};
+
+		// nested popups can take over the grab of their parent,
+		// but an unrelated grab can't be interrupted
+		if let Some(keyboard) = seat.get_keyboard() {
+			if keyboard.is_grabbed()
+				&& !(keyboard.has_grab(serial) || keyboard.has_grab(grab.previous_serial().unwrap_or(serial)))
+			{
+				grab.ungrab(PopupUngrabStrategy::All);
+				return;
+			}
+
+			keyboard.set_focus(self, grab.current_grab(), serial);
+			keyboard.set_grab(self, PopupKeyboardGrab::new(&grab), serial);
+		}
+
+		if let Some(pointer) = seat.get_pointer() {
+			if pointer.is_grabbed()
+				&& !(pointer.has_grab(serial) || pointer.has_grab(grab.previous_serial().unwrap_or(serial)))
+			{
+				grab.ungrab(PopupUngrabStrategy::All);
+				return;
+			}
+
+			pointer.set_grab(self, PopupPointerGrab::new(&grab), serial, Focus::Keep);
+		}
+	}
+
+	fn fullscreen_request(&mut self, toplevel: ToplevelSurface, _output: Option<WlOutput>) {
+		self.toplevel_mode_request(toplevel, WindowMode::Fullscreen, true);
+	}
+
+	fn unfullscreen_request(&mut self, toplevel: ToplevelSurface) {
+		self.toplevel_mode_request(toplevel, WindowMode::Fullscreen, false);
+	}
+
+	fn maximize_request(&mut self, toplevel: ToplevelSurface) {
+		self.toplevel_mode_request(toplevel, WindowMode::Maximized, true);
+	}
+
+	fn unmaximize_request(&mut self, toplevel: ToplevelSurface) {
+		self.toplevel_mode_request(toplevel, WindowMode::Maximized, false);
+	}
+
+	fn move_request(&mut self, toplevel: ToplevelSurface, _seat: WlSeat, serial: Serial) {
+		let Some(window) = self.mayland.workspaces.window_for_surface(&toplevel).cloned() else {
+			return;
+		};
+
+		self.xdg_move(window, serial);
+	}
+
+	fn resize_request(
+		&mut self,
+		toplevel: ToplevelSurface,
+		_seat: WlSeat,
+		serial: Serial,
+		_edges: xdg_toplevel::ResizeEdge,
+	) {
+		let Some(window) = self.mayland.workspaces.window_for_surface(&toplevel).cloned() else {
+			return;
+		};
+
+		self.xdg_resize(window, serial);
 	}
 
 	fn toplevel_destroyed(&mut self, toplevel: ToplevelSurface) {
@@ -59,6 +170,10 @@ impl XdgShellHandler for State {
 		};
 
 		self.mayland.workspaces.remove_window(&window);
+
+		let window = mayland_comm::workspace::Window::from(&window);
+		self.mayland.may_socket.send_event(Event::WindowClosed(window));
+
 		self.reset_focus();
 		self.mayland.queue_redraw_all();
 	}
@@ -80,6 +195,9 @@ impl XdgShellHandler for State {
 		};
 
 		window.recompute_windowrules(&self.mayland.config.windowrules);
+
+		let window = window.clone();
+		self.mayland.refresh_foreign_toplevel(&window);
 	}
 
 	fn title_changed(&mut self, toplevel: ToplevelSurface) {
@@ -94,6 +212,14 @@ impl XdgShellHandler for State {
 		};
 
 		window.recompute_windowrules(&self.mayland.config.windowrules);
+
+		let window = window.clone();
+		self.mayland.refresh_foreign_toplevel(&window);
+
+		let window = mayland_comm::workspace::Window::from(&window);
+		self.mayland
+			.may_socket
+			.send_event(Event::WindowTitleChanged(window));
 	}
 }
 
@@ -113,6 +239,133 @@ fn initial_configure_sent(toplevel: &ToplevelSurface) -> bool {
 }
 
 impl State {
+	/// can the popup with the root take the grab with the serial?
+	fn may_grab_popup(&self, root: &KeyboardFocusTarget, serial: Serial) -> bool {
+		let keyboard = &self.mayland.keyboard;
+		if keyboard.has_grab(serial) || self.mayland.pointer.has_grab(serial) {
+			return true;
+		}
+
+		// nested popups are opened while their parent popup has the focus
+		let focus = match keyboard.current_focus() {
+			Some(KeyboardFocusTarget::Popup(popup)) => self.popup_root(&popup),
+			focus => focus,
+		};
+
+		focus.as_ref() == Some(root)
+	}
+
+	/// the window or layer surface that the popup belongs to
+	fn popup_root(&self, popup: &PopupKind) -> Option<KeyboardFocusTarget> {
+		let root = find_popup_root_surface(popup).ok()?;
+
+		if let Some(window) = self.mayland.workspaces.window_for_surface(&root) {
+			return Some(KeyboardFocusTarget::Window(window.clone()));
+		}
+
+		(self.mayland.workspaces.outputs()).find_map(|output| {
+			let layer_map = layer_map_for_output(output);
+			let layer = layer_map.layer_for_surface(&root, WindowSurfaceType::TOPLEVEL)?;
+			Some(KeyboardFocusTarget::LayerSurface(layer.clone()))
+		})
+	}
+
+	/// keep the popup inside of the working area of the output of its window,
+	/// or inside of the entire output for fullscreen windows and layer surfaces
+	pub fn unconstrain_popup(&self, popup: &PopupSurface) {
+		let kind = PopupKind::Xdg(popup.clone());
+		let Ok(root) = find_popup_root_surface(&kind) else {
+			return;
+		};
+
+		// the area the popup has to fit in and the location of the root, relative to the output
+		let target = if let Some(window) = self.mayland.workspaces.window_for_surface(&root) {
+			(self.mayland.workspaces.workspaces()).find_map(|workspace| {
+				let output = workspace.output.as_ref()?;
+				let geometry = workspace.window_geometry(window)?;
+
+				let area = if workspace.window_mode(window) == Some(WindowMode::Fullscreen) {
+					Rectangle::from_size(output_size(output))
+				} else {
+					layer_map_for_output(output).non_exclusive_zone()
+				};
+
+				Some((area, geometry.loc))
+			})
+		} else {
+			(self.mayland.workspaces.outputs()).find_map(|output| {
+				let layer_map = layer_map_for_output(output);
+				let layer = layer_map.layer_for_surface(&root, WindowSurfaceType::TOPLEVEL)?;
+				let geometry = layer_map.layer_geometry(layer)?;
+
+				Some((Rectangle::from_size(output_size(output)), geometry.loc))
+			})
+		};
+
+		let Some((mut target, root_location)) = target else {
+			return;
+		};
+
+		// the popup is positioned relative to the geometry of its parent
+		target.loc -= root_location + get_popup_toplevel_coords(&kind);
+
+		popup.with_pending_state(|state| {
+			state.geometry = state.positioner.get_unconstrained_geometry(target);
+		});
+	}
+
+	/// handle a request of a toplevel to set or unset its fullscreen or maximized state
+	fn toplevel_mode_request(&mut self, toplevel: ToplevelSurface, mode: WindowMode, set: bool) {
+		if self.mayland.unmapped_windows.iter().any(|w| w == &toplevel) {
+			// remember the state until the window gets mapped
+			toplevel.with_pending_state(|state| {
+				let xdg_state = match mode {
+					WindowMode::Fullscreen => xdg_toplevel::State::Fullscreen,
+					WindowMode::Maximized => xdg_toplevel::State::Maximized,
+				};
+
+				if set {
+					state.states.set(xdg_state);
+				} else {
+					state.states.unset(xdg_state);
+				}
+			});
+
+			if initial_configure_sent(&toplevel) {
+				toplevel.send_pending_configure();
+			}
+
+			return;
+		}
+
+		let Some(window) = self.mayland.workspaces.window_for_surface(&toplevel).cloned() else {
+			tracing::error!("couldn't find toplevel");
+			return;
+		};
+
+		if !self.window_mode_request(&window, mode, set) {
+			// the client expects a configure, even if nothing changed
+			toplevel.send_configure();
+		}
+	}
+
+	/// set or unset the fullscreen or maximized state of a mapped window
+	///
+	/// returns `false` if the state didn't change
+	pub fn window_mode_request(&mut self, window: &MappedWindow, mode: WindowMode, set: bool) -> bool {
+		let prev = self.mayland.workspaces.window_mode(window);
+		if set {
+			self.mayland.workspaces.set_window_mode(window, mode);
+		} else if prev == Some(mode) {
+			self.mayland.workspaces.restore_window(window);
+		}
+
+		self.reset_focus();
+		self.mayland.queue_redraw_all();
+
+		self.mayland.workspaces.window_mode(window) != prev
+	}
+
 	/// handle unmapped windows
 	pub fn try_map_window(&mut self, surface: &WlSurface) {
 		if let Some((idx, unmapped)) = self
@@ -138,10 +391,19 @@ impl State {
 				let location = self.mayland.pointer.current_location();
 				self.mayland.workspaces.add_window(mapped.clone(), location);
 
+				// the window might have requested to be fullscreen before it was mapped,
+				// which has to be checked before the tiled state is set
+				if let Some(mode) = mapped.requested_mode() {
+					self.mayland.workspaces.set_window_mode(&mapped, mode);
+				}
+
 				// set the window state to be tiled, so that
 				// gtk apps don't round their corners
 				mapped.set_tiled();
 
+				let window = mayland_comm::workspace::Window::from(&mapped);
+				self.mayland.may_socket.send_event(Event::WindowOpened(window));
+
 				// automatically focus new windows
 				self.focus_window(mapped);
 
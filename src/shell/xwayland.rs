@@ -0,0 +1,412 @@
+use super::window::{MappedWindow, UnmappedSurface};
+use crate::{
+	layout::workspace::WindowMode,
+	render::MaylandRenderElements,
+	shell::focus::KeyboardFocusTarget,
+	state::{Mayland, State},
+};
+use mayland_comm::Event;
+use smithay::{
+	backend::renderer::{element::AsRenderElements, glow::GlowRenderer},
+	delegate_xwayland_shell,
+	desktop::utils::send_frames_surface_tree,
+	output::Output,
+	reexports::wayland_server::protocol::wl_surface::WlSurface,
+	utils::{Logical, Point, Rectangle, Size},
+	wayland::{
+		seat::WaylandFocus,
+		selection::{
+			SelectionTarget,
+			data_device::{
+				clear_data_device_selection, current_data_device_selection_userdata,
+				request_data_device_client_selection, set_data_device_selection,
+			},
+			primary_selection::{
+				clear_primary_selection, current_primary_selection_userdata,
+				request_primary_client_selection, set_primary_selection,
+			},
+		},
+		xwayland_shell::{XWaylandShellHandler, XWaylandShellState},
+	},
+	xwayland::{
+		X11Surface, X11Wm, XWayland, XWaylandEvent, XwmHandler,
+		xwm::{Reorder, ResizeEdge, WmWindowProperty, XwmId},
+	},
+};
+use std::{os::fd::OwnedFd, process::Stdio, time::Duration};
+
+impl Mayland {
+	/// spawn xwayland and start the x11 window manager once it's ready
+	pub fn start_xwayland(&mut self) {
+		let spawned = XWayland::spawn(
+			&self.display_handle,
+			None,
+			std::iter::empty::<(String, String)>(),
+			true,
+			Stdio::null(),
+			Stdio::null(),
+			|_| (),
+		);
+
+		let (xwayland, client) = match spawned {
+			Ok(spawned) => spawned,
+			Err(err) => {
+				tracing::warn!("failed to spawn xwayland: {:?}", err);
+				return;
+			}
+		};
+
+		self.loop_handle
+			.insert_source(xwayland, move |event, (), state| match event {
+				XWaylandEvent::Ready {
+					x11_socket,
+					display_number,
+				} => {
+					let loop_handle = state.mayland.loop_handle.clone();
+					let mut xwm = match X11Wm::start_wm(loop_handle, x11_socket, client.clone()) {
+						Ok(xwm) => xwm,
+						Err(err) => {
+							tracing::warn!("failed to start x11 window manager: {:?}", err);
+							return;
+						}
+					};
+
+					let xcursor = state.mayland.cursor.default_cursor();
+					let image = xcursor.frame(Duration::ZERO).image();
+					if let Err(err) = xwm.set_cursor(
+						&image.pixels_rgba,
+						Size::new(image.width as u16, image.height as u16),
+						Point::new(image.xhot as u16, image.yhot as u16),
+					) {
+						tracing::warn!("failed to set xwayland cursor: {:?}", err);
+					}
+
+					tracing::info!("xwayland ready on display :{}", display_number);
+
+					state.mayland.xwm = Some(xwm);
+					(state.mayland.environment).insert("DISPLAY".to_owned(), format!(":{}", display_number));
+				}
+				XWaylandEvent::Error => {
+					tracing::warn!("xwayland crashed on startup");
+				}
+			})
+			.unwrap();
+	}
+
+	/// get the override redirect x11 window under the location
+	pub fn override_redirect_under(
+		&self,
+		location: Point<f64, Logical>,
+	) -> Option<(WlSurface, Point<f64, Logical>)> {
+		self.override_redirect.iter().rev().find_map(|window| {
+			let geometry = window.geometry();
+			if !geometry.to_f64().contains(location) {
+				return None;
+			}
+
+			let surface = window.wl_surface()?;
+			Some((surface.into_owned(), geometry.loc.to_f64()))
+		})
+	}
+
+	/// render the override redirect x11 windows, like menus and tooltips,
+	/// that are on the output
+	pub fn override_redirect_elements(
+		&self,
+		renderer: &mut GlowRenderer,
+		output: &Output,
+	) -> Vec<MaylandRenderElements> {
+		let Some(output_geometry) = self.workspaces.output_geometry(output) else {
+			return Vec::new();
+		};
+
+		let scale = output.current_scale().fractional_scale();
+		self.override_redirect
+			.iter()
+			.rev()
+			.filter(|window| window.geometry().overlaps(output_geometry))
+			.flat_map(|window| {
+				let location = window.geometry().loc - output_geometry.loc;
+				let location = location.to_physical_precise_round(scale);
+				AsRenderElements::<GlowRenderer>::render_elements::<MaylandRenderElements>(
+					window,
+					renderer,
+					location,
+					scale.into(),
+					1.,
+				)
+			})
+			.collect()
+	}
+
+	pub fn send_override_redirect_frames(&self, output: &Output) {
+		for window in &self.override_redirect {
+			if let Some(surface) = window.wl_surface() {
+				send_frames_surface_tree(
+					&surface,
+					output,
+					self.start_time.elapsed(),
+					Some(Duration::ZERO),
+					|_, _| Some(output.clone()),
+				);
+			}
+		}
+	}
+}
+
+impl XwmHandler for State {
+	fn xwm_state(&mut self, _xwm: XwmId) -> &mut X11Wm {
+		self.mayland.xwm.as_mut().unwrap()
+	}
+
+	fn new_window(&mut self, _xwm: XwmId, _window: X11Surface) {}
+
+	fn new_override_redirect_window(&mut self, _xwm: XwmId, _window: X11Surface) {}
+
+	fn map_window_request(&mut self, _xwm: XwmId, window: X11Surface) {
+		if let Err(err) = window.set_mapped(true) {
+			tracing::warn!("failed to map x11 window: {:?}", err);
+			return;
+		}
+
+		if let Some(xwm) = self.mayland.xwm.as_mut() {
+			let _ = xwm.raise_window(&window);
+		}
+
+		let unmapped = UnmappedSurface::from(window);
+		let windowrules = unmapped.compute_windowrules(&self.mayland.config.windowrules);
+		let mapped = MappedWindow::new(unmapped, windowrules);
+
+		let location = self.mayland.pointer.current_location();
+		self.mayland.workspaces.add_window(mapped.clone(), location);
+
+		if let Some(mode) = mapped.requested_mode() {
+			self.mayland.workspaces.set_window_mode(&mapped, mode);
+		}
+
+		let window = mayland_comm::workspace::Window::from(&mapped);
+		self.mayland.may_socket.send_event(Event::WindowOpened(window));
+
+		// automatically focus new windows
+		self.focus_window(mapped);
+		self.mayland.queue_redraw_all();
+	}
+
+	fn mapped_override_redirect_window(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.mayland.override_redirect.push(window);
+		self.mayland.queue_redraw_all();
+	}
+
+	fn unmapped_window(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.unmap_x11_window(&window);
+
+		if !window.is_override_redirect() {
+			let _ = window.set_mapped(false);
+		}
+	}
+
+	fn destroyed_window(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.unmap_x11_window(&window);
+	}
+
+	fn configure_request(
+		&mut self,
+		_xwm: XwmId,
+		window: X11Surface,
+		_x: Option<i32>,
+		_y: Option<i32>,
+		w: Option<u32>,
+		h: Option<u32>,
+		_reorder: Option<Reorder>,
+	) {
+		// tiled, fullscreen and maximized windows don't get to decide their own size
+		let workspaces = &self.mayland.workspaces;
+		let tiled = (workspaces.window_for_surface(&window))
+			.is_some_and(|mapped| workspaces.is_tiled(mapped) || workspaces.window_mode(mapped).is_some());
+
+		if tiled {
+			let _ = window.configure(None);
+			return;
+		}
+
+		// windows don't get to move themselves around either
+		let mut geometry = window.geometry();
+		if let Some(w) = w {
+			geometry.size.w = w as i32;
+		}
+		if let Some(h) = h {
+			geometry.size.h = h as i32;
+		}
+
+		let _ = window.configure(geometry);
+	}
+
+	fn property_notify(&mut self, _xwm: XwmId, window: X11Surface, property: WmWindowProperty) {
+		if !matches!(property, WmWindowProperty::Title | WmWindowProperty::Class) {
+			return;
+		}
+
+		let Some(mapped) = self.mayland.workspaces.window_for_surface(&window) else {
+			return;
+		};
+
+		mapped.recompute_windowrules(&self.mayland.config.windowrules);
+
+		if matches!(property, WmWindowProperty::Title) {
+			let window = mayland_comm::workspace::Window::from(mapped);
+			self.mayland
+				.may_socket
+				.send_event(Event::WindowTitleChanged(window));
+		}
+	}
+
+	fn configure_notify(
+		&mut self,
+		_xwm: XwmId,
+		_window: X11Surface,
+		_geometry: Rectangle<i32, Logical>,
+		_above: Option<u32>,
+	) {
+		self.mayland.queue_redraw_all();
+	}
+
+	fn fullscreen_request(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.x11_mode_request(window, WindowMode::Fullscreen, true);
+	}
+
+	fn unfullscreen_request(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.x11_mode_request(window, WindowMode::Fullscreen, false);
+	}
+
+	fn maximize_request(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.x11_mode_request(window, WindowMode::Maximized, true);
+	}
+
+	fn unmaximize_request(&mut self, _xwm: XwmId, window: X11Surface) {
+		self.x11_mode_request(window, WindowMode::Maximized, false);
+	}
+
+	fn resize_request(&mut self, _xwm: XwmId, window: X11Surface, _button: u32, _resize_edge: ResizeEdge) {
+		let Some(mapped) = self.mayland.workspaces.window_for_surface(&window).cloned() else {
+			return;
+		};
+
+		self.x11_resize(mapped);
+	}
+
+	fn move_request(&mut self, _xwm: XwmId, window: X11Surface, _button: u32) {
+		let Some(mapped) = self.mayland.workspaces.window_for_surface(&window).cloned() else {
+			return;
+		};
+
+		self.x11_move(mapped);
+	}
+
+	fn allow_selection_access(&mut self, xwm: XwmId, _selection: SelectionTarget) -> bool {
+		// only allow the focussed x11 window to access the selection
+		let Some(KeyboardFocusTarget::Window(window)) = self.mayland.keyboard.current_focus() else {
+			return false;
+		};
+
+		window
+			.window
+			.x11_surface()
+			.is_some_and(|surface| surface.xwm_id() == Some(xwm))
+	}
+
+	fn send_selection(&mut self, _xwm: XwmId, selection: SelectionTarget, mime_type: String, fd: OwnedFd) {
+		match selection {
+			SelectionTarget::Clipboard => {
+				if let Err(err) = request_data_device_client_selection(&self.mayland.seat, mime_type, fd) {
+					tracing::warn!("failed to send clipboard to xwayland: {:?}", err);
+				}
+			}
+			SelectionTarget::Primary => {
+				if let Err(err) = request_primary_client_selection(&self.mayland.seat, mime_type, fd) {
+					tracing::warn!("failed to send primary selection to xwayland: {:?}", err);
+				}
+			}
+		}
+	}
+
+	fn new_selection(&mut self, _xwm: XwmId, selection: SelectionTarget, mime_types: Vec<String>) {
+		let dh = &self.mayland.display_handle;
+		match selection {
+			SelectionTarget::Clipboard => set_data_device_selection(dh, &self.mayland.seat, mime_types, ()),
+			SelectionTarget::Primary => set_primary_selection(dh, &self.mayland.seat, mime_types, ()),
+		}
+	}
+
+	fn cleared_selection(&mut self, _xwm: XwmId, selection: SelectionTarget) {
+		let dh = &self.mayland.display_handle;
+		match selection {
+			SelectionTarget::Clipboard => {
+				if current_data_device_selection_userdata(&self.mayland.seat).is_some() {
+					clear_data_device_selection(dh, &self.mayland.seat);
+				}
+			}
+			SelectionTarget::Primary => {
+				if current_primary_selection_userdata(&self.mayland.seat).is_some() {
+					clear_primary_selection(dh, &self.mayland.seat);
+				}
+			}
+		}
+	}
+
+	fn disconnected(&mut self, _xwm: XwmId) {
+		tracing::warn!("xwayland disconnected");
+
+		self.mayland.xwm = None;
+		self.mayland.override_redirect.clear();
+	}
+}
+
+impl State {
+	fn x11_mode_request(&mut self, window: X11Surface, mode: WindowMode, set: bool) {
+		let Some(mapped) = self.mayland.workspaces.window_for_surface(&window).cloned() else {
+			// remember the state until the window gets mapped
+			let ret = match mode {
+				WindowMode::Fullscreen => window.set_fullscreen(set),
+				WindowMode::Maximized => window.set_maximized(set),
+			};
+
+			if let Err(err) = ret {
+				tracing::warn!("failed to set x11 window state: {:?}", err);
+			}
+
+			return;
+		};
+
+		if set {
+			self.mayland.workspaces.set_window_mode(&mapped, mode);
+		} else if self.mayland.workspaces.window_mode(&mapped) == Some(mode) {
+			self.mayland.workspaces.restore_window(&mapped);
+		}
+
+		self.reset_focus();
+		self.mayland.queue_redraw_all();
+	}
+
+	fn unmap_x11_window(&mut self, window: &X11Surface) {
+		self.mayland.override_redirect.retain(|w| w != window);
+
+		if let Some(mapped) = self.mayland.workspaces.window_for_surface(window).cloned() {
+			self.mayland.workspaces.remove_window(&mapped);
+
+			let window = mayland_comm::workspace::Window::from(&mapped);
+			self.mayland.may_socket.send_event(Event::WindowClosed(window));
+
+			self.reset_focus();
+		}
+
+		self.mayland.queue_redraw_all();
+	}
+}
+
+impl XWaylandShellHandler for State {
+	fn xwayland_shell_state(&mut self) -> &mut XWaylandShellState {
+		&mut self.mayland.xwayland_shell_state
+	}
+}
+
+delegate_xwayland_shell!(State);
@@ -1,18 +1,27 @@
 use crate::{
 	Args,
-	backend::{Backend, udev::Udev, winit::Winit},
+	backend::{Backend, headless::Headless, udev::Udev, winit::Winit},
 	comm::MaySocket,
 	cursor::{Cursor, RenderCursor},
-	input::{apply_libinput_settings, device::InputDevice},
+	input::{SwipeGesture, apply_libinput_settings, device::InputDevice},
 	layout::workspace::WorkspaceManager,
+	protocols::{
+		foreign_toplevel::ForeignToplevelManagerState, output_management::OutputManagementState,
+		output_power::OutputPowerManagementState, screencopy::ScreencopyState,
+	},
 	render::MaylandRenderElements,
-	shell::{focus::KeyboardFocusTarget, window::UnmappedSurface},
-	utils::output_size,
+	screenshot::Selection,
+	shell::{
+		focus::KeyboardFocusTarget,
+		session_lock::{LOCKED_COLOR, LockState},
+		window::UnmappedSurface,
+	},
+	utils::{output_size, send_scale_transform},
 };
 use calloop::futures::Scheduler;
 use indexmap::IndexSet;
-use mayland_comm::MAYLAND_SOCKET_VAR;
-use mayland_config::{Config, bind::CompMod};
+use mayland_comm::{Event, MAYLAND_SOCKET_VAR};
+use mayland_config::{Config, bind::CompMod, outputs::OutputInfo};
 use smithay::{
 	backend::{
 		input::Keycode,
@@ -29,31 +38,39 @@ use smithay::{
 	desktop::{
 		LayerSurface, PopupManager, layer_map_for_output,
 		utils::{
-			OutputPresentationFeedback, surface_presentation_feedback_flags_from_states,
-			surface_primary_scanout_output,
+			OutputPresentationFeedback, send_frames_surface_tree,
+			surface_presentation_feedback_flags_from_states, surface_primary_scanout_output,
 		},
 	},
-	input::{Seat, SeatState, keyboard::KeyboardHandle, pointer::PointerHandle},
+	input::{Seat, SeatState, keyboard::KeyboardHandle, pointer::PointerHandle, touch::TouchHandle},
 	output::Output,
 	reexports::{
 		calloop::{EventLoop, Interest, LoopHandle, LoopSignal, Mode, PostAction, generic::Generic},
 		wayland_server::{
 			Display, DisplayHandle,
 			backend::{ClientData, GlobalId},
+			protocol::wl_surface::WlSurface,
 		},
 	},
 	utils::{Clock, IsAlive, Logical, Monotonic, Point},
 	wayland::{
-		compositor::{CompositorClientState, CompositorState},
+		compositor::{CompositorClientState, CompositorState, TraversalAction, with_surface_tree_downward},
 		cursor_shape::CursorShapeManagerState,
 		dmabuf::DmabufState,
+		foreign_toplevel_list::ForeignToplevelListState,
+		fractional_scale::FractionalScaleManagerState,
+		idle_inhibit::IdleInhibitManagerState,
+		idle_notify::IdleNotifierState,
 		output::OutputManagerState,
+		pointer_gestures::PointerGesturesState,
 		presentation::PresentationState,
 		relative_pointer::RelativePointerManagerState,
+		seat::WaylandFocus,
 		selection::{
 			data_device::DataDeviceState, primary_selection::PrimarySelectionState,
 			wlr_data_control::DataControlState,
 		},
+		session_lock::{LockSurface, SessionLockManagerState},
 		shell::{
 			wlr_layer::WlrLayerShellState,
 			xdg::{XdgShellState, decoration::XdgDecorationState},
@@ -62,7 +79,9 @@ use smithay::{
 		socket::ListeningSocketSource,
 		tablet_manager::TabletManagerState,
 		viewporter::ViewporterState,
+		xwayland_shell::XWaylandShellState,
 	},
+	xwayland::{X11Surface, X11Wm},
 };
 use std::{
 	collections::{HashMap, HashSet},
@@ -72,6 +91,7 @@ use std::{
 };
 
 mod handlers;
+mod idle;
 mod pointer;
 
 pub struct State {
@@ -86,7 +106,14 @@ impl State {
 		args: &Args,
 	) -> Result<Self, mayland_config::Error> {
 		let has_display = std::env::var("WAYLAND_DISPLAY").is_ok() || std::env::var("DISPLAY").is_ok();
-		let mut state = if has_display {
+		let mut state = if args.headless {
+			let mut mayland = Mayland::new(event_loop, display, args, CompMod::Meta)?;
+
+			let headless = Headless::init(&mut mayland);
+			let backend = Backend::Headless(headless);
+
+			State { backend, mayland }
+		} else if has_display {
 			let mut mayland = Mayland::new(event_loop, display, args, CompMod::Alt)?;
 
 			let winit = Winit::init(&mut mayland);
@@ -161,7 +188,8 @@ impl State {
 		}
 
 		if prev.output != self.mayland.config.output {
-			self.backend.reload_output_config(&mut self.mayland);
+			// errors are already logged
+			let _ = self.backend.reload_output_config(&mut self.mayland);
 		}
 
 		if prev.cursor != self.mayland.config.cursor {
@@ -190,6 +218,7 @@ impl State {
 			}
 		}
 
+		self.mayland.may_socket.send_event(Event::ConfigReloaded);
 		self.mayland.queue_redraw_all();
 	}
 
@@ -197,6 +226,9 @@ impl State {
 		// refresh workspaces and popups
 		self.mayland.workspaces.refresh();
 		self.mayland.popups.cleanup();
+		self.mayland.refresh_idle_inhibit();
+		self.mayland.refresh_foreign_toplevels();
+		self.refresh_output_management();
 
 		// redraw the queued outputs
 		self.mayland.redraw_all_queued(&mut self.backend);
@@ -206,6 +238,89 @@ impl State {
 		self.mayland.unmapped_windows.retain(|window| window.alive());
 		self.mayland.unmapped_layers.retain(|(layer, _)| layer.alive());
 	}
+
+	/// turn the [`Output`] on or off
+	pub fn set_output_power(&mut self, output: &Output, powered: bool) {
+		let Some(output_state) = self.mayland.output_state.get_mut(output) else {
+			return;
+		};
+
+		if output_state.powered == powered {
+			return;
+		}
+		output_state.powered = powered;
+
+		self.backend.set_output_power(&mut self.mayland, output, powered);
+		self.mayland.output_power_state.mode_changed(output, powered);
+
+		if powered {
+			self.mayland.queue_redraw(output.clone());
+		} else {
+			// an output that is turned off never renders the lock screen
+			self.mayland.lock_output_done(Some(output));
+			// or the screencopy frames that were waiting for its next redraw
+			self.mayland.render_screencopy(&mut self.backend, output);
+		}
+	}
+
+	/// turn all outputs on or off
+	pub fn set_outputs_power(&mut self, powered: bool) {
+		let outputs = self.mayland.output_state.keys().cloned().collect::<Vec<_>>();
+		for output in outputs {
+			self.set_output_power(&output, powered);
+		}
+	}
+
+	/// replace the output config until the next config reload
+	///
+	/// the previous output config is restored if the new one can't be applied
+	pub fn override_output_config(&mut self, outputs: mayland_config::Outputs) -> Result<(), String> {
+		let prev = std::mem::replace(&mut self.mayland.config.output, outputs);
+		let applied = self.backend.reload_output_config(&mut self.mayland);
+
+		// outputs that overlap aren't placed at their configured position
+		let overlapping = self.mayland.workspaces.outputs().find(|output| {
+			let output_info = output.user_data().get::<OutputInfo>().unwrap();
+			let position =
+				(self.mayland.config.output.get_output(output_info)).and_then(|conf| conf.position);
+			position.is_some_and(|[x, y]| output.current_location() != Point::new(x, y))
+		});
+
+		let err = if let Some(output) = overlapping {
+			format!("output {:?} overlaps with another output", output.name())
+		} else if !applied {
+			"failed to set the output mode".to_owned()
+		} else {
+			return Ok(());
+		};
+
+		self.mayland.config.output = prev;
+		// errors are already logged
+		let _ = self.backend.reload_output_config(&mut self.mayland);
+
+		Err(err)
+	}
+
+	/// change the config of the output with the connector name until the next config reload
+	pub fn configure_output(
+		&mut self,
+		name: &str,
+		configure: impl FnOnce(&mut mayland_config::outputs::Output),
+	) -> Result<(), mayland_comm::Error> {
+		// disabled and mirroring outputs aren't mapped, so the name is looked up in the connectors
+		let connected = self.backend.comm_outputs(&self.mayland);
+		if !connected.iter().any(|output| output.name == name) {
+			return Err(mayland_comm::Error::OutputNotFound(name.to_owned()));
+		}
+
+		let mut outputs = self.mayland.config.output.clone();
+		let mut config = outputs.get_output_by_name(name).cloned().unwrap_or_default();
+		configure(&mut config);
+		outputs.set_output(name.to_owned(), config);
+
+		self.override_output_config(outputs)
+			.map_err(mayland_comm::Error::InvalidOutputConfig)
+	}
 }
 
 #[derive(Debug)]
@@ -249,19 +364,47 @@ pub struct Mayland {
 	pub shm_state: ShmState,
 	pub cursor_shape_manager_state: CursorShapeManagerState,
 	pub relative_pointer_manager_state: RelativePointerManagerState,
+	pub pointer_gestures_state: PointerGesturesState,
 	pub viewporter_state: ViewporterState,
+	pub fractional_scale_manager_state: FractionalScaleManagerState,
+	pub xwayland_shell_state: XWaylandShellState,
+	pub session_lock_state: SessionLockManagerState,
+	pub idle_notifier_state: IdleNotifierState<State>,
+	pub idle_inhibit_manager_state: IdleInhibitManagerState,
+	pub output_power_state: OutputPowerManagementState,
+	pub output_management_state: OutputManagementState,
+	pub screencopy_state: ScreencopyState,
+	pub foreign_toplevel_state: ForeignToplevelManagerState,
+	pub foreign_toplevel_list_state: ForeignToplevelListState,
+
+	// idle
+	pub idle_inhibitors: HashSet<WlSurface>,
+
+	// session lock
+	pub lock_state: LockState,
+
+	// screenshot
+	pub screenshot_selection: Option<Selection>,
+
+	// xwayland
+	pub xwm: Option<X11Wm>,
+	pub override_redirect: Vec<X11Surface>,
 
 	// input
 	pub devices: IndexSet<InputDevice>,
 	pub pointer: PointerHandle<State>,
 	pub keyboard: KeyboardHandle<State>,
+	pub touch: TouchHandle<State>,
 	pub cursor: Cursor,
 	pub tablet_cursor_location: Option<Point<f64, Logical>>,
+	pub dnd_icon: Option<DndIcon>,
 
 	pub may_socket: MaySocket,
 
 	pub comp_mod: CompMod,
 	pub suppressed_keys: HashSet<Keycode>,
+	pub scroll_v120: (f64, f64),
+	pub swipe_gesture: Option<SwipeGesture>,
 }
 
 #[derive(Debug)]
@@ -275,6 +418,22 @@ pub struct OutputState {
 	///
 	/// apparently it also avoids damage tracking issues
 	pub background: SolidColorBuffer,
+	/// the output is turned on, outputs that are turned off don't get redrawn
+	pub powered: bool,
+	/// the lock surface of the lock client for this output
+	pub lock_surface: Option<LockSurface>,
+	/// shown instead of the desktop if the lock client crashed
+	pub locked: SolidColorBuffer,
+}
+
+/// the icon of an ongoing drag-and-drop operation
+#[derive(Debug)]
+pub struct DndIcon {
+	pub surface: WlSurface,
+	/// the offset of the icon to the drag location, moved by `wl_surface.offset`
+	pub offset: Point<i32, Logical>,
+	/// the location of the touch point for touch drags, `None` for pointer drags
+	pub touch_location: Option<Point<f64, Logical>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -335,16 +494,28 @@ impl Mayland {
 		args: &Args,
 		comp_mod: CompMod,
 	) -> Result<Self, mayland_config::Error> {
-		let loop_handle = event_loop.handle();
-
 		let (config, rx) = Config::init(comp_mod, args.config.clone())?;
-		loop_handle
+		event_loop
+			.handle()
 			.insert_source(rx, |event, (), state| match event {
 				calloop::channel::Event::Msg(config) => state.reload_config(config),
 				calloop::channel::Event::Closed => (),
 			})
 			.unwrap();
 
+		let mayland = Mayland::with_config(event_loop, display, config, comp_mod);
+		Ok(mayland)
+	}
+
+	/// create [`Mayland`] with an already loaded config, without watching the config file
+	pub fn with_config(
+		event_loop: &EventLoop<'static, State>,
+		display: Display<State>,
+		config: Config,
+		comp_mod: CompMod,
+	) -> Self {
+		let loop_handle = event_loop.handle();
+
 		let mut environment = HashMap::new();
 
 		let display_handle = display.handle();
@@ -378,7 +549,18 @@ impl Mayland {
 		let shm_state = ShmState::new::<State>(&display_handle, Vec::new());
 		let cursor_shape_manager_state = CursorShapeManagerState::new::<State>(&display_handle);
 		let relative_pointer_manager_state = RelativePointerManagerState::new::<State>(&display_handle);
+		let pointer_gestures_state = PointerGesturesState::new::<State>(&display_handle);
 		let viewporter_state = ViewporterState::new::<State>(&display_handle);
+		let fractional_scale_manager_state = FractionalScaleManagerState::new::<State>(&display_handle);
+		let xwayland_shell_state = XWaylandShellState::new::<State>(&display_handle);
+		let session_lock_state = SessionLockManagerState::new::<State, _>(&display_handle, |_| true);
+		let idle_notifier_state = IdleNotifierState::new(&display_handle, loop_handle.clone());
+		let idle_inhibit_manager_state = IdleInhibitManagerState::new::<State>(&display_handle);
+		let output_power_state = OutputPowerManagementState::new(&display_handle);
+		let output_management_state = OutputManagementState::new(&display_handle);
+		let screencopy_state = ScreencopyState::new(&display_handle);
+		let foreign_toplevel_state = ForeignToplevelManagerState::new(&display_handle);
+		let foreign_toplevel_list_state = ForeignToplevelListState::new::<State>(&display_handle);
 
 		let devices = IndexSet::new();
 		let keyboard = seat
@@ -389,6 +571,7 @@ impl Mayland {
 			)
 			.unwrap();
 		let pointer = seat.add_pointer();
+		let touch = seat.add_touch();
 		let cursor = Cursor::new(&config.cursor, &mut environment);
 
 		let may_socket = MaySocket::init(&loop_handle, &socket_name);
@@ -398,7 +581,7 @@ impl Mayland {
 			may_socket.path.clone().into_os_string().into_string().unwrap(),
 		);
 
-		let mayland = Mayland {
+		Mayland {
 			config,
 			environment,
 
@@ -435,21 +618,43 @@ impl Mayland {
 			shm_state,
 			cursor_shape_manager_state,
 			relative_pointer_manager_state,
+			pointer_gestures_state,
 			viewporter_state,
+			fractional_scale_manager_state,
+			xwayland_shell_state,
+			session_lock_state,
+			idle_notifier_state,
+			idle_inhibit_manager_state,
+			output_power_state,
+			output_management_state,
+			screencopy_state,
+			foreign_toplevel_state,
+			foreign_toplevel_list_state,
+
+			idle_inhibitors: HashSet::new(),
+
+			lock_state: LockState::Unlocked,
+
+			screenshot_selection: None,
+
+			xwm: None,
+			override_redirect: Vec::new(),
 
 			devices,
 			pointer,
 			keyboard,
+			touch,
 			cursor,
 			tablet_cursor_location: None,
+			dnd_icon: None,
 
 			may_socket,
 
 			comp_mod,
 			suppressed_keys: HashSet::new(),
-		};
-
-		Ok(mayland)
+			scroll_v120: (0., 0.),
+			swipe_gesture: None,
+		}
 	}
 }
 
@@ -469,8 +674,14 @@ impl Mayland {
 			global: output.create_global::<State>(&self.display_handle),
 			queued: QueueState::Idle,
 			background: SolidColorBuffer::new(size, background_color),
+			powered: true,
+			lock_surface: None,
+			locked: SolidColorBuffer::new(size, LOCKED_COLOR),
 		};
 
+		self.may_socket.send_event(Event::OutputAdded(output.name()));
+		self.output_management_state.outputs_changed();
+
 		let prev = self.output_state.insert(output, state);
 		assert!(prev.is_none(), "output was already tracked");
 	}
@@ -479,6 +690,14 @@ impl Mayland {
 		let state = self.output_state.remove(output).unwrap();
 		self.display_handle.remove_global::<State>(state.global);
 
+		self.may_socket.send_event(Event::OutputRemoved(output.name()));
+
+		// don't wait for the output to render the lock screen
+		self.lock_output_done(Some(output));
+		self.output_power_state.output_removed(output);
+		self.output_management_state.outputs_changed();
+		self.screencopy_output_removed(output);
+
 		if let Some(relocate) = self.workspaces.remove_output(&self.config.output, output) {
 			self.loop_handle.insert_idle(move |state| {
 				state.relocate(relocate);
@@ -504,6 +723,9 @@ impl Mayland {
 		let size = output_size(output);
 		let output_state = self.output_state.get_mut(output).unwrap();
 		output_state.background.resize(size);
+		output_state.locked.resize(size);
+
+		self.resize_lock_surface(output);
 	}
 
 	/// reconfigure outputs in the output space and relocate the cursor if
@@ -512,6 +734,8 @@ impl Mayland {
 	/// you can give it an output config to use, otherwise it'll fall back to
 	/// the output config in [`Mayland::config`]
 	pub fn reconfigure_outputs(&mut self) {
+		self.output_management_state.outputs_changed();
+
 		if let Some(relocate) = self.workspaces.reconfigure_outputs(&self.config.output) {
 			self.loop_handle.insert_idle(move |state| {
 				state.relocate(relocate);
@@ -534,7 +758,7 @@ impl Mayland {
 		while let Some((output, _)) = self
 			.output_state
 			.iter()
-			.find(|(_, state)| state.queued.is_queued())
+			.find(|(_, state)| state.powered && state.queued.is_queued())
 		{
 			let output = output.clone();
 			self.redraw(backend, &output);
@@ -545,18 +769,56 @@ impl Mayland {
 		let output_state = self.output_state.get_mut(output).unwrap();
 		output_state.queued.idle();
 
-		let renderer = backend.renderer();
-		let elements = self.elements(renderer, output);
+		self.send_scale_transform(output);
+
+		let elements = match backend.renderer() {
+			Some(renderer) => self.elements(renderer, output, true),
+			None => Vec::new(),
+		};
+
+		// only confirm the lock once the lock screen is queued for the output
+		if backend.render(self, output, &elements) {
+			self.lock_output_done(Some(output));
+		}
+		self.render_screencopy(backend, output);
 
-		backend.render(self, output, &elements);
 		self.display_handle.flush_clients().unwrap();
 	}
 
-	fn elements(&mut self, renderer: &mut GlowRenderer, output: &Output) -> Vec<MaylandRenderElements> {
+	/// the elements of the [`Output`], with or without the cursor
+	pub fn elements(
+		&mut self,
+		renderer: &mut GlowRenderer,
+		output: &Output,
+		cursor: bool,
+	) -> Vec<MaylandRenderElements> {
 		let mut elements = Vec::new();
 
-		let pointer_element = self.pointer_element(renderer, output);
-		elements.extend(pointer_element);
+		if cursor {
+			let pointer_element = self.pointer_element(renderer, output);
+			elements.extend(pointer_element);
+		}
+
+		// only show the lock screen while locked
+		if self.is_locked() {
+			elements.extend(self.lock_elements(renderer, output));
+			return elements;
+		}
+
+		// the icon of a drag that started before locking stays hidden with the windows
+		if cursor {
+			let dnd_icon_elements = self.dnd_icon_elements(renderer, output);
+			elements.extend(dnd_icon_elements);
+		}
+
+		if let Some(selection) = &self.screenshot_selection
+			&& selection.output == *output
+		{
+			elements.push(selection.element());
+		}
+
+		let override_redirect_elements = self.override_redirect_elements(renderer, output);
+		elements.extend(override_redirect_elements);
 
 		let focus = self.keyboard.current_focus().and_then(|focus| match focus {
 			KeyboardFocusTarget::Window(mapped) => Some(mapped),
@@ -566,12 +828,14 @@ impl Mayland {
 		let workspace_elements = self.workspaces.render_elements(renderer, output, focus);
 		elements.extend(workspace_elements);
 
+		// the background buffer is sized in logical coordinates
+		let scale = output.current_scale().fractional_scale();
 		let output_state = &self.output_state[output];
 		elements.push(MaylandRenderElements::Solid(
 			SolidColorRenderElement::from_buffer(
 				&output_state.background,
 				(0, 0),
-				1.0,
+				scale,
 				1.0,
 				Kind::Unspecified,
 			),
@@ -591,21 +855,34 @@ impl Mayland {
 			.tablet_cursor_location
 			.unwrap_or_else(|| self.pointer.current_location());
 		let pointer_pos = pointer_pos - output_position.to_f64();
-		let pointer_pos = pointer_pos.to_physical(1.);
 
-		let render_cursor = self.cursor.get_render_cursor(1);
+		let scale = output.current_scale();
+		let pointer_pos = pointer_pos.to_physical(scale.fractional_scale());
+
+		let render_cursor = self.cursor.get_render_cursor(scale.integer_scale());
 		match render_cursor {
 			RenderCursor::Hidden => Vec::new(),
 			RenderCursor::Surface { surface, hotspot } => {
-				let pointer_pos = pointer_pos.to_i32_round() - hotspot.to_physical(1);
+				let hotspot = hotspot.to_physical_precise_round(scale.fractional_scale());
+				let pointer_pos = pointer_pos.to_i32_round() - hotspot;
 
-				render_elements_from_surface_tree(renderer, &surface, pointer_pos, 1., 1., Kind::Cursor)
+				render_elements_from_surface_tree(
+					renderer,
+					&surface,
+					pointer_pos,
+					scale.fractional_scale(),
+					1.,
+					Kind::Cursor,
+				)
 			}
 			RenderCursor::Named(xcursor) => {
 				let frame = xcursor.frame(self.start_time.elapsed());
 
-				let hotspot = frame.hotspot();
-				let pointer_pos = pointer_pos - hotspot.to_f64();
+				// the hotspot is in buffer pixels of the cursor image
+				let hotspot = (frame.hotspot().to_f64())
+					.to_logical(f64::from(frame.scale()))
+					.to_physical(scale.fractional_scale());
+				let pointer_pos = pointer_pos - hotspot;
 
 				let buffer = frame.buffer();
 				let texture = MemoryRenderBufferRenderElement::from_buffer(
@@ -625,6 +902,22 @@ impl Mayland {
 		}
 	}
 
+	fn dnd_icon_elements(&self, renderer: &mut GlowRenderer, output: &Output) -> Vec<MaylandRenderElements> {
+		let Some(icon) = &self.dnd_icon else {
+			return Vec::new();
+		};
+
+		let output_position = self.workspaces.output_position(output).unwrap();
+
+		let location = (icon.touch_location).unwrap_or_else(|| self.pointer.current_location());
+		let location = location - output_position.to_f64() + icon.offset.to_f64();
+
+		let scale = output.current_scale().fractional_scale();
+		let location = location.to_physical(scale).to_i32_round();
+
+		render_elements_from_surface_tree(renderer, &icon.surface, location, scale, 1., Kind::Unspecified)
+	}
+
 	pub fn presentation_feedback(
 		&self,
 		output: &Output,
@@ -656,6 +949,47 @@ impl Mayland {
 		output_presentation_feedback
 	}
 
+	/// send the scale and transform of the output to all surfaces on it
+	fn send_scale_transform(&self, output: &Output) {
+		let scale = output.current_scale();
+		let transform = output.current_transform();
+
+		for mapped in self.workspaces.windows_for_output(output) {
+			mapped.window.with_surfaces(|surface, data| {
+				send_scale_transform(surface, data, scale, transform);
+			});
+		}
+
+		let layer_map = layer_map_for_output(output);
+		for layer_surface in layer_map.layers() {
+			layer_surface.with_surfaces(|surface, data| {
+				send_scale_transform(surface, data, scale, transform);
+			});
+		}
+
+		for window in &self.override_redirect {
+			if let Some(surface) = window.wl_surface() {
+				with_surface_tree_downward(
+					&surface,
+					(),
+					|_, _, ()| TraversalAction::DoChildren(()),
+					|surface, data, ()| send_scale_transform(surface, data, scale, transform),
+					|_, _, ()| true,
+				);
+			}
+		}
+
+		if let Some(lock_surface) = self.lock_surface(output) {
+			with_surface_tree_downward(
+				lock_surface.wl_surface(),
+				(),
+				|_, _, ()| TraversalAction::DoChildren(()),
+				|surface, data, ()| send_scale_transform(surface, data, scale, transform),
+				|_, _, ()| true,
+			);
+		}
+	}
+
 	pub fn send_frame_callbacks(&self, output: &Output) {
 		for mapped in self.workspaces.windows_for_output(output) {
 			mapped
@@ -671,6 +1005,28 @@ impl Mayland {
 				Some(output.clone())
 			});
 		}
+
+		self.send_override_redirect_frames(output);
+
+		if let Some(lock_surface) = self.lock_surface(output) {
+			send_frames_surface_tree(
+				lock_surface.wl_surface(),
+				output,
+				self.start_time.elapsed(),
+				Some(Duration::ZERO),
+				|_, _| Some(output.clone()),
+			);
+		}
+
+		if let Some(icon) = &self.dnd_icon {
+			send_frames_surface_tree(
+				&icon.surface,
+				output,
+				self.start_time.elapsed(),
+				Some(Duration::ZERO),
+				|_, _| Some(output.clone()),
+			);
+		}
 	}
 }
 
@@ -1,25 +1,34 @@
-use super::State;
+use super::{DndIcon, State};
 use crate::shell::focus::{KeyboardFocusTarget, PointerFocusTarget};
+use mayland_comm::Event;
 use smithay::{
 	backend::{allocator::dmabuf::Dmabuf, input::TabletToolDescriptor},
-	delegate_cursor_shape, delegate_data_control, delegate_data_device, delegate_dmabuf, delegate_output,
-	delegate_primary_selection, delegate_seat, delegate_tablet_manager, delegate_viewporter,
-	delegate_xdg_decoration,
+	delegate_cursor_shape, delegate_data_control, delegate_data_device, delegate_dmabuf,
+	delegate_fractional_scale, delegate_output, delegate_primary_selection, delegate_seat,
+	delegate_tablet_manager, delegate_viewporter, delegate_xdg_decoration,
+	desktop::utils::surface_primary_scanout_output,
 	input::{
 		Seat, SeatHandler, SeatState,
-		dnd::{self, DnDGrab, DndGrabHandler, GrabType},
+		dnd::{self, DnDGrab, DndGrabHandler, DndTarget, GrabType},
 		pointer::{CursorImageStatus, Focus},
 	},
+	output::Output,
 	reexports::{
 		wayland_protocols::xdg::decoration::zv1::server::zxdg_toplevel_decoration_v1::Mode as DecorationMode,
-		wayland_server::{Resource, protocol::wl_surface::WlSurface},
+		wayland_server::{
+			Resource,
+			protocol::{wl_output::WlOutput, wl_surface::WlSurface},
+		},
 	},
+	utils::{Logical, Point},
 	wayland::{
+		compositor::{get_parent, with_states},
 		dmabuf::{DmabufGlobal, DmabufHandler, DmabufState, ImportNotifier},
+		fractional_scale::{FractionalScaleHandler, with_fractional_scale},
 		output::OutputHandler,
 		seat::WaylandFocus,
 		selection::{
-			SelectionHandler,
+			SelectionHandler, SelectionSource, SelectionTarget,
 			data_device::{DataDeviceHandler, DataDeviceState, WaylandDndGrabHandler, set_data_device_focus},
 			primary_selection::{PrimarySelectionHandler, PrimarySelectionState, set_primary_focus},
 			wlr_data_control::{DataControlHandler, DataControlState},
@@ -28,6 +37,7 @@ use smithay::{
 		tablet_manager::TabletSeatHandler,
 	},
 };
+use std::os::fd::OwnedFd;
 
 impl SeatHandler for State {
 	type KeyboardFocus = KeyboardFocusTarget;
@@ -50,6 +60,12 @@ impl SeatHandler for State {
 		let client = wl_surface.and_then(|s| dh.get_client(s.id()).ok());
 		set_data_device_focus(dh, seat, client.clone());
 		set_primary_focus(dh, seat, client);
+
+		let window = match target {
+			Some(KeyboardFocusTarget::Window(window)) => Some(mayland_comm::workspace::Window::from(window)),
+			_ => None,
+		};
+		self.mayland.may_socket.send_event(Event::WindowFocused(window));
 	}
 }
 
@@ -68,6 +84,29 @@ delegate_tablet_manager!(State);
 
 impl SelectionHandler for State {
 	type SelectionUserData = ();
+
+	fn new_selection(&mut self, ty: SelectionTarget, source: Option<SelectionSource>, _seat: Seat<Self>) {
+		if let Some(xwm) = &mut self.mayland.xwm
+			&& let Err(err) = xwm.new_selection(ty, source.map(|source| source.mime_types()))
+		{
+			tracing::warn!("failed to set xwayland selection: {:?}", err);
+		}
+	}
+
+	fn send_selection(
+		&mut self,
+		ty: SelectionTarget,
+		mime_type: String,
+		fd: OwnedFd,
+		_seat: Seat<Self>,
+		_user_data: &Self::SelectionUserData,
+	) {
+		if let Some(xwm) = &mut self.mayland.xwm
+			&& let Err(err) = xwm.send_selection(ty, mime_type, fd, self.mayland.loop_handle.clone())
+		{
+			tracing::warn!("failed to send selection to xwayland: {:?}", err);
+		}
+	}
 }
 
 impl DataDeviceHandler for State {
@@ -76,12 +115,24 @@ impl DataDeviceHandler for State {
 	}
 }
 
-impl DndGrabHandler for State {}
+impl DndGrabHandler for State {
+	fn dropped(
+		&mut self,
+		_target: Option<DndTarget<'_, Self>>,
+		_validated: bool,
+		_seat: Seat<Self>,
+		_location: Point<f64, Logical>,
+	) {
+		self.mayland.dnd_icon = None;
+		self.mayland.queue_redraw_all();
+	}
+}
+
 impl WaylandDndGrabHandler for State {
 	fn dnd_requested<S: dnd::Source>(
 		&mut self,
 		source: S,
-		_icon: Option<WlSurface>,
+		icon: Option<WlSurface>,
 		seat: Seat<Self>,
 		serial: smithay::utils::Serial,
 		r#type: smithay::input::dnd::GrabType,
@@ -91,18 +142,44 @@ impl WaylandDndGrabHandler for State {
 				let ptr = seat.get_pointer().unwrap();
 				let start_data = ptr.grab_start_data().unwrap();
 
+				self.mayland.dnd_icon = icon.map(|surface| DndIcon {
+					surface,
+					offset: Point::default(),
+					touch_location: None,
+				});
+
 				let grab = DnDGrab::new_pointer(&self.mayland.display_handle, start_data, source, seat);
 				ptr.set_grab(self, grab, serial, Focus::Keep);
 			}
-			// TODO: touch handling
-			GrabType::Touch => {}
+			GrabType::Touch => {
+				let touch = seat.get_touch().unwrap();
+				let Some(start_data) = touch.grab_start_data() else {
+					return;
+				};
+
+				// the icon follows the touch point that started the drag
+				self.mayland.dnd_icon = icon.map(|surface| DndIcon {
+					surface,
+					offset: Point::default(),
+					touch_location: Some(start_data.location),
+				});
+
+				let grab = DnDGrab::new_touch(&self.mayland.display_handle, start_data, source, seat);
+				touch.set_grab(self, grab, serial);
+			}
 		}
+
+		self.mayland.queue_redraw_all();
 	}
 }
 
 delegate_data_device!(State);
 
-impl OutputHandler for State {}
+impl OutputHandler for State {
+	fn output_bound(&mut self, output: Output, wl_output: WlOutput) {
+		self.mayland.foreign_toplevel_output_bound(&output, &wl_output);
+	}
+}
 
 delegate_output!(State);
 
@@ -162,3 +239,28 @@ impl XdgDecorationHandler for State {
 delegate_xdg_decoration!(State);
 
 delegate_viewporter!(State);
+
+impl FractionalScaleHandler for State {
+	fn new_fractional_scale(&mut self, surface: WlSurface) {
+		let mut root = surface.clone();
+		while let Some(parent) = get_parent(&root) {
+			root = parent;
+		}
+
+		// surfaces that haven't been rendered yet will most likely
+		// be mapped on the active output
+		let output = with_states(&root, |states| surface_primary_scanout_output(&root, states))
+			.or_else(|| self.mayland.workspaces.active_output().cloned());
+
+		if let Some(output) = output {
+			let scale = output.current_scale().fractional_scale();
+			with_states(&surface, |states| {
+				with_fractional_scale(states, |fractional| {
+					fractional.set_preferred_scale(scale);
+				});
+			});
+		}
+	}
+}
+
+delegate_fractional_scale!(State);
@@ -0,0 +1,69 @@
+use super::{Mayland, State};
+use smithay::{
+	delegate_idle_inhibit, delegate_idle_notify,
+	desktop::{WindowSurfaceType, layer_map_for_output},
+	reexports::wayland_server::protocol::wl_surface::WlSurface,
+	utils::IsAlive,
+	wayland::{
+		compositor::get_parent,
+		idle_inhibit::IdleInhibitHandler,
+		idle_notify::{IdleNotifierHandler, IdleNotifierState},
+	},
+};
+
+impl IdleNotifierHandler for State {
+	fn idle_notifier_state(&mut self) -> &mut IdleNotifierState<Self> {
+		&mut self.mayland.idle_notifier_state
+	}
+}
+
+delegate_idle_notify!(State);
+
+impl IdleInhibitHandler for State {
+	fn inhibit(&mut self, surface: WlSurface) {
+		self.mayland.idle_inhibitors.insert(surface);
+	}
+
+	fn uninhibit(&mut self, surface: WlSurface) {
+		self.mayland.idle_inhibitors.remove(&surface);
+	}
+}
+
+delegate_idle_inhibit!(State);
+
+impl Mayland {
+	/// inhibit idle while any surface with an idle inhibitor is visible
+	pub fn refresh_idle_inhibit(&mut self) {
+		self.idle_inhibitors.retain(|surface| surface.alive());
+
+		let is_inhibited = self.idle_inhibitors.iter().any(|surface| {
+			let mut root = surface.clone();
+			while let Some(parent) = get_parent(&root) {
+				root = parent;
+			}
+
+			self.is_visible(&root)
+		});
+
+		self.idle_notifier_state.set_is_inhibited(is_inhibited);
+	}
+
+	/// is the root [`WlSurface`] shown on any output?
+	fn is_visible(&self, surface: &WlSurface) -> bool {
+		// only the lock surfaces are visible while locked
+		if self.is_locked() {
+			return (self.workspaces.outputs()).any(|output| {
+				self.lock_surface(output)
+					.is_some_and(|lock| lock.wl_surface() == surface)
+			});
+		}
+
+		self.workspaces.outputs().any(|output| {
+			let mut windows = self.workspaces.windows_for_output(output);
+			windows.any(|window| window == surface)
+				|| (layer_map_for_output(output))
+					.layer_for_surface(surface, WindowSurfaceType::TOPLEVEL)
+					.is_some()
+		})
+	}
+}
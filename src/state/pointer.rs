@@ -1,9 +1,10 @@
 use super::State;
-use crate::{layout::Relocate, shell::focus::KeyboardFocusTarget};
+use crate::{layout::Relocate, shell::focus::KeyboardFocusTarget, utils::RectExt};
 use smithay::{
 	delegate_pointer_gestures, delegate_relative_pointer,
 	desktop::LayerSurface,
 	input::pointer::MotionEvent,
+	output::Output,
 	utils::{Logical, Point, SERIAL_COUNTER},
 };
 
@@ -23,6 +24,17 @@ impl State {
 		self.mayland.queue_redraw_all();
 	}
 
+	/// move the pointer to the center of the [`Output`], which makes it the active output
+	pub fn focus_output(&mut self, output: &Output) {
+		let Some(output_geometry) = self.mayland.workspaces.output_geometry(output) else {
+			return;
+		};
+
+		let location = output_geometry.center();
+		self.relocate(Relocate::Absolute(location));
+		self.update_active_output(location.to_f64(), SERIAL_COUNTER.next_serial());
+	}
+
 	pub fn move_pointer(&mut self, location: Point<f64, Logical>) {
 		let pointer = self.mayland.pointer.clone();
 		let under = self.surface_under(location);
@@ -43,6 +55,10 @@ impl State {
 	}
 
 	pub fn focus_layer_surface(&mut self, surface: LayerSurface) {
+		if self.mayland.is_locked() {
+			return;
+		}
+
 		let serial = SERIAL_COUNTER.next_serial();
 		let keyboard = self.mayland.keyboard.clone();
 
@@ -52,6 +68,12 @@ impl State {
 
 	/// resets the keyboard and pointer focus
 	pub fn reset_focus(&mut self) {
+		if self.mayland.is_locked() {
+			self.focus_lock_surface();
+			self.refresh_pointer_focus();
+			return;
+		}
+
 		let serial = SERIAL_COUNTER.next_serial();
 
 		let workspace = self.mayland.workspaces.workspace();
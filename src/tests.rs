@@ -0,0 +1,7 @@
+mod client;
+mod fixture;
+mod foreign_toplevel;
+mod layout;
+mod output_management;
+mod popup;
+mod session_lock;
@@ -0,0 +1,661 @@
+use std::{
+	fs::File,
+	os::{
+		fd::{AsFd, FromRawFd, OwnedFd},
+		unix::net::UnixStream,
+	},
+};
+use wayland_client::{
+	Connection, Dispatch, EventQueue, QueueHandle, WEnum, delegate_noop,
+	protocol::{
+		wl_buffer::WlBuffer,
+		wl_callback::{self, WlCallback},
+		wl_compositor::WlCompositor,
+		wl_output::WlOutput,
+		wl_registry::{self, WlRegistry},
+		wl_seat::WlSeat,
+		wl_shm::{self, WlShm},
+		wl_shm_pool::WlShmPool,
+		wl_surface::WlSurface,
+	},
+};
+use wayland_protocols::{
+	ext::{
+		foreign_toplevel_list::v1::client::{
+			ext_foreign_toplevel_handle_v1::{self, ExtForeignToplevelHandleV1},
+			ext_foreign_toplevel_list_v1::{self, ExtForeignToplevelListV1},
+		},
+		session_lock::v1::client::{
+			ext_session_lock_manager_v1::ExtSessionLockManagerV1,
+			ext_session_lock_surface_v1::{self, ExtSessionLockSurfaceV1},
+			ext_session_lock_v1::ExtSessionLockV1,
+		},
+	},
+	xdg::shell::client::{
+		xdg_popup::{self, XdgPopup},
+		xdg_positioner::XdgPositioner,
+		xdg_surface::{self, XdgSurface},
+		xdg_toplevel::{self, XdgToplevel},
+		xdg_wm_base::{self, XdgWmBase},
+	},
+};
+use wayland_protocols_wlr::output_management::v1::client::{
+	zwlr_output_configuration_head_v1::ZwlrOutputConfigurationHeadV1,
+	zwlr_output_configuration_v1::{self, ZwlrOutputConfigurationV1},
+	zwlr_output_head_v1::{self, ZwlrOutputHeadV1},
+	zwlr_output_manager_v1::{self, ZwlrOutputManagerV1},
+	zwlr_output_mode_v1::ZwlrOutputModeV1,
+};
+
+/// a minimal wayland client, that can create xdg toplevels with shm buffers
+pub struct TestClient {
+	connection: Connection,
+	queue: EventQueue<ClientState>,
+	qh: QueueHandle<ClientState>,
+	pub state: ClientState,
+}
+
+#[derive(Default)]
+pub struct ClientState {
+	compositor: Option<WlCompositor>,
+	shm: Option<WlShm>,
+	xdg_wm_base: Option<XdgWmBase>,
+	output: Option<WlOutput>,
+	seat: Option<WlSeat>,
+	session_lock_manager: Option<ExtSessionLockManagerV1>,
+	_toplevel_list: Option<ExtForeignToplevelListV1>,
+	output_manager: Option<ZwlrOutputManagerV1>,
+
+	pub windows: Vec<Window>,
+	pub popups: Vec<Popup>,
+	pub lock: Option<SessionLock>,
+	pub toplevels: Vec<Toplevel>,
+	pub heads: Vec<Head>,
+	/// the serial of the last `zwlr_output_manager_v1.done`
+	output_serial: u32,
+	/// the result of the last output configuration
+	pub configuration_result: Option<ConfigurationResult>,
+	sync_done: bool,
+}
+
+pub struct Window {
+	pub surface: WlSurface,
+	pub xdg_surface: XdgSurface,
+	pub toplevel: XdgToplevel,
+
+	/// the size of the last received configure
+	pub size: (i32, i32),
+	pub states: Vec<xdg_toplevel::State>,
+	pub close_requested: bool,
+}
+
+pub struct Popup {
+	_surface: WlSurface,
+	_xdg_surface: XdgSurface,
+	_popup: XdgPopup,
+
+	/// the geometry of the last received configure, relative to the parent
+	pub geometry: (i32, i32, i32, i32),
+	/// the compositor dismissed the popup
+	pub done: bool,
+}
+
+/// a window published by ext-foreign-toplevel-list
+pub struct Toplevel {
+	pub handle: ExtForeignToplevelHandleV1,
+	pub title: String,
+	pub app_id: String,
+	pub closed: bool,
+}
+
+/// an output published by wlr-output-management
+pub struct Head {
+	pub head: ZwlrOutputHeadV1,
+	pub name: String,
+	pub position: (i32, i32),
+	pub scale: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigurationResult {
+	Succeeded,
+	Failed,
+	Cancelled,
+}
+
+pub struct SessionLock {
+	pub lock: ExtSessionLockV1,
+	pub surface: WlSurface,
+
+	/// the size of the last received configure
+	pub size: (u32, u32),
+}
+
+impl TestClient {
+	pub fn new(stream: UnixStream) -> Self {
+		let connection = Connection::from_socket(stream).unwrap();
+		let queue = connection.new_event_queue();
+		let qh = queue.handle();
+
+		connection.display().get_registry(&qh, ());
+
+		TestClient {
+			connection,
+			queue,
+			qh,
+			state: ClientState::default(),
+		}
+	}
+
+	/// flush outgoing requests and dispatch all incoming events
+	pub fn dispatch(&mut self) {
+		self.connection.flush().unwrap();
+
+		if let Some(guard) = self.queue.prepare_read() {
+			// the socket is non-blocking, so this only fails if nothing was sent yet
+			let _ = guard.read();
+		}
+
+		self.queue.dispatch_pending(&mut self.state).unwrap();
+		self.connection.flush().unwrap();
+	}
+
+	/// send a `wl_display.sync` request, check if it's done with [`TestClient::is_synced`]
+	pub fn sync(&mut self) {
+		self.state.sync_done = false;
+		self.connection.display().sync(&self.qh, ());
+	}
+
+	pub fn is_synced(&self) -> bool {
+		self.state.sync_done
+	}
+
+	/// create a new xdg toplevel and send the initial commit
+	///
+	/// returns the index of the window
+	pub fn create_window(&mut self) -> usize {
+		let idx = self.state.windows.len();
+
+		let compositor = self.state.compositor.as_ref().expect("no wl_compositor global");
+		let xdg_wm_base = self.state.xdg_wm_base.as_ref().expect("no xdg_wm_base global");
+
+		let surface = compositor.create_surface(&self.qh, ());
+		let xdg_surface = xdg_wm_base.get_xdg_surface(&surface, &self.qh, idx);
+		let toplevel = xdg_surface.get_toplevel(&self.qh, idx);
+		surface.commit();
+
+		self.state.windows.push(Window {
+			surface,
+			xdg_surface,
+			toplevel,
+
+			size: (0, 0),
+			states: Vec::new(),
+			close_requested: false,
+		});
+
+		idx
+	}
+
+	pub fn set_fixed_size(&mut self, idx: usize, w: i32, h: i32) {
+		let toplevel = &self.state.windows[idx].toplevel;
+		toplevel.set_min_size(w, h);
+		toplevel.set_max_size(w, h);
+	}
+
+	pub fn set_title(&mut self, idx: usize, title: &str) {
+		let window = &self.state.windows[idx];
+		window.toplevel.set_title(title.to_owned());
+		window.surface.commit();
+	}
+
+	pub fn set_fullscreen(&mut self, idx: usize, fullscreen: bool) {
+		let toplevel = &self.state.windows[idx].toplevel;
+		if fullscreen {
+			toplevel.set_fullscreen(None);
+		} else {
+			toplevel.unset_fullscreen();
+		}
+	}
+
+	/// attach a buffer with the last configured size and commit,
+	/// which maps the window if it wasn't mapped already
+	pub fn commit_buffer(&mut self, idx: usize) {
+		// a size of 0 means that the client can decide
+		let (w, h) = self.state.windows[idx].size;
+		let w = if w > 0 { w } else { 100 };
+		let h = if h > 0 { h } else { 100 };
+
+		let buffer = self.shm_buffer(w, h);
+		let window = &self.state.windows[idx];
+		window.surface.attach(Some(&buffer), 0, 0);
+		window.surface.damage_buffer(0, 0, w, h);
+		window.surface.commit();
+	}
+
+	pub fn destroy_window(&mut self, idx: usize) {
+		let window = &self.state.windows[idx];
+		window.toplevel.destroy();
+		window.xdg_surface.destroy();
+		window.surface.destroy();
+	}
+
+	pub fn window(&self, idx: usize) -> &Window {
+		&self.state.windows[idx]
+	}
+
+	/// create a popup for the window, with a positioner set up by the closure,
+	/// and send the initial commit
+	///
+	/// returns the index of the popup
+	pub fn create_popup(&mut self, parent: usize, position: impl FnOnce(&XdgPositioner)) -> usize {
+		self.new_popup(parent, position, false)
+	}
+
+	/// create a popup like [`TestClient::create_popup`], that grabs the input before the initial commit
+	pub fn create_grabbing_popup(&mut self, parent: usize, position: impl FnOnce(&XdgPositioner)) -> usize {
+		self.new_popup(parent, position, true)
+	}
+
+	fn new_popup(&mut self, parent: usize, position: impl FnOnce(&XdgPositioner), grab: bool) -> usize {
+		let idx = self.state.popups.len();
+
+		let compositor = self.state.compositor.as_ref().expect("no wl_compositor global");
+		let xdg_wm_base = self.state.xdg_wm_base.as_ref().expect("no xdg_wm_base global");
+
+		let positioner = xdg_wm_base.create_positioner(&self.qh, ());
+		position(&positioner);
+
+		let surface = compositor.create_surface(&self.qh, ());
+		let xdg_surface = xdg_wm_base.get_xdg_surface(&surface, &self.qh, idx);
+		let parent = &self.state.windows[parent].xdg_surface;
+		let popup = xdg_surface.get_popup(Some(parent), &positioner, &self.qh, idx);
+		positioner.destroy();
+
+		// the client doesn't track input serials, so the grab relies on the parent having focus
+		if grab {
+			let seat = self.state.seat.as_ref().expect("no wl_seat global");
+			popup.grab(seat, 0);
+		}
+		surface.commit();
+
+		self.state.popups.push(Popup {
+			_surface: surface,
+			_xdg_surface: xdg_surface,
+			_popup: popup,
+
+			geometry: (0, 0, 0, 0),
+			done: false,
+		});
+
+		idx
+	}
+
+	pub fn popup(&self, idx: usize) -> &Popup {
+		&self.state.popups[idx]
+	}
+
+	/// lock the session and create a lock surface on the first output
+	pub fn lock(&mut self) {
+		let compositor = self.state.compositor.as_ref().expect("no wl_compositor global");
+		let output = self.state.output.as_ref().expect("no wl_output global");
+		let manager =
+			(self.state.session_lock_manager.as_ref()).expect("no ext_session_lock_manager_v1 global");
+
+		let lock = manager.lock(&self.qh, ());
+		let surface = compositor.create_surface(&self.qh, ());
+		lock.get_lock_surface(&surface, output, &self.qh, ());
+
+		self.state.lock = Some(SessionLock {
+			lock,
+			surface,
+			size: (0, 0),
+		});
+	}
+
+	/// attach a buffer with the configured size to the lock surface and commit
+	pub fn commit_lock_buffer(&mut self) {
+		let (w, h) = self.state.lock.as_ref().expect("session isn't locked").size;
+		let buffer = self.shm_buffer(w as i32, h as i32);
+
+		let lock = self.state.lock.as_ref().unwrap();
+		lock.surface.attach(Some(&buffer), 0, 0);
+		lock.surface.damage_buffer(0, 0, w as i32, h as i32);
+		lock.surface.commit();
+	}
+
+	/// enable every head, configure it with the closure and apply the configuration
+	pub fn configure_outputs(&mut self, configure: impl Fn(&str, &ZwlrOutputConfigurationHeadV1)) {
+		let manager = (self.state.output_manager.as_ref()).expect("no zwlr_output_manager_v1 global");
+		let configuration = manager.create_configuration(self.state.output_serial, &self.qh, ());
+
+		for head in &self.state.heads {
+			let configuration_head = configuration.enable_head(&head.head, &self.qh, ());
+			configure(&head.name, &configuration_head);
+		}
+
+		configuration.apply();
+		self.state.configuration_result = None;
+	}
+
+	fn shm_buffer(&mut self, w: i32, h: i32) -> WlBuffer {
+		let shm = self.state.shm.as_ref().expect("no wl_shm global");
+
+		let stride = w * 4;
+		let len = stride * h;
+
+		// SAFETY: the name is a valid nul-terminated string
+		let fd = unsafe { libc::memfd_create(c"mayland-test".as_ptr(), libc::MFD_CLOEXEC) };
+		assert!(fd >= 0, "failed to create memfd");
+		// SAFETY: the fd was just created and is owned by nobody else
+		let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+		let file = File::from(fd);
+		file.set_len(len as u64).unwrap();
+
+		let pool = shm.create_pool(file.as_fd(), len, &self.qh, ());
+		let buffer = pool.create_buffer(0, w, h, stride, wl_shm::Format::Argb8888, &self.qh, ());
+		pool.destroy();
+
+		buffer
+	}
+}
+
+impl Dispatch<WlRegistry, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		registry: &WlRegistry,
+		event: wl_registry::Event,
+		_data: &(),
+		_conn: &Connection,
+		qh: &QueueHandle<Self>,
+	) {
+		if let wl_registry::Event::Global {
+			name,
+			interface,
+			version,
+		} = event
+		{
+			match &*interface {
+				"wl_compositor" => {
+					state.compositor = Some(registry.bind(name, version.min(6), qh, ()));
+				}
+				"wl_shm" => {
+					state.shm = Some(registry.bind(name, 1, qh, ()));
+				}
+				"xdg_wm_base" => {
+					state.xdg_wm_base = Some(registry.bind(name, version.min(6), qh, ()));
+				}
+				"wl_seat" if state.seat.is_none() => {
+					state.seat = Some(registry.bind(name, version.min(9), qh, ()));
+				}
+				"wl_output" if state.output.is_none() => {
+					state.output = Some(registry.bind(name, version.min(4), qh, ()));
+				}
+				"ext_session_lock_manager_v1" => {
+					state.session_lock_manager = Some(registry.bind(name, 1, qh, ()));
+				}
+				"zwlr_output_manager_v1" => {
+					state.output_manager = Some(registry.bind(name, version.min(4), qh, ()));
+				}
+				"ext_foreign_toplevel_list_v1" => {
+					state._toplevel_list = Some(registry.bind(name, 1, qh, ()));
+				}
+				_ => {}
+			}
+		}
+	}
+}
+
+impl Dispatch<WlCallback, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		_callback: &WlCallback,
+		event: wl_callback::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		if let wl_callback::Event::Done { .. } = event {
+			state.sync_done = true;
+		}
+	}
+}
+
+impl Dispatch<XdgWmBase, ()> for ClientState {
+	fn event(
+		_state: &mut Self,
+		xdg_wm_base: &XdgWmBase,
+		event: xdg_wm_base::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		if let xdg_wm_base::Event::Ping { serial } = event {
+			xdg_wm_base.pong(serial);
+		}
+	}
+}
+
+impl Dispatch<XdgSurface, usize> for ClientState {
+	fn event(
+		_state: &mut Self,
+		xdg_surface: &XdgSurface,
+		event: xdg_surface::Event,
+		_idx: &usize,
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		if let xdg_surface::Event::Configure { serial } = event {
+			xdg_surface.ack_configure(serial);
+		}
+	}
+}
+
+impl Dispatch<XdgToplevel, usize> for ClientState {
+	fn event(
+		state: &mut Self,
+		_toplevel: &XdgToplevel,
+		event: xdg_toplevel::Event,
+		idx: &usize,
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		let window = &mut state.windows[*idx];
+		match event {
+			xdg_toplevel::Event::Configure {
+				width,
+				height,
+				states,
+			} => {
+				window.size = (width, height);
+				window.states = states
+					.chunks_exact(4)
+					.map(|chunk| u32::from_ne_bytes(chunk.try_into().unwrap()))
+					.filter_map(|state| match WEnum::from(state) {
+						WEnum::Value(state) => Some(state),
+						WEnum::Unknown(_) => None,
+					})
+					.collect();
+			}
+			xdg_toplevel::Event::Close => window.close_requested = true,
+			_ => {}
+		}
+	}
+}
+
+impl Dispatch<XdgPopup, usize> for ClientState {
+	fn event(
+		state: &mut Self,
+		_popup: &XdgPopup,
+		event: xdg_popup::Event,
+		idx: &usize,
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		match event {
+			xdg_popup::Event::Configure { x, y, width, height } => {
+				state.popups[*idx].geometry = (x, y, width, height);
+			}
+			xdg_popup::Event::PopupDone => state.popups[*idx].done = true,
+			_ => {}
+		}
+	}
+}
+
+impl Dispatch<ExtSessionLockSurfaceV1, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		lock_surface: &ExtSessionLockSurfaceV1,
+		event: ext_session_lock_surface_v1::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		if let ext_session_lock_surface_v1::Event::Configure {
+			serial,
+			width,
+			height,
+		} = event
+		{
+			lock_surface.ack_configure(serial);
+			if let Some(lock) = &mut state.lock {
+				lock.size = (width, height);
+			}
+		}
+	}
+}
+
+impl Dispatch<ExtForeignToplevelListV1, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		_list: &ExtForeignToplevelListV1,
+		event: ext_foreign_toplevel_list_v1::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		if let ext_foreign_toplevel_list_v1::Event::Toplevel { toplevel } = event {
+			state.toplevels.push(Toplevel {
+				handle: toplevel,
+				title: String::new(),
+				app_id: String::new(),
+				closed: false,
+			});
+		}
+	}
+
+	wayland_client::event_created_child!(ClientState, ExtForeignToplevelListV1, [
+		ext_foreign_toplevel_list_v1::EVT_TOPLEVEL_OPCODE => (ExtForeignToplevelHandleV1, ()),
+	]);
+}
+
+impl Dispatch<ExtForeignToplevelHandleV1, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		handle: &ExtForeignToplevelHandleV1,
+		event: ext_foreign_toplevel_handle_v1::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		let Some(toplevel) = (state.toplevels.iter_mut()).find(|toplevel| toplevel.handle == *handle) else {
+			return;
+		};
+
+		match event {
+			ext_foreign_toplevel_handle_v1::Event::Title { title } => toplevel.title = title,
+			ext_foreign_toplevel_handle_v1::Event::AppId { app_id } => toplevel.app_id = app_id,
+			ext_foreign_toplevel_handle_v1::Event::Closed => toplevel.closed = true,
+			_ => {}
+		}
+	}
+}
+
+impl Dispatch<ZwlrOutputManagerV1, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		_manager: &ZwlrOutputManagerV1,
+		event: zwlr_output_manager_v1::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		match event {
+			zwlr_output_manager_v1::Event::Head { head } => state.heads.push(Head {
+				head,
+				name: String::new(),
+				position: (0, 0),
+				scale: 1.,
+			}),
+			zwlr_output_manager_v1::Event::Done { serial } => state.output_serial = serial,
+			_ => {}
+		}
+	}
+
+	wayland_client::event_created_child!(ClientState, ZwlrOutputManagerV1, [
+		zwlr_output_manager_v1::EVT_HEAD_OPCODE => (ZwlrOutputHeadV1, ()),
+	]);
+}
+
+impl Dispatch<ZwlrOutputHeadV1, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		head: &ZwlrOutputHeadV1,
+		event: zwlr_output_head_v1::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		if let zwlr_output_head_v1::Event::Finished = event {
+			state.heads.retain(|h| h.head != *head);
+			return;
+		}
+
+		let Some(h) = state.heads.iter_mut().find(|h| h.head == *head) else {
+			return;
+		};
+
+		match event {
+			zwlr_output_head_v1::Event::Name { name } => h.name = name,
+			zwlr_output_head_v1::Event::Position { x, y } => h.position = (x, y),
+			zwlr_output_head_v1::Event::Scale { scale } => h.scale = scale,
+			_ => {}
+		}
+	}
+
+	wayland_client::event_created_child!(ClientState, ZwlrOutputHeadV1, [
+		zwlr_output_head_v1::EVT_MODE_OPCODE => (ZwlrOutputModeV1, ()),
+	]);
+}
+
+impl Dispatch<ZwlrOutputConfigurationV1, ()> for ClientState {
+	fn event(
+		state: &mut Self,
+		_configuration: &ZwlrOutputConfigurationV1,
+		event: zwlr_output_configuration_v1::Event,
+		_data: &(),
+		_conn: &Connection,
+		_qh: &QueueHandle<Self>,
+	) {
+		state.configuration_result = match event {
+			zwlr_output_configuration_v1::Event::Succeeded => Some(ConfigurationResult::Succeeded),
+			zwlr_output_configuration_v1::Event::Failed => Some(ConfigurationResult::Failed),
+			zwlr_output_configuration_v1::Event::Cancelled => Some(ConfigurationResult::Cancelled),
+			_ => return,
+		};
+	}
+}
+
+delegate_noop!(ClientState: WlCompositor);
+delegate_noop!(ClientState: ignore WlSurface);
+delegate_noop!(ClientState: ignore WlShm);
+delegate_noop!(ClientState: WlShmPool);
+delegate_noop!(ClientState: ignore WlBuffer);
+delegate_noop!(ClientState: ignore WlOutput);
+delegate_noop!(ClientState: ignore WlSeat);
+delegate_noop!(ClientState: XdgPositioner);
+delegate_noop!(ClientState: ExtSessionLockManagerV1);
+delegate_noop!(ClientState: ignore ExtSessionLockV1);
+delegate_noop!(ClientState: ignore ZwlrOutputModeV1);
+delegate_noop!(ClientState: ZwlrOutputConfigurationHeadV1);
@@ -0,0 +1,167 @@
+use super::client::TestClient;
+use crate::{
+	backend::{Backend, headless::Headless},
+	shell::window::MappedWindow,
+	state::{ClientState, Mayland, State},
+};
+use mayland_config::{Config, bind::CompMod};
+use smithay::{
+	reexports::{
+		calloop::EventLoop,
+		wayland_server::{Client, Display, Resource},
+	},
+	wayland::seat::WaylandFocus,
+};
+use std::{
+	os::unix::net::UnixStream,
+	sync::{Arc, Once},
+	time::Duration,
+};
+use wayland_client::Proxy;
+
+/// an in-process compositor on the headless backend, with test clients
+/// connected over a socket pair
+pub struct Fixture {
+	event_loop: EventLoop<'static, State>,
+	pub state: State,
+	clients: Vec<(TestClient, Client)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientId(usize);
+
+impl Fixture {
+	pub fn new() -> Self {
+		Fixture::with_config(Config::default())
+	}
+
+	pub fn with_config(config: Config) -> Self {
+		ensure_runtime_dir();
+
+		let event_loop = EventLoop::try_new().unwrap();
+		let display = Display::new().unwrap();
+
+		let mut mayland = Mayland::with_config(&event_loop, display, config, CompMod::Meta);
+		let headless = Headless::init(&mut mayland);
+		let backend = Backend::Headless(headless);
+
+		let mut fixture = Fixture {
+			event_loop,
+			state: State { backend, mayland },
+			clients: Vec::new(),
+		};
+		fixture.dispatch_server();
+
+		fixture
+	}
+
+	/// connect a new test client and wait until it has received the globals
+	pub fn add_client(&mut self) -> ClientId {
+		let (client_stream, server_stream) = UnixStream::pair().unwrap();
+
+		let client = self
+			.state
+			.mayland
+			.display_handle
+			.insert_client(server_stream, Arc::new(ClientState::default()))
+			.unwrap();
+
+		let id = ClientId(self.clients.len());
+		self.clients.push((TestClient::new(client_stream), client));
+
+		// once for the globals and once for the binds
+		self.roundtrip(id);
+		self.roundtrip(id);
+
+		id
+	}
+
+	pub fn client(&mut self, id: ClientId) -> &mut TestClient {
+		&mut self.clients[id.0].0
+	}
+
+	/// step the event loop of the compositor once, without blocking
+	pub fn dispatch_server(&mut self) {
+		self.event_loop
+			.dispatch(Some(Duration::ZERO), &mut self.state)
+			.unwrap();
+		self.state.refresh_and_redraw();
+	}
+
+	/// dispatch the client and the compositor until the compositor
+	/// has processed all requests of the client, and the client has
+	/// received all the replies
+	pub fn roundtrip(&mut self, id: ClientId) {
+		self.client(id).sync();
+
+		for _ in 0..100 {
+			self.client(id).dispatch();
+			self.dispatch_server();
+
+			if self.client(id).is_synced() {
+				// dispatch the events that arrived together with the callback
+				self.client(id).dispatch();
+				return;
+			}
+		}
+
+		panic!("roundtrip timed out");
+	}
+
+	/// roundtrip every connected client
+	pub fn dispatch(&mut self) {
+		for idx in 0..self.clients.len() {
+			self.roundtrip(ClientId(idx));
+		}
+	}
+
+	/// create a window, wait for the initial configure and map it
+	///
+	/// returns the index of the window on the client
+	pub fn map_window(&mut self, id: ClientId) -> usize {
+		let idx = self.client(id).create_window();
+		self.roundtrip(id);
+
+		self.client(id).commit_buffer(idx);
+		self.roundtrip(id);
+
+		idx
+	}
+
+	/// get the [`MappedWindow`] on the compositor side of a client window
+	pub fn mapped_window(&self, id: ClientId, idx: usize) -> MappedWindow {
+		let (test_client, client) = &self.clients[id.0];
+		let surface_id = test_client.window(idx).surface.id().protocol_id();
+
+		self.state
+			.mayland
+			.workspaces
+			.windows()
+			.find(|window| {
+				window.wl_surface().is_some_and(|surface| {
+					surface.client().as_ref() == Some(client) && surface.id().protocol_id() == surface_id
+				})
+			})
+			.cloned()
+			.expect("window is not mapped")
+	}
+}
+
+/// the wayland and mayland sockets are created in the runtime dir,
+/// which isn't necessarily set in ci
+fn ensure_runtime_dir() {
+	static RUNTIME_DIR: Once = Once::new();
+
+	RUNTIME_DIR.call_once(|| {
+		if std::env::var_os("XDG_RUNTIME_DIR").is_some() {
+			return;
+		}
+
+		let runtime_dir = std::env::temp_dir().join(format!("mayland-test-{}", std::process::id()));
+		std::fs::create_dir_all(&runtime_dir).unwrap();
+
+		// SAFETY: this runs before any fixture is created, and no other
+		// test reads or writes the environment concurrently
+		unsafe { std::env::set_var("XDG_RUNTIME_DIR", runtime_dir) };
+	});
+}
@@ -0,0 +1,27 @@
+use super::fixture::Fixture;
+
+#[test]
+fn toplevels_are_published() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+	let taskbar = f.add_client();
+
+	let idx = f.map_window(id);
+	f.client(id).set_title(idx, "first");
+	f.roundtrip(id);
+	f.roundtrip(taskbar);
+
+	let toplevels = &f.client(taskbar).state.toplevels;
+	assert_eq!(toplevels.len(), 1);
+	assert_eq!(toplevels[0].title, "first");
+
+	f.client(id).set_title(idx, "second");
+	f.roundtrip(id);
+	f.roundtrip(taskbar);
+	assert_eq!(f.client(taskbar).state.toplevels[0].title, "second");
+
+	f.client(id).destroy_window(idx);
+	f.roundtrip(id);
+	f.roundtrip(taskbar);
+	assert!(f.client(taskbar).state.toplevels[0].closed);
+}
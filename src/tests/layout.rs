@@ -0,0 +1,443 @@
+use super::fixture::Fixture;
+use crate::{layout::workspace::WindowMode, utils::RectExt};
+use mayland_config::{Action, bind::CycleDirection};
+use smithay::utils::Point;
+use wayland_protocols::xdg::shell::client::xdg_toplevel;
+
+#[test]
+fn tile_windows() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let first = f.map_window(id);
+	let second = f.map_window(id);
+	let third = f.map_window(id);
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.windows().count(), 3);
+
+	let geometries = [first, second, third].map(|idx| {
+		let window = f.mapped_window(id, idx);
+		assert!(!workspace.is_floating(&window));
+		workspace.window_geometry(&window).unwrap()
+	});
+
+	// with the pointer at the top left, new windows become the master window
+	let [first, second, third] = geometries;
+	assert!(third.loc.x < first.loc.x);
+	assert!(third.loc.x < second.loc.x);
+	assert_eq!(first.loc.x, second.loc.x);
+
+	for (idx, geometry) in geometries.iter().enumerate() {
+		for other in &geometries[idx + 1..] {
+			assert!(!geometry.overlaps(*other), "{:?} overlaps {:?}", geometry, other);
+		}
+	}
+}
+
+#[test]
+fn tiled_windows_are_configured() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	f.dispatch();
+
+	let window = f.client(id).window(idx);
+	assert!(window.size.0 > 0 && window.size.1 > 0);
+	assert!(window.states.contains(&xdg_toplevel::State::TiledLeft));
+}
+
+#[test]
+fn remove_window() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let first = f.map_window(id);
+	let second = f.map_window(id);
+	f.dispatch();
+
+	let before = {
+		let window = f.mapped_window(id, first);
+		let workspace = f.state.mayland.workspaces.workspace().unwrap();
+		workspace.window_geometry(&window).unwrap()
+	};
+
+	f.client(id).destroy_window(second);
+	f.dispatch();
+
+	let window = f.mapped_window(id, first);
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.windows().count(), 1);
+
+	// the remaining window takes up the entire output
+	let after = workspace.window_geometry(&window).unwrap();
+	assert!(after.size.w > before.size.w);
+}
+
+#[test]
+fn non_resizable_windows_float() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.client(id).create_window();
+	f.client(id).set_fixed_size(idx, 200, 100);
+	f.roundtrip(id);
+	f.client(id).commit_buffer(idx);
+	f.roundtrip(id);
+
+	let window = f.mapped_window(id, idx);
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert!(workspace.is_floating(&window));
+}
+
+#[test]
+fn toggle_floating() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	f.state.handle_action(Action::ToggleFloating).unwrap();
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert!(workspace.is_floating(&window));
+
+	f.state.handle_action(Action::ToggleFloating).unwrap();
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert!(!workspace.is_floating(&window));
+}
+
+#[test]
+fn switch_to_workspace() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	f.state.handle_action(Action::Workspace(1)).unwrap();
+	f.dispatch();
+
+	let workspaces = &f.state.mayland.workspaces;
+	let active = workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 1);
+	assert!(active.is_empty());
+	assert_eq!(workspaces.workspaces().count(), 2);
+
+	let focus = f.state.mayland.keyboard.current_focus();
+	assert!(focus.is_none());
+
+	f.state.handle_action(Action::Workspace(0)).unwrap();
+	f.dispatch();
+
+	// the empty workspace gets cleaned up
+	let workspaces = &f.state.mayland.workspaces;
+	let active = workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 0);
+	assert!(active.has_window(&window));
+	assert_eq!(workspaces.workspaces().count(), 1);
+}
+
+#[test]
+fn cycle_window() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let first = f.map_window(id);
+	let second = f.map_window(id);
+
+	let first = f.mapped_window(id, first);
+	let second = f.mapped_window(id, second);
+
+	// new windows get focussed automatically
+	let focus = f.state.mayland.keyboard.current_focus().unwrap();
+	assert!(focus == second);
+
+	f.state
+		.handle_action(Action::Cycle(CycleDirection::Next))
+		.unwrap();
+	f.dispatch();
+
+	let focus = f.state.mayland.keyboard.current_focus().unwrap();
+	assert!(focus == first);
+
+	f.state
+		.handle_action(Action::Cycle(CycleDirection::Prev))
+		.unwrap();
+	f.dispatch();
+
+	let focus = f.state.mayland.keyboard.current_focus().unwrap();
+	assert!(focus == second);
+}
+
+#[test]
+fn cycle_workspace() {
+	let mut f = Fixture::new();
+
+	// there is no workspace before the first one
+	f.state
+		.handle_action(Action::CycleWorkspace(CycleDirection::Prev))
+		.unwrap();
+	f.dispatch();
+
+	let active = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 0);
+
+	f.state
+		.handle_action(Action::CycleWorkspace(CycleDirection::Next))
+		.unwrap();
+	f.dispatch();
+
+	let active = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 1);
+
+	f.state
+		.handle_action(Action::CycleWorkspace(CycleDirection::Prev))
+		.unwrap();
+	f.dispatch();
+
+	let active = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 0);
+}
+
+#[test]
+fn swap_tiled_windows() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let first = f.map_window(id);
+	let second = f.map_window(id);
+	f.dispatch();
+
+	let first = f.mapped_window(id, first);
+	let second = f.mapped_window(id, second);
+
+	let workspaces = &mut f.state.mayland.workspaces;
+	let output_position = workspaces.active_output_position().unwrap();
+
+	let workspace = workspaces.workspace().unwrap();
+	let first_geometry = workspace.window_geometry(&first).unwrap();
+	let second_geometry = workspace.window_geometry(&second).unwrap();
+
+	// moving a window onto itself doesn't change the layout
+	let location = (first_geometry.center() + output_position).to_f64();
+	assert!(!workspaces.swap_tiled(&first, location));
+
+	let location = (second_geometry.center() + output_position).to_f64();
+	assert!(workspaces.swap_tiled(&first, location));
+
+	let workspace = workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_geometry(&first), Some(second_geometry));
+	assert_eq!(workspace.window_geometry(&second), Some(first_geometry));
+}
+
+#[test]
+fn floating_window_covers_split() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let first = f.map_window(id);
+	let second = f.map_window(id);
+	f.dispatch();
+
+	let first = f.mapped_window(id, first);
+	let second = f.mapped_window(id, second);
+
+	let split = {
+		let workspace = f.state.mayland.workspaces.workspace().unwrap();
+		let [first, second] = [first, second].map(|window| workspace.window_geometry(&window).unwrap());
+		let (left, right) = if first.loc.x < second.loc.x {
+			(first, second)
+		} else {
+			(second, first)
+		};
+		Point::new((left.loc.x + left.size.w + right.loc.x) / 2, left.center().y)
+	};
+
+	let output_position = f.state.mayland.workspaces.active_output_position().unwrap();
+	let location = (split + output_position).to_f64();
+	assert!(f.state.mayland.workspaces.split_under(location));
+
+	let idx = f.client(id).create_window();
+	f.client(id).set_fixed_size(idx, 200, 100);
+	f.roundtrip(id);
+	f.client(id).commit_buffer(idx);
+	f.roundtrip(id);
+
+	let window = f.mapped_window(id, idx);
+	let workspace = f.state.mayland.workspaces.workspace_mut().unwrap();
+	workspace.floating_move(window, split - Point::new(10, 10));
+
+	assert!(!f.state.mayland.workspaces.split_under(location));
+}
+
+#[test]
+fn close_window() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+
+	f.state.handle_action(Action::CloseWindow).unwrap();
+	f.dispatch();
+
+	assert!(f.client(id).window(idx).close_requested);
+}
+
+#[test]
+fn move_to_workspace() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let first = f.map_window(id);
+	let second = f.map_window(id);
+
+	let first = f.mapped_window(id, first);
+	let second = f.mapped_window(id, second);
+
+	f.state.handle_action(Action::MoveToWorkspace(2)).unwrap();
+	f.dispatch();
+
+	let workspaces = &f.state.mayland.workspaces;
+	let active = workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 0);
+	assert!(active.has_window(&first));
+	assert!(!active.has_window(&second));
+
+	let target = workspaces.workspaces().find(|ws| ws.idx == 2).unwrap();
+	assert!(target.has_window(&second));
+	assert!(!target.is_floating(&second));
+
+	// the focus stays on the active workspace
+	let focus = f.state.mayland.keyboard.current_focus();
+	assert!(focus.is_none_or(|focus| focus != second));
+}
+
+#[test]
+fn move_to_workspace_and_follow() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	f.state.handle_action(Action::ToggleFloating).unwrap();
+	f.dispatch();
+
+	f.state
+		.handle_action(Action::MoveToWorkspaceAndFollow(1))
+		.unwrap();
+	f.dispatch();
+
+	// the empty workspace gets cleaned up
+	let workspaces = &f.state.mayland.workspaces;
+	let active = workspaces.workspace().unwrap();
+	assert_eq!(active.idx, 1);
+	assert!(active.is_floating(&window));
+	assert_eq!(workspaces.workspaces().count(), 1);
+
+	let focus = f.state.mayland.keyboard.current_focus().unwrap();
+	assert!(focus == window);
+}
+
+#[test]
+fn toggle_fullscreen() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	f.map_window(id);
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	f.state.handle_action(Action::ToggleFullscreen).unwrap();
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), Some(WindowMode::Fullscreen));
+
+	let client_window = f.client(id).window(idx);
+	assert_eq!(client_window.size, (1920, 1080));
+	assert!(client_window.states.contains(&xdg_toplevel::State::Fullscreen));
+
+	f.state.handle_action(Action::ToggleFullscreen).unwrap();
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), None);
+	assert!(!workspace.is_floating(&window));
+
+	let client_window = f.client(id).window(idx);
+	assert!(client_window.size.0 < 1920);
+	assert!(!client_window.states.contains(&xdg_toplevel::State::Fullscreen));
+}
+
+#[test]
+fn toggle_maximize() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	f.state.handle_action(Action::ToggleFloating).unwrap();
+	f.state.handle_action(Action::ToggleMaximize).unwrap();
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), Some(WindowMode::Maximized));
+	assert!(
+		f.client(id)
+			.window(idx)
+			.states
+			.contains(&xdg_toplevel::State::Maximized)
+	);
+
+	// the window goes back to floating
+	f.state.handle_action(Action::ToggleMaximize).unwrap();
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), None);
+	assert!(workspace.is_floating(&window));
+}
+
+#[test]
+fn fullscreen_request() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	f.client(id).set_fullscreen(idx, true);
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), Some(WindowMode::Fullscreen));
+
+	// new windows leave fullscreen, so that they aren't hidden
+	f.map_window(id);
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), None);
+
+	f.client(id).set_fullscreen(idx, true);
+	f.dispatch();
+	f.client(id).set_fullscreen(idx, false);
+	f.dispatch();
+
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	assert_eq!(workspace.window_mode(&window), None);
+	assert!(
+		!f.client(id)
+			.window(idx)
+			.states
+			.contains(&xdg_toplevel::State::Fullscreen)
+	);
+}
@@ -0,0 +1,92 @@
+use super::{client::ConfigurationResult, fixture::Fixture};
+use smithay::utils::Size;
+
+#[test]
+fn apply_configuration() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let heads = &f.client(id).state.heads;
+	assert_eq!(heads.len(), 1);
+	assert_eq!(heads[0].name, "headless-1");
+	assert_eq!(heads[0].scale, 1.);
+
+	f.client(id).configure_outputs(|_, head| head.set_scale(2.));
+	f.roundtrip(id);
+	assert_eq!(
+		f.client(id).state.configuration_result,
+		Some(ConfigurationResult::Succeeded)
+	);
+
+	let output = f.state.mayland.workspaces.output_by_name("headless-1").unwrap();
+	assert_eq!(output.current_scale().fractional_scale(), 2.);
+
+	// the new state is sent to the client
+	f.roundtrip(id);
+	assert_eq!(f.client(id).state.heads[0].scale, 2.);
+}
+
+#[test]
+fn overlapping_outputs_fail() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	f.state
+		.backend
+		.headless()
+		.add_output(&mut f.state.mayland, Size::new(1920, 1080));
+	f.roundtrip(id);
+
+	let heads = &f.client(id).state.heads;
+	assert_eq!(heads.len(), 2);
+	assert_eq!(heads[1].position, (1920, 0));
+
+	f.client(id).configure_outputs(|_, head| head.set_position(0, 0));
+	f.roundtrip(id);
+	assert_eq!(
+		f.client(id).state.configuration_result,
+		Some(ConfigurationResult::Failed)
+	);
+
+	// nothing was changed
+	let output = f.state.mayland.workspaces.output_by_name("headless-2").unwrap();
+	assert_eq!(output.current_location(), (1920, 0).into());
+}
+
+#[test]
+fn configure_output() {
+	let mut f = Fixture::new();
+	f.state
+		.backend
+		.headless()
+		.add_output(&mut f.state.mayland, Size::new(1920, 1080));
+	f.dispatch_server();
+
+	f.state
+		.configure_output("headless-1", |config| config.position = Some([0, 0]))
+		.unwrap();
+	f.state
+		.configure_output("headless-2", |config| config.scale = Some(2.))
+		.unwrap();
+
+	let output = f
+		.state
+		.mayland
+		.workspaces
+		.output_by_name("headless-2")
+		.cloned()
+		.unwrap();
+	assert_eq!(output.current_scale().fractional_scale(), 2.);
+	assert_eq!(output.current_location(), (1920, 0).into());
+
+	// overlapping outputs are rolled back
+	let ret = f
+		.state
+		.configure_output("headless-2", |config| config.position = Some([100, 0]));
+	assert!(matches!(ret, Err(mayland_comm::Error::InvalidOutputConfig(_))));
+	assert_eq!(output.current_location(), (1920, 0).into());
+	assert_eq!(output.current_scale().fractional_scale(), 2.);
+
+	let ret = f.state.configure_output("headless-3", |_| {});
+	assert!(matches!(ret, Err(mayland_comm::Error::OutputNotFound(_))));
+}
@@ -0,0 +1,130 @@
+use super::fixture::Fixture;
+use crate::{
+	shell::{focus::KeyboardFocusTarget, window::MappedWindow},
+	utils::{RectExt, output_size},
+};
+use mayland_config::bind::BTN_LEFT;
+use smithay::{
+	backend::input::ButtonState,
+	input::pointer::{ButtonEvent, MotionEvent},
+	utils::SERIAL_COUNTER,
+};
+use wayland_protocols::xdg::shell::client::xdg_positioner::{
+	Anchor, ConstraintAdjustment, Gravity, XdgPositioner,
+};
+
+#[test]
+fn popups_are_unconstrained() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let window = f.map_window(id);
+	f.dispatch();
+
+	// a popup that would open far outside of the output
+	let popup = f.client(id).create_popup(window, |positioner| {
+		positioner.set_size(200, 100);
+		positioner.set_anchor_rect(0, 0, 1, 1);
+		positioner.set_anchor(Anchor::BottomRight);
+		positioner.set_gravity(Gravity::BottomRight);
+		positioner.set_offset(10000, 10000);
+		positioner.set_constraint_adjustment(ConstraintAdjustment::SlideX | ConstraintAdjustment::SlideY);
+	});
+	f.roundtrip(id);
+
+	let mapped = f.mapped_window(id, window);
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	let window_geometry = workspace.window_geometry(&mapped).unwrap();
+	let output_size = output_size(workspace.output.as_ref().unwrap());
+
+	let (x, y, w, h) = f.client(id).popup(popup).geometry;
+	assert_eq!((w, h), (200, 100));
+	assert!(window_geometry.loc.x + x + w <= output_size.w);
+	assert!(window_geometry.loc.y + y + h <= output_size.h);
+}
+
+#[test]
+fn popup_closes_on_outside_click() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+	let other = f.add_client();
+
+	let window = f.map_window(id);
+	let other_window = f.map_window(other);
+	let mapped = f.mapped_window(id, window);
+	f.state.focus_window(mapped);
+	f.dispatch();
+
+	let popup = f.client(id).create_grabbing_popup(window, menu);
+	f.roundtrip(id);
+	assert!(!f.client(id).popup(popup).done);
+
+	let keyboard_focus = f.state.mayland.keyboard.current_focus();
+	assert!(matches!(keyboard_focus, Some(KeyboardFocusTarget::Popup(_))));
+
+	// clicking the window of another client dismisses the popup
+	let other_mapped = f.mapped_window(other, other_window);
+	click(&mut f, &other_mapped);
+	f.roundtrip(id);
+	assert!(f.client(id).popup(popup).done);
+}
+
+#[test]
+fn popup_grab_is_refused_while_locked() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let window = f.map_window(id);
+
+	let locker = f.add_client();
+	f.client(locker).lock();
+	f.roundtrip(locker);
+	f.client(locker).commit_lock_buffer();
+	f.dispatch();
+	assert!(f.state.mayland.is_locked());
+
+	let popup = f.client(id).create_grabbing_popup(window, menu);
+	f.roundtrip(id);
+	assert!(f.client(id).popup(popup).done);
+
+	// the lock surface keeps the keyboard focus
+	let keyboard_focus = f.state.mayland.keyboard.current_focus();
+	assert!(matches!(
+		keyboard_focus,
+		Some(KeyboardFocusTarget::LockSurface(_))
+	));
+}
+
+fn menu(positioner: &XdgPositioner) {
+	positioner.set_size(200, 100);
+	positioner.set_anchor_rect(0, 0, 1, 1);
+}
+
+/// press the left mouse button in the center of the window
+fn click(f: &mut Fixture, window: &MappedWindow) {
+	let workspace = f.state.mayland.workspaces.workspace().unwrap();
+	let location = workspace.window_geometry(window).unwrap().center().to_f64();
+	let under = f.state.surface_under(location);
+
+	let pointer = f.state.mayland.pointer.clone();
+	let serial = SERIAL_COUNTER.next_serial();
+	pointer.motion(
+		&mut f.state,
+		under,
+		&MotionEvent {
+			location,
+			serial,
+			time: 0,
+		},
+	);
+	pointer.button(
+		&mut f.state,
+		&ButtonEvent {
+			serial,
+			time: 0,
+			button: BTN_LEFT,
+			state: ButtonState::Pressed,
+		},
+	);
+	pointer.frame(&mut f.state);
+}
@@ -0,0 +1,38 @@
+use super::fixture::Fixture;
+use crate::shell::{focus::KeyboardFocusTarget, window::MappedWindow};
+use wayland_client::Proxy;
+
+#[test]
+fn lock_takes_focus() {
+	let mut f = Fixture::new();
+	let id = f.add_client();
+
+	let idx = f.map_window(id);
+	let window = f.mapped_window(id, idx);
+
+	let locker = f.add_client();
+	f.client(locker).lock();
+	f.roundtrip(locker);
+
+	// the lock surface is configured with the size of the output
+	let lock = f.client(locker).state.lock.as_ref().unwrap();
+	assert_eq!(lock.size, (1920, 1080));
+	assert!(lock.lock.is_alive());
+
+	f.client(locker).commit_lock_buffer();
+	f.dispatch();
+
+	assert!(f.state.mayland.is_locked());
+	assert_lock_focus(&f, &window);
+
+	// new windows can't steal the focus from the lock surface
+	let idx = f.map_window(id);
+	let new = f.mapped_window(id, idx);
+	assert_lock_focus(&f, &new);
+}
+
+fn assert_lock_focus(f: &Fixture, window: &MappedWindow) {
+	let focus = f.state.mayland.keyboard.current_focus().unwrap();
+	assert!(matches!(focus, KeyboardFocusTarget::LockSurface(_)));
+	assert!(focus != *window);
+}
@@ -1,7 +1,12 @@
 use crate::state::Mayland;
 use smithay::{
-	output::Output,
+	output::{self, Output},
+	reexports::wayland_server::protocol::wl_surface::WlSurface,
 	utils::{Logical, Point, Rectangle, Size, Transform},
+	wayland::{
+		compositor::{SurfaceData, send_surface_state},
+		fractional_scale::with_fractional_scale,
+	},
 };
 use std::{
 	os::unix::process::CommandExt,
@@ -90,13 +95,44 @@ impl<Kind> SizeExt<i32, Kind> for Size<i32, Kind> {
 }
 
 pub fn output_size(output: &Output) -> Size<i32, Logical> {
-	let output_scale = output.current_scale().integer_scale();
+	let output_scale = output.current_scale().fractional_scale();
 	let output_mode = output.current_mode().unwrap();
 	let output_transform = output.current_transform();
 
 	output_transform
 		.transform_size(output_mode.size)
+		.to_f64()
 		.to_logical(output_scale)
+		.to_i32_round()
+}
+
+/// get the configured [`output::Scale`] of an output
+///
+/// the scale is rounded to a multiple of 1/120, as that is
+/// the precision of the fractional scale protocol
+pub fn output_scale(config: Option<&mayland_config::outputs::Output>) -> output::Scale {
+	let scale = config.and_then(|conf| conf.scale).unwrap_or(1.);
+	let scale = (scale.clamp(0.25, 10.) * 120.).round() / 120.;
+
+	output::Scale::Fractional(scale)
+}
+
+/// the transform of an output, defaults to normal
+pub fn output_transform(config: Option<&mayland_config::outputs::Output>) -> Transform {
+	Transform::from(config.map(|conf| conf.transform).unwrap_or_default())
+}
+
+/// send the preferred scale and transform of an output to a surface
+pub fn send_scale_transform(
+	surface: &WlSurface,
+	data: &SurfaceData,
+	scale: output::Scale,
+	transform: Transform,
+) {
+	send_surface_state(surface, data, scale.integer_scale(), transform);
+	with_fractional_scale(data, |fractional| {
+		fractional.set_preferred_scale(scale.fractional_scale());
+	});
 }
 
 pub fn logical_output(output: &Output) -> mayland_comm::output::Logical {
@@ -120,6 +156,7 @@ pub fn logical_output(output: &Output) -> mayland_comm::output::Logical {
 		w: size.w,
 		h: size.h,
 		transform,
+		scale: output.current_scale().fractional_scale(),
 	}
 }
 